@@ -3,9 +3,9 @@
 use shared::{
     errors::Error,
     events::{
-        ASSET_WRAPPED, BRIDGE_DEPOSIT, BRIDGE_INITIALIZED, BRIDGE_PAUSED, BRIDGE_TX_CONFIRMED,
-        BRIDGE_UNPAUSED, BRIDGE_WITHDRAW, RELAYER_ADDED, RELAYER_REMOVED, SUPPORTED_CHAIN_ADDED,
-        SUPPORTED_CHAIN_REMOVED,
+        publish, AssetWrapped, BridgeDeposit, BridgeInitialized, BridgePaused, BridgeTxConfirmed,
+        BridgeUnpaused, BridgeWithdraw, RelayerAdded, RelayerRemoved, SupportedChainAdded,
+        SupportedChainRemoved,
     },
     types::{
         Amount, BridgeConfig, BridgeOperationType, BridgeTransaction, BridgeTransactionStatus,
@@ -61,7 +61,7 @@ impl CrossChainBridge {
         set_config(&env, &config);
         set_transaction_counter(&env, 0);
 
-        env.events().publish((BRIDGE_INITIALIZED,), (admin,));
+        publish(&env, BridgeInitialized { admin });
 
         Ok(())
     }
@@ -93,7 +93,7 @@ impl CrossChainBridge {
         }
 
         let chain_config = ChainConfig {
-            chain_id: chain_id.clone(),
+            chain_id,
             name,
             bridge_contract_address: bridge_contract.clone(),
             confirmations_required,
@@ -103,9 +103,12 @@ impl CrossChainBridge {
 
         set_chain_config(&env, chain_id, &chain_config);
 
-        env.events().publish(
-            (SUPPORTED_CHAIN_ADDED,),
-            (chain_id as u32, bridge_contract),
+        publish(
+            &env,
+            SupportedChainAdded {
+                chain_id,
+                bridge_contract,
+            },
         );
 
         Ok(())
@@ -121,8 +124,7 @@ impl CrossChainBridge {
 
         set_chain_config(&env, chain_id, &chain_config);
 
-        env.events()
-            .publish((SUPPORTED_CHAIN_REMOVED,), (chain_id as u32,));
+        publish(&env, SupportedChainRemoved { chain_id });
 
         Ok(())
     }
@@ -158,7 +160,7 @@ impl CrossChainBridge {
         let asset = WrappedAssetInfo {
             asset_code: asset_code.clone(),
             issuer: issuer.clone(),
-            original_chain: original_chain.clone(),
+            original_chain,
             original_contract: original_contract.clone(),
             decimals,
             is_active: true,
@@ -169,9 +171,13 @@ impl CrossChainBridge {
         set_wrapped_asset(&env, issuer.clone(), &asset);
         set_asset_by_original(&env, original_chain, &original_contract, &issuer);
 
-        env.events().publish(
-            (ASSET_WRAPPED,),
-            (asset_code, issuer.clone(), original_chain as u32),
+        publish(
+            &env,
+            AssetWrapped {
+                asset_code,
+                issuer: issuer.clone(),
+                original_chain,
+            },
         );
 
         Ok(issuer)
@@ -229,7 +235,7 @@ impl CrossChainBridge {
 
         let transaction = BridgeTransaction {
             tx_id,
-            source_chain: source_chain.clone(),
+            source_chain,
             destination_chain: source_chain, // For deposits, the destination is conceptually the same chain (informational)
             operation: BridgeOperationType::Deposit,
             sender,
@@ -259,13 +265,24 @@ impl CrossChainBridge {
         // by calling an authorized token contract with proper authentication.
         // For this implementation, we track the deposits internally.
 
-        env.events().publish(
-            (BRIDGE_DEPOSIT,),
-            (tx_id, recipient, asset, amount, source_tx_hash),
+        publish(
+            &env,
+            BridgeDeposit {
+                tx_id,
+                recipient,
+                asset,
+                amount,
+                source_tx_hash: source_tx_hash.clone(),
+            },
         );
 
-        env.events()
-            .publish((BRIDGE_TX_CONFIRMED,), (tx_id, tx_id));
+        publish(
+            &env,
+            BridgeTxConfirmed {
+                tx_id,
+                external_tx_hash: source_tx_hash,
+            },
+        );
 
         Ok(tx_id)
     }
@@ -330,7 +347,7 @@ impl CrossChainBridge {
         let transaction = BridgeTransaction {
             tx_id,
             source_chain: ChainId::Ethereum, // Placeholder
-            destination_chain: destination_chain.clone(),
+            destination_chain,
             operation: BridgeOperationType::Withdraw,
             sender: BytesN::from_array(&env, &[0u8; 32]), // Placeholder
             recipient: sender.clone(),
@@ -354,9 +371,15 @@ impl CrossChainBridge {
             .ok_or(Error::InvalidInput)?;
         set_wrapped_asset(&env, asset.clone(), &wrapped_asset);
 
-        env.events().publish(
-            (BRIDGE_WITHDRAW,),
-            (tx_id, destination_chain as u32, recipient, asset, amount),
+        publish(
+            &env,
+            BridgeWithdraw {
+                tx_id,
+                destination_chain,
+                recipient,
+                asset,
+                amount,
+            },
         );
 
         Ok(tx_id)
@@ -398,8 +421,13 @@ impl CrossChainBridge {
 
         set_transaction(&env, tx_id, &transaction);
 
-        env.events()
-            .publish((BRIDGE_TX_CONFIRMED,), (tx_id, destination_tx_hash));
+        publish(
+            &env,
+            BridgeTxConfirmed {
+                tx_id,
+                external_tx_hash: destination_tx_hash,
+            },
+        );
 
         Ok(())
     }
@@ -433,7 +461,7 @@ impl CrossChainBridge {
 
         set_relayer(&env, &relayer, &relayer_info);
 
-        env.events().publish((RELAYER_ADDED,), (relayer, stake));
+        publish(&env, RelayerAdded { relayer, stake });
 
         Ok(())
     }
@@ -451,7 +479,7 @@ impl CrossChainBridge {
 
         set_relayer(&env, &relayer, &relayer_info);
 
-        env.events().publish((RELAYER_REMOVED,), (relayer,));
+        publish(&env, RelayerRemoved { relayer });
 
         Ok(())
     }
@@ -466,7 +494,12 @@ impl CrossChainBridge {
 
         set_config(&env, &new_config);
 
-        env.events().publish((BRIDGE_PAUSED,), ());
+        publish(
+            &env,
+            BridgePaused {
+                admin: new_config.admin,
+            },
+        );
 
         Ok(())
     }
@@ -481,7 +514,12 @@ impl CrossChainBridge {
 
         set_config(&env, &new_config);
 
-        env.events().publish((BRIDGE_UNPAUSED,), ());
+        publish(
+            &env,
+            BridgeUnpaused {
+                admin: new_config.admin,
+            },
+        );
 
         Ok(())
     }
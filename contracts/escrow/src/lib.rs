@@ -1,15 +1,25 @@
 #![no_std]
 
 use shared::{
-    constants::{
-        ESCROW_INITIALIZED, MILESTONE_APPROVAL_THRESHOLD, MILESTONE_APPROVED, MILESTONE_CREATED,
-        MILESTONE_REJECTED, MILESTONE_SUBMITTED, MIN_VALIDATORS,
-    },
+    constants::{MILESTONE_APPROVAL_THRESHOLD, MIN_VALIDATORS},
     errors::Error,
     events::*,
-    types::{Amount, EscrowInfo, Hash, Milestone, MilestoneStatus},
+    interfaces::{ProjectLaunchClient, VaultClient},
+    types::{
+        Amount, ArbitratorVote, BackerVotingConfig, BasisPoints, BasketAmount, Dispute,
+        DisputeConfig, DisputeStatus, EscrowInfo, EscrowSummary, Hash, Milestone, MilestonePage,
+        MilestoneStatus, ProjectStatus, Roadmap, RoadmapItem, TokenAmount, TokenBalance, Tranche,
+        ValidatorBond, ValidatorChangeProposal, ValidatorRegistryConfig, ValidatorVote, VotingMode,
+        YieldConfig, YieldPosition,
+    },
+    utils::{calculate_percentage, validate_basis_points},
+};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractimpl,
+    token::TokenClient,
+    vec, Address, BytesN, Env, IntoVal, Symbol, Vec,
 };
-use soroban_sdk::{contract, contractimpl, token::TokenClient, Address, BytesN, Env, Vec};
 
 mod storage;
 mod validation;
@@ -18,6 +28,20 @@ mod validation;
 mod tests;
 
 use storage::*;
+use validation::{
+    backer_vote_passes, validate_backer_voting_config, validate_bond, validate_dependencies,
+    validate_dispute_config, validate_tranches, validate_validator,
+    validate_validator_registry_config,
+};
+
+/// A whole milestone, in basis points
+const FULL_SHARE: BasisPoints = 10000;
+
+/// Maximum number of milestones returned by `list_milestones`
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Maximum number of tokens besides the primary one an escrow can hold
+const MAX_BASKET_TOKENS: u32 = 10;
 
 #[contract]
 pub struct EscrowContract;
@@ -51,10 +75,13 @@ impl EscrowContract {
         creator.require_auth();
 
         // Validate inputs
-        if (validators.len() as u32) < MIN_VALIDATORS {
+        if validators.len() < MIN_VALIDATORS {
             return Err(Error::InvalidInput);
         }
 
+        // Validators must be bonded once the registry is configured
+        require_bonded_validators(&env, &validators)?;
+
         // Check if escrow already exists
         if escrow_exists(&env, project_id) {
             return Err(Error::AlreadyInitialized);
@@ -68,6 +95,8 @@ impl EscrowContract {
             total_deposited: 0,
             released_amount: 0,
             validators,
+            voting_mode: VotingMode::Validators,
+            tokens: Vec::new(&env),
         };
 
         // Store escrow
@@ -77,8 +106,88 @@ impl EscrowContract {
         set_milestone_counter(&env, project_id, 0);
 
         // Emit event
-        env.events()
-            .publish((ESCROW_INITIALIZED,), (project_id, creator, token));
+        publish(
+            &env,
+            EscrowInitialized {
+                project_id,
+                creator,
+                token,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Initialize an escrow whose milestones are voted on by the project's backers
+    ///
+    /// Each backer's vote is weighted by their contribution recorded in the
+    /// ProjectLaunch contract referenced by `config`.
+    ///
+    /// # Arguments
+    /// * `project_id` - Unique project identifier (same id as in ProjectLaunch)
+    /// * `creator` - Address of the project creator
+    /// * `token` - Token address for the escrow
+    /// * `config` - Quorum, approval threshold and voting period for milestone votes
+    pub fn initialize_backer_voting(
+        env: Env,
+        project_id: u64,
+        creator: Address,
+        token: Address,
+        config: BackerVotingConfig,
+    ) -> Result<(), Error> {
+        creator.require_auth();
+
+        // Validate inputs
+        validate_backer_voting_config(&config)?;
+
+        // Check if escrow already exists
+        if escrow_exists(&env, project_id) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        // Backers of a funded project, as recorded by the admin-set
+        // ProjectLaunch, vote on its creator's milestones
+        let project_launch = get_project_launch(&env)?;
+        let project =
+            match ProjectLaunchClient::new(&env, &project_launch).try_get_project(&project_id) {
+                Ok(Ok(project)) => project,
+                _ => return Err(Error::ProjectNotFound),
+            };
+        if project.creator != creator {
+            return Err(Error::Unauthorized);
+        }
+        if project.status != ProjectStatus::Completed {
+            return Err(Error::FundingGoalNotReached);
+        }
+
+        // Create escrow info
+        let escrow = EscrowInfo {
+            project_id,
+            creator: creator.clone(),
+            token: token.clone(),
+            total_deposited: 0,
+            released_amount: 0,
+            validators: Vec::new(&env),
+            voting_mode: VotingMode::Backers,
+            tokens: Vec::new(&env),
+        };
+
+        // Store escrow and voting parameters
+        set_escrow(&env, project_id, &escrow);
+        set_backer_voting_config(&env, project_id, &config);
+
+        // Initialize milestone counter
+        set_milestone_counter(&env, project_id, 0);
+
+        // Emit event
+        publish(
+            &env,
+            EscrowInitialized {
+                project_id,
+                creator,
+                token,
+            },
+        );
 
         Ok(())
     }
@@ -107,7 +216,65 @@ impl EscrowContract {
         set_escrow(&env, project_id, &escrow);
 
         // Emit event
-        env.events().publish((FUNDS_LOCKED,), (project_id, amount));
+        publish(&env, FundsLocked { project_id, amount });
+
+        Ok(())
+    }
+
+    /// Deposit funds in any token into the escrow
+    ///
+    /// Deposits in the escrow's primary token are the same as `deposit`; other
+    /// tokens are tracked separately and can fund basket milestones. Adding a
+    /// token to the basket requires the creator's authorization and at most
+    /// `MAX_BASKET_TOKENS` tokens can be added.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `token` - Token deposited
+    /// * `amount` - Amount to deposit (note: actual token transfer would be handled separately)
+    pub fn deposit_token(
+        env: Env,
+        project_id: u64,
+        token: Address,
+        amount: Amount,
+    ) -> Result<(), Error> {
+        let mut escrow = get_escrow(&env, project_id)?;
+        if token == escrow.token {
+            return Self::deposit(env, project_id, amount);
+        }
+
+        if amount <= 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        let is_new = !escrow.tokens.contains(&token);
+        if is_new {
+            escrow.creator.require_auth();
+            if escrow.tokens.len() >= MAX_BASKET_TOKENS {
+                return Err(Error::InvalidInput);
+            }
+        }
+
+        let mut balance = get_token_balance(&env, project_id, &token);
+        balance.total_deposited = balance
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(Error::InvalidInput)?;
+        set_token_balance(&env, project_id, &balance);
+
+        if is_new {
+            escrow.tokens.push_back(token.clone());
+            set_escrow(&env, project_id, &escrow);
+        }
+
+        publish(
+            &env,
+            TokenFundsLocked {
+                project_id,
+                token,
+                amount,
+            },
+        );
 
         Ok(())
     }
@@ -118,11 +285,13 @@ impl EscrowContract {
     /// * `project_id` - Project identifier
     /// * `description_hash` - Hash of the milestone description
     /// * `amount` - Amount to be released when milestone is approved
+    /// * `dependencies` - Earlier milestones that must be approved before this one is submitted
     pub fn create_milestone(
         env: Env,
         project_id: u64,
         description_hash: Hash,
         amount: Amount,
+        dependencies: Vec<u64>,
     ) -> Result<(), Error> {
         // Get escrow to verify it exists and get creator
         let escrow = get_escrow(&env, project_id)?;
@@ -133,45 +302,80 @@ impl EscrowContract {
             return Err(Error::InvalidInput);
         }
 
-        // Validate that total milestone amounts don't exceed escrow total
-        let total_milestones = get_total_milestone_amount(&env, project_id)?;
-        let new_total = total_milestones
-            .checked_add(amount)
-            .ok_or(Error::InvalidInput)?;
+        add_milestone(
+            &env,
+            &escrow,
+            description_hash,
+            amount,
+            Vec::new(&env),
+            dependencies,
+        )
+    }
 
-        if new_total > escrow.total_deposited {
-            return Err(Error::InsufficientEscrowBalance);
+    /// Create a milestone paid out in several of the escrow's tokens
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `description_hash` - Hash of the milestone description
+    /// * `amounts` - Amount of each token released when the milestone is approved
+    /// * `dependencies` - Earlier milestones that must be approved before this one is submitted
+    pub fn create_basket_milestone(
+        env: Env,
+        project_id: u64,
+        description_hash: Hash,
+        amounts: Vec<TokenAmount>,
+        dependencies: Vec<u64>,
+    ) -> Result<(), Error> {
+        let escrow = get_escrow(&env, project_id)?;
+        escrow.creator.require_auth();
+
+        if amounts.is_empty() {
+            return Err(Error::InvalidInput);
         }
 
-        // Get next milestone ID
-        let milestone_id = get_milestone_counter(&env, project_id)?;
-        let next_id = milestone_id.checked_add(1).ok_or(Error::InvalidInput)?;
+        let mut amount: Amount = 0;
+        let mut basket: Vec<BasketAmount> = Vec::new(&env);
+        for (index, entry) in amounts.iter().enumerate() {
+            let repeated = amounts
+                .iter()
+                .take(index)
+                .any(|earlier| earlier.token == entry.token);
+            if entry.amount <= 0 || repeated {
+                return Err(Error::InvalidInput);
+            }
+
+            if entry.token == escrow.token {
+                amount = entry.amount;
+                continue;
+            }
+            if !escrow.tokens.contains(&entry.token) {
+                return Err(Error::InvalidInput);
+            }
+
+            // Each token's milestones must be covered by its own deposits
+            let allocated = get_total_token_allocation(&env, project_id, &entry.token)?
+                .checked_add(entry.amount)
+                .ok_or(Error::InvalidInput)?;
+            if allocated > get_token_balance(&env, project_id, &entry.token).total_deposited {
+                return Err(Error::InsufficientEscrowBalance);
+            }
+
+            basket.push_back(BasketAmount {
+                token: entry.token,
+                amount: entry.amount,
+                approved_amount: 0,
+                released_amount: 0,
+            });
+        }
 
-        // Create milestone (with empty proof hash)
-        let empty_hash = BytesN::from_array(&env, &[0u8; 32]);
-        let milestone = Milestone {
-            id: milestone_id,
-            project_id,
-            description_hash: description_hash.clone(),
+        add_milestone(
+            &env,
+            &escrow,
+            description_hash,
             amount,
-            status: MilestoneStatus::Pending,
-            proof_hash: empty_hash,
-            approval_count: 0,
-            rejection_count: 0,
-            created_at: env.ledger().timestamp(),
-        };
-
-        // Store milestone
-        set_milestone(&env, project_id, milestone_id, &milestone);
-        set_milestone_counter(&env, project_id, next_id);
-
-        // Emit event
-        env.events().publish(
-            (MILESTONE_CREATED,),
-            (project_id, milestone_id, amount, description_hash),
-        );
-
-        Ok(())
+            basket,
+            dependencies,
+        )
     }
 
     /// Submit a milestone with proof
@@ -198,9 +402,31 @@ impl EscrowContract {
             return Err(Error::InvalidMilestoneStatus);
         }
 
+        // Prerequisites must be approved first
+        if !dependencies_approved(&env, &milestone) {
+            return Err(Error::MilestoneNotApproved);
+        }
+
         // Update milestone
         milestone.status = MilestoneStatus::Submitted;
         milestone.proof_hash = proof_hash.clone();
+        milestone.approval_weight = 0;
+        milestone.rejection_weight = 0;
+
+        // Validator changes only apply to later submissions
+        if escrow.voting_mode == VotingMode::Validators {
+            set_milestone_validators(&env, project_id, milestone_id, &escrow.validators);
+        }
+
+        // Open the voting window for backer-voted escrows
+        if escrow.voting_mode == VotingMode::Backers {
+            let config = get_backer_voting_config(&env, project_id)?;
+            milestone.voting_ends_at = env
+                .ledger()
+                .timestamp()
+                .checked_add(config.voting_period)
+                .ok_or(Error::InvalidInput)?;
+        }
 
         // Store updated milestone
         set_milestone(&env, project_id, milestone_id, &milestone);
@@ -212,9 +438,13 @@ impl EscrowContract {
         clear_milestone_voters(&env, project_id, milestone_id);
 
         // Emit event
-        env.events().publish(
-            (MILESTONE_SUBMITTED,),
-            (project_id, milestone_id, proof_hash),
+        publish(
+            &env,
+            MilestoneSubmitted {
+                project_id,
+                milestone_id,
+                proof_hash,
+            },
         );
 
         Ok(())
@@ -222,6 +452,11 @@ impl EscrowContract {
 
     /// Vote on a milestone (approve or reject)
     ///
+    /// For validator-voted escrows the voter must be one of the escrow's
+    /// validators and the milestone is decided as soon as a majority is reached.
+    /// For backer-voted escrows the voter must have contributed to the project;
+    /// votes are tallied by weight and decided by `finalize_milestone`.
+    ///
     /// # Arguments
     /// * `project_id` - Project identifier
     /// * `milestone_id` - Milestone identifier
@@ -239,10 +474,65 @@ impl EscrowContract {
         // Get escrow
         let mut escrow = get_escrow(&env, project_id)?;
 
-        // Verify voter is a validator
-        if !escrow.validators.iter().any(|v| v == voter) {
-            return Err(Error::NotAValidator);
+        if escrow.voting_mode == VotingMode::Backers {
+            return cast_backer_vote(&env, project_id, milestone_id, &voter, approve);
+        }
+
+        let share = if approve { FULL_SHARE } else { 0 };
+        cast_validator_vote(&env, &mut escrow, milestone_id, &voter, share)
+    }
+
+    /// Approve part of a milestone as a validator
+    ///
+    /// Counts as an approval. When the milestone is approved, the smallest share
+    /// approved by any approving validator determines the amount released.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    /// * `voter` - Address of the validator
+    /// * `share` - Share of the milestone amount approved, in basis points
+    pub fn vote_milestone_partial(
+        env: Env,
+        project_id: u64,
+        milestone_id: u64,
+        voter: Address,
+        share: BasisPoints,
+    ) -> Result<(), Error> {
+        voter.require_auth();
+
+        let mut escrow = get_escrow(&env, project_id)?;
+        if escrow.voting_mode != VotingMode::Validators {
+            return Err(Error::InvalidVotingMode);
+        }
+        if share == 0 || !validate_basis_points(share) {
+            return Err(Error::InvalidInput);
+        }
+
+        cast_validator_vote(&env, &mut escrow, milestone_id, &voter, share)
+    }
+
+    /// Close a backer vote once its voting window has ended
+    ///
+    /// Anyone may call this. The milestone is approved when the weight cast
+    /// reaches the quorum (measured against the project's total raised) and the
+    /// approving weight reaches the threshold; otherwise it is rejected.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    pub fn finalize_milestone(
+        env: Env,
+        project_id: u64,
+        milestone_id: u64,
+    ) -> Result<MilestoneStatus, Error> {
+        // Get escrow
+        let mut escrow = get_escrow(&env, project_id)?;
+        if escrow.voting_mode != VotingMode::Backers {
+            return Err(Error::InvalidVotingMode);
         }
+        let config = get_backer_voting_config(&env, project_id)?;
+        let project_launch = get_project_launch(&env)?;
 
         // Get milestone
         let mut milestone = get_milestone(&env, project_id, milestone_id)?;
@@ -252,99 +542,199 @@ impl EscrowContract {
             return Err(Error::InvalidMilestoneStatus);
         }
 
-        // Check if validator already voted
-        if has_validator_voted(&env, project_id, milestone_id, &voter)? {
-            return Err(Error::AlreadyVoted);
+        // Votes can only be counted once the window has closed
+        if env.ledger().timestamp() <= milestone.voting_ends_at {
+            return Err(Error::VotingPeriodActive);
         }
 
-        // Update vote counts
-        if approve {
-            milestone.approval_count = milestone
-                .approval_count
-                .checked_add(1)
-                .ok_or(Error::InvalidInput)?;
+        let project = ProjectLaunchClient::new(&env, &project_launch).get_project(&project_id);
+
+        if backer_vote_passes(&config, &milestone, project.total_raised)? {
+            approve_milestone(&env, &mut escrow, &mut milestone)?;
         } else {
-            milestone.rejection_count = milestone
-                .rejection_count
-                .checked_add(1)
-                .ok_or(Error::InvalidInput)?;
+            reject_milestone(&env, &mut milestone);
         }
 
-        // Record that this validator voted
-        set_validator_vote(&env, project_id, milestone_id, &voter)?;
+        Ok(milestone.status)
+    }
 
-        // Check if milestone is approved or rejected
-        let _total_votes = milestone.approval_count as u32 + milestone.rejection_count as u32;
-        let required_approvals =
-            (escrow.validators.len() as u32 * MILESTONE_APPROVAL_THRESHOLD) / 10000;
+    /// Get escrow information
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    pub fn get_escrow(env: Env, project_id: u64) -> Result<EscrowInfo, Error> {
+        get_escrow(&env, project_id)
+    }
 
-        // Check for majority approval
-        if milestone.approval_count as u32 >= required_approvals {
-            milestone.status = MilestoneStatus::Approved;
+    /// Get milestone information
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    pub fn get_milestone(env: Env, project_id: u64, milestone_id: u64) -> Result<Milestone, Error> {
+        get_milestone(&env, project_id, milestone_id)
+    }
 
-            // Release funds
-            release_milestone_funds(&env, &mut escrow, &milestone)?;
+    /// List a project's milestones in id order, optionally filtered by status
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `cursor` - Milestone id to start from (0 for the first page)
+    /// * `limit` - Maximum number of milestones to return (at most 50)
+    /// * `status` - Only return milestones with this status
+    pub fn list_milestones(
+        env: Env,
+        project_id: u64,
+        cursor: u64,
+        limit: u32,
+        status: Option<MilestoneStatus>,
+    ) -> Result<MilestonePage, Error> {
+        if limit == 0 || limit > MAX_PAGE_SIZE {
+            return Err(Error::InvalidInput);
+        }
+        let counter = get_milestone_counter(&env, project_id)?;
 
-            // Perform token transfer to creator
-            let token_client = TokenClient::new(&env, &escrow.token);
-            token_client.transfer(
-                &env.current_contract_address(),
-                &escrow.creator,
-                &milestone.amount,
-            );
+        let mut page = MilestonePage {
+            milestones: Vec::new(&env),
+            next_cursor: None,
+        };
+        for milestone_id in cursor..counter {
+            if page.milestones.len() == limit {
+                page.next_cursor = Some(milestone_id);
+                break;
+            }
+            let milestone = get_milestone(&env, project_id, milestone_id)?;
+            if status.map_or(true, |status| milestone.status == status) {
+                page.milestones.push_back(milestone);
+            }
+        }
 
-            // Store updated escrow
-            set_escrow(&env, project_id, &escrow);
+        Ok(page)
+    }
 
-            // Store updated milestone
-            set_milestone(&env, project_id, milestone_id, &milestone);
+    /// Get the votes cast on a milestone's current submission
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    pub fn get_votes(
+        env: Env,
+        project_id: u64,
+        milestone_id: u64,
+    ) -> Result<Vec<ValidatorVote>, Error> {
+        get_milestone(&env, project_id, milestone_id)?;
+        Ok(get_milestone_voters(&env, project_id, milestone_id))
+    }
 
-            // Emit approval event
-            env.events().publish(
-                (MILESTONE_APPROVED,),
-                (project_id, milestone_id, milestone.approval_count),
-            );
+    /// Get deposit, release and per-status milestone totals for an escrow
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    pub fn get_escrow_summary(env: Env, project_id: u64) -> Result<EscrowSummary, Error> {
+        let escrow = get_escrow(&env, project_id)?;
+        let counter = get_milestone_counter(&env, project_id)?;
 
-            // Emit fund release event
-            env.events().publish(
-                (FUNDS_RELEASED,),
-                (project_id, milestone_id, milestone.amount),
-            );
-        } else if milestone.rejection_count as u32
-            > escrow.validators.len() as u32 - required_approvals
-        {
-            // Majority has rejected
-            milestone.status = MilestoneStatus::Rejected;
-            set_milestone(&env, project_id, milestone_id, &milestone);
+        let mut summary = EscrowSummary {
+            project_id,
+            total_deposited: escrow.total_deposited,
+            released_amount: escrow.released_amount,
+            allocated_amount: 0,
+            milestone_count: counter,
+            pending_count: 0,
+            pending_amount: 0,
+            submitted_count: 0,
+            submitted_amount: 0,
+            approved_count: 0,
+            approved_amount: 0,
+            rejected_count: 0,
+            disputed_count: 0,
+        };
 
-            // Emit rejection event
-            env.events().publish(
-                (MILESTONE_REJECTED,),
-                (project_id, milestone_id, milestone.rejection_count),
-            );
-        } else {
-            // Store updated milestone (vote recorded, but not yet finalized)
-            set_milestone(&env, project_id, milestone_id, &milestone);
+        for milestone_id in 0..counter {
+            let milestone = get_milestone(&env, project_id, milestone_id)?;
+            summary.allocated_amount = summary
+                .allocated_amount
+                .checked_add(milestone_allocation(&milestone))
+                .ok_or(Error::InvalidInput)?;
+            match milestone.status {
+                MilestoneStatus::Pending => {
+                    summary.pending_count += 1;
+                    summary.pending_amount = summary
+                        .pending_amount
+                        .checked_add(milestone.amount)
+                        .ok_or(Error::InvalidInput)?;
+                }
+                MilestoneStatus::Submitted => {
+                    summary.submitted_count += 1;
+                    summary.submitted_amount = summary
+                        .submitted_amount
+                        .checked_add(milestone.amount)
+                        .ok_or(Error::InvalidInput)?;
+                }
+                MilestoneStatus::Approved => {
+                    summary.approved_count += 1;
+                    summary.approved_amount = summary
+                        .approved_amount
+                        .checked_add(milestone.approved_amount)
+                        .ok_or(Error::InvalidInput)?;
+                }
+                MilestoneStatus::Rejected => summary.rejected_count += 1,
+                MilestoneStatus::Disputed => summary.disputed_count += 1,
+            }
         }
 
-        Ok(())
+        Ok(summary)
     }
 
-    /// Get escrow information
+    /// Get the project's milestone roadmap and progress
     ///
     /// # Arguments
     /// * `project_id` - Project identifier
-    pub fn get_escrow(env: Env, project_id: u64) -> Result<EscrowInfo, Error> {
-        get_escrow(&env, project_id)
+    pub fn get_roadmap(env: Env, project_id: u64) -> Result<Roadmap, Error> {
+        let counter = get_milestone_counter(&env, project_id)?;
+        let mut roadmap = Roadmap {
+            project_id,
+            items: Vec::new(&env),
+            approved_count: 0,
+            total_amount: 0,
+            released_amount: 0,
+        };
+
+        for milestone_id in 0..counter {
+            let milestone = get_milestone(&env, project_id, milestone_id)?;
+            if milestone.status == MilestoneStatus::Approved {
+                roadmap.approved_count += 1;
+            }
+            roadmap.total_amount = roadmap
+                .total_amount
+                .checked_add(milestone.amount)
+                .ok_or(Error::InvalidInput)?;
+            roadmap.released_amount = roadmap
+                .released_amount
+                .checked_add(milestone.released_amount)
+                .ok_or(Error::InvalidInput)?;
+            roadmap.items.push_back(RoadmapItem {
+                milestone_id,
+                status: milestone.status,
+                amount: milestone.amount,
+                released_amount: milestone.released_amount,
+                unlocked: dependencies_approved(&env, &milestone),
+                dependencies: milestone.dependencies,
+            });
+        }
+
+        Ok(roadmap)
     }
 
-    /// Get milestone information
+    /// Get the backer voting parameters of a backer-voted escrow
     ///
     /// # Arguments
     /// * `project_id` - Project identifier
-    /// * `milestone_id` - Milestone identifier
-    pub fn get_milestone(env: Env, project_id: u64, milestone_id: u64) -> Result<Milestone, Error> {
-        get_milestone(&env, project_id, milestone_id)
+    pub fn get_backer_voting_config(
+        env: Env,
+        project_id: u64,
+    ) -> Result<BackerVotingConfig, Error> {
+        get_backer_voting_config(&env, project_id)
     }
 
     /// Get the total amount allocated to milestones
@@ -364,8 +754,29 @@ impl EscrowContract {
         Ok(escrow.total_deposited - escrow.released_amount)
     }
 
+    /// Get deposits and releases of every escrow token, primary token first
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    pub fn get_token_balances(env: Env, project_id: u64) -> Result<Vec<TokenBalance>, Error> {
+        let escrow = get_escrow(&env, project_id)?;
+        let mut balances = Vec::new(&env);
+        balances.push_back(TokenBalance {
+            token: escrow.token.clone(),
+            total_deposited: escrow.total_deposited,
+            released_amount: escrow.released_amount,
+        });
+        for token in escrow.tokens.iter() {
+            balances.push_back(get_token_balance(&env, project_id, &token));
+        }
+        Ok(balances)
+    }
+
     /// Update validators for an escrow
     ///
+    /// Milestones already submitted keep being voted on by the validator set
+    /// they were submitted with.
+    ///
     /// # Arguments
     /// * `project_id` - Project identifier
     /// * `new_validators` - New list of validator addresses
@@ -379,12 +790,16 @@ impl EscrowContract {
         admin.require_auth();
 
         // Validate new validators
-        if (new_validators.len() as u32) < MIN_VALIDATORS {
+        if new_validators.len() < MIN_VALIDATORS {
             return Err(Error::InvalidInput);
         }
 
         // Get escrow
         let mut escrow = get_escrow(&env, project_id)?;
+        if escrow.voting_mode != VotingMode::Validators {
+            return Err(Error::InvalidVotingMode);
+        }
+        require_bonded_validators(&env, &new_validators)?;
 
         // Update validators
         escrow.validators = new_validators.clone();
@@ -393,29 +808,1671 @@ impl EscrowContract {
         set_escrow(&env, project_id, &escrow);
 
         // Emit event
-        env.events()
-            .publish((VALIDATORS_UPDATED,), (project_id, new_validators));
+        publish(
+            &env,
+            ValidatorsUpdated {
+                project_id,
+                validators: new_validators,
+            },
+        );
 
         Ok(())
     }
-}
 
-/// Helper function to release milestone funds
-fn release_milestone_funds(
-    _env: &Env,
-    escrow: &mut EscrowInfo,
-    milestone: &Milestone,
-) -> Result<(), Error> {
-    // Verify funds are not released more than once
-    let new_released = escrow
-        .released_amount
-        .checked_add(milestone.amount)
-        .ok_or(Error::InvalidInput)?;
+    /// Propose a new validator set for an escrow
+    ///
+    /// The change takes effect once the admin or a majority of the current
+    /// validators approve it. A new proposal replaces any pending one.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `new_validators` - Proposed list of validator addresses
+    pub fn propose_validator_change(
+        env: Env,
+        project_id: u64,
+        new_validators: Vec<Address>,
+    ) -> Result<(), Error> {
+        let escrow = get_escrow(&env, project_id)?;
+        escrow.creator.require_auth();
 
-    if new_released > escrow.total_deposited {
+        if escrow.voting_mode != VotingMode::Validators {
+            return Err(Error::InvalidVotingMode);
+        }
+        if new_validators.len() < MIN_VALIDATORS {
+            return Err(Error::InvalidInput);
+        }
+        require_bonded_validators(&env, &new_validators)?;
+
+        let proposal = ValidatorChangeProposal {
+            project_id,
+            validators: new_validators.clone(),
+            approvals: Vec::new(&env),
+            proposed_at: env.ledger().timestamp(),
+        };
+        set_validator_change(&env, &proposal);
+
+        publish(
+            &env,
+            ValidatorChangeProposed {
+                project_id,
+                validators: new_validators,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Approve a pending validator change
+    ///
+    /// An approval from the admin applies the change immediately; otherwise the
+    /// approver must be a current validator and the change is applied once more
+    /// than half of the current validators have approved it. Approvals from
+    /// validators that have since been removed no longer count.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `approver` - Admin or current validator approving the change
+    pub fn approve_validator_change(
+        env: Env,
+        project_id: u64,
+        approver: Address,
+    ) -> Result<(), Error> {
+        approver.require_auth();
+
+        let mut escrow = get_escrow(&env, project_id)?;
+        let mut proposal = get_validator_change(&env, project_id)?;
+
+        // The validator set may have been updated since the proposal was made
+        let mut approvals = Vec::new(&env);
+        for approval in proposal.approvals.iter() {
+            if escrow.validators.contains(&approval) {
+                approvals.push_back(approval);
+            }
+        }
+        proposal.approvals = approvals;
+
+        let is_admin = get_admin(&env)
+            .map(|admin| admin == approver)
+            .unwrap_or(false);
+        if !is_admin {
+            validate_validator(&escrow.validators, &approver)?;
+            if proposal.approvals.contains(&approver) {
+                return Err(Error::AlreadyVoted);
+            }
+            proposal.approvals.push_back(approver.clone());
+        }
+
+        publish(
+            &env,
+            ValidatorChangeApproved {
+                project_id,
+                approver,
+            },
+        );
+
+        if is_admin || proposal.approvals.len() * 2 > escrow.validators.len() {
+            // Bonds may have changed since the proposal was made
+            require_bonded_validators(&env, &proposal.validators)?;
+
+            escrow.validators = proposal.validators.clone();
+            set_escrow(&env, project_id, &escrow);
+            remove_validator_change(&env, project_id);
+
+            publish(
+                &env,
+                ValidatorsUpdated {
+                    project_id,
+                    validators: proposal.validators,
+                },
+            );
+        } else {
+            set_validator_change(&env, &proposal);
+        }
+
+        Ok(())
+    }
+
+    /// Get the pending validator change for an escrow
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    pub fn get_validator_change(
+        env: Env,
+        project_id: u64,
+    ) -> Result<ValidatorChangeProposal, Error> {
+        get_validator_change(&env, project_id)
+    }
+
+    /// Get the validator set a submitted milestone is voted on by
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    pub fn get_milestone_validators(
+        env: Env,
+        project_id: u64,
+        milestone_id: u64,
+    ) -> Result<Vec<Address>, Error> {
+        get_milestone_validators(&env, project_id, milestone_id)
+    }
+
+    /// Configure the validator registry
+    ///
+    /// Once configured, escrow validators must keep at least `min_bond` of the
+    /// stake token bonded, earn `fee_share` of every milestone they approve and
+    /// lose `slash_rate` of their stake when a dispute against such a milestone
+    /// is upheld. The stake token cannot be changed after it is first set.
+    ///
+    /// # Arguments
+    /// * `config` - Registry parameters
+    pub fn configure_validator_registry(
+        env: Env,
+        config: ValidatorRegistryConfig,
+    ) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        validate_validator_registry_config(&config)?;
+        if let Ok(current) = get_validator_registry(&env) {
+            if current.stake_token != config.stake_token {
+                return Err(Error::InvalidInput);
+            }
+        }
+
+        set_validator_registry(&env, &config);
+        Ok(())
+    }
+
+    /// Get the validator registry parameters
+    pub fn get_validator_registry(env: Env) -> Result<ValidatorRegistryConfig, Error> {
+        get_validator_registry(&env)
+    }
+
+    /// Bond stake tokens to become eligible as an escrow validator
+    ///
+    /// # Arguments
+    /// * `validator` - Address of the validator
+    /// * `amount` - Amount of stake token to bond
+    pub fn bond_validator(env: Env, validator: Address, amount: Amount) -> Result<(), Error> {
+        validator.require_auth();
+        let config = get_validator_registry(&env)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut bond = get_validator_bond(&env, &validator).unwrap_or(ValidatorBond {
+            validator: validator.clone(),
+            bonded: 0,
+            unbonding: 0,
+            unbonding_ends_at: 0,
+            slashable_until: 0,
+            open_disputes: 0,
+        });
+        bond.bonded = bond.bonded.checked_add(amount).ok_or(Error::InvalidInput)?;
+
+        TokenClient::new(&env, &config.stake_token).transfer(
+            &validator,
+            &env.current_contract_address(),
+            &amount,
+        );
+        set_validator_bond(&env, &bond);
+
+        publish(
+            &env,
+            ValidatorBonded {
+                validator,
+                amount,
+                bonded: bond.bonded,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Start unbonding part of a validator's stake
+    ///
+    /// The amount stays slashable until the unbonding period has passed.
+    /// Unbonding again restarts the period for the whole unbonding amount.
+    ///
+    /// # Arguments
+    /// * `validator` - Address of the validator
+    /// * `amount` - Amount of bonded stake to unbond
+    pub fn unbond_validator(env: Env, validator: Address, amount: Amount) -> Result<(), Error> {
+        validator.require_auth();
+        let config = get_validator_registry(&env)?;
+        let mut bond = get_validator_bond(&env, &validator)?;
+
+        if amount <= 0 || amount > bond.bonded {
+            return Err(Error::InvalidInput);
+        }
+
+        bond.bonded -= amount;
+        bond.unbonding = bond
+            .unbonding
+            .checked_add(amount)
+            .ok_or(Error::InvalidInput)?;
+        bond.unbonding_ends_at = env
+            .ledger()
+            .timestamp()
+            .checked_add(config.unbonding_period)
+            .ok_or(Error::InvalidInput)?;
+        set_validator_bond(&env, &bond);
+
+        publish(
+            &env,
+            ValidatorUnbonded {
+                validator,
+                amount,
+                unbonding_ends_at: bond.unbonding_ends_at,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw stake whose unbonding period has passed
+    ///
+    /// The stake is also held while a milestone the validator approved can
+    /// still be disputed, and until any such dispute is settled.
+    ///
+    /// # Arguments
+    /// * `validator` - Address of the validator
+    pub fn withdraw_unbonded(env: Env, validator: Address) -> Result<Amount, Error> {
+        validator.require_auth();
+        let config = get_validator_registry(&env)?;
+        let mut bond = get_validator_bond(&env, &validator)?;
+
+        if bond.unbonding == 0 {
+            return Err(Error::NoClaimableAmount);
+        }
+        // Stake stays slashable while the validator's approvals can be disputed
+        let now = env.ledger().timestamp();
+        if now < bond.unbonding_ends_at || now < bond.slashable_until || bond.open_disputes > 0 {
+            return Err(Error::UnbondingPeriodActive);
+        }
+
+        let amount = bond.unbonding;
+        bond.unbonding = 0;
+        set_validator_bond(&env, &bond);
+
+        TokenClient::new(&env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &validator,
+            &amount,
+        );
+
+        publish(&env, ValidatorWithdrawn { validator, amount });
+
+        Ok(amount)
+    }
+
+    /// Get a validator's bond
+    ///
+    /// # Arguments
+    /// * `validator` - Address of the validator
+    pub fn get_validator_bond(env: Env, validator: Address) -> Result<ValidatorBond, Error> {
+        get_validator_bond(&env, &validator)
+    }
+
+    /// Claim the milestone fees a validator has earned in a token
+    ///
+    /// # Arguments
+    /// * `validator` - Address of the validator
+    /// * `token` - Escrow token the fees were paid in
+    pub fn claim_validator_fees(
+        env: Env,
+        validator: Address,
+        token: Address,
+    ) -> Result<Amount, Error> {
+        validator.require_auth();
+
+        let amount = get_validator_fees(&env, &validator, &token);
+        if amount <= 0 {
+            return Err(Error::NoClaimableAmount);
+        }
+        set_validator_fees(&env, &validator, &token, 0);
+
+        TokenClient::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &validator,
+            &amount,
+        );
+
+        publish(
+            &env,
+            ValidatorFeesClaimed {
+                validator,
+                token,
+                amount,
+            },
+        );
+
+        Ok(amount)
+    }
+
+    /// Get the unclaimed milestone fees a validator has earned in a token
+    ///
+    /// # Arguments
+    /// * `validator` - Address of the validator
+    /// * `token` - Escrow token the fees were paid in
+    pub fn get_validator_fees(env: Env, validator: Address, token: Address) -> Amount {
+        get_validator_fees(&env, &validator, &token)
+    }
+
+    /// Set the ProjectLaunch contract backer-voted escrows and disputes read
+    /// projects and contributions from
+    pub fn set_project_launch(env: Env, project_launch: Address) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        set_project_launch(&env, &project_launch);
+        Ok(())
+    }
+
+    /// Get the ProjectLaunch contract backer voting reads from
+    pub fn get_project_launch(env: Env) -> Result<Address, Error> {
+        get_project_launch(&env)
+    }
+
+    /// Configure milestone disputes
+    ///
+    /// Once configured, approved milestone funds are held for `window` seconds
+    /// after the decision so backers or the creator can contest it, and are then
+    /// paid out through `release_milestone`.
+    ///
+    /// # Arguments
+    /// * `config` - Dispute bond, window, arbitrator panel and treasury
+    pub fn configure_disputes(env: Env, config: DisputeConfig) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        validate_dispute_config(&config)?;
+        set_dispute_config(&env, &config);
+        Ok(())
+    }
+
+    /// Get the dispute parameters
+    pub fn get_dispute_config(env: Env) -> Result<DisputeConfig, Error> {
+        get_dispute_config(&env)
+    }
+
+    /// Split a pending milestone's payout into tranches
+    ///
+    /// Each tranche releases its share of the approved amount once its delay
+    /// after approval has passed. Without tranches the approved amount is paid
+    /// in one go. When disputes are configured no tranche is paid before the
+    /// dispute window has passed.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    /// * `tranches` - Release schedule; shares must add up to 10000
+    pub fn set_milestone_tranches(
+        env: Env,
+        project_id: u64,
+        milestone_id: u64,
+        tranches: Vec<Tranche>,
+    ) -> Result<(), Error> {
+        let escrow = get_escrow(&env, project_id)?;
+        escrow.creator.require_auth();
+
+        let milestone = get_milestone(&env, project_id, milestone_id)?;
+        if milestone.status != MilestoneStatus::Pending {
+            return Err(Error::InvalidMilestoneStatus);
+        }
+        validate_tranches(&tranches)?;
+
+        set_milestone_tranches(&env, project_id, milestone_id, &tranches);
+        Ok(())
+    }
+
+    /// Get the release schedule of a milestone (empty when paid in one go)
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    pub fn get_milestone_tranches(env: Env, project_id: u64, milestone_id: u64) -> Vec<Tranche> {
+        get_milestone_tranches(&env, project_id, milestone_id)
+    }
+
+    /// Pay out the tranches of an approved milestone that have become due
+    ///
+    /// Anyone may call this. Returns the amount released.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    pub fn release_milestone(
+        env: Env,
+        project_id: u64,
+        milestone_id: u64,
+    ) -> Result<Amount, Error> {
+        let mut escrow = get_escrow(&env, project_id)?;
+        let mut milestone = get_milestone(&env, project_id, milestone_id)?;
+
+        if milestone.status != MilestoneStatus::Approved || fully_released(&milestone) {
+            return Err(Error::InvalidMilestoneStatus);
+        }
+
+        // Nothing is due while the holdback is running
+        let released = release_due_funds(&env, &mut escrow, &mut milestone)?;
+        if released.is_empty() {
+            return Err(Error::HoldbackActive);
+        }
+        Ok(released
+            .iter()
+            .filter(|release| release.token == escrow.token)
+            .map(|release| release.amount)
+            .sum())
+    }
+
+    /// Dispute a milestone decision by posting a bond
+    ///
+    /// Only the creator or a backer of the project may dispute, and only within
+    /// the dispute window after the milestone was approved or rejected. The
+    /// milestone is marked disputed, holding any unreleased funds, until the
+    /// arbitrator panel or the admin rules. Disputing a rejection reserves the
+    /// full milestone amount, so the escrow must still be able to cover it.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    /// * `challenger` - Address opening the dispute
+    /// * `evidence_hash` - Hash of the evidence supporting the dispute
+    pub fn open_dispute(
+        env: Env,
+        project_id: u64,
+        milestone_id: u64,
+        challenger: Address,
+        evidence_hash: Hash,
+    ) -> Result<(), Error> {
+        challenger.require_auth();
+        let config = get_dispute_config(&env)?;
+        let escrow = get_escrow(&env, project_id)?;
+
+        // A decision can only be contested once, and approvals only while funds are held
+        let mut milestone = get_milestone(&env, project_id, milestone_id)?;
+        let contestable = match milestone.status {
+            MilestoneStatus::Approved => !fully_released(&milestone),
+            MilestoneStatus::Rejected => true,
+            _ => false,
+        };
+        if !contestable || get_dispute(&env, project_id, milestone_id).is_ok() {
+            return Err(Error::InvalidMilestoneStatus);
+        }
+        if env.ledger().timestamp() >= dispute_window_end(&config, &milestone)? {
+            return Err(Error::VotingPeriodEnded);
+        }
+
+        // Only parties to the project may dispute
+        if challenger != escrow.creator
+            && ProjectLaunchClient::new(&env, &get_project_launch(&env)?)
+                .get_user_contribution(&project_id, &challenger)
+                <= 0
+        {
+            return Err(Error::NotABacker);
+        }
+
+        // Reserve what upholding a rejection would pay out
+        let disputed_status = milestone.status;
+        if disputed_status == MilestoneStatus::Rejected {
+            milestone.approved_amount = milestone.amount;
+            milestone.basket = scale_basket(&env, &milestone.basket, |entry| entry.amount);
+        }
+        milestone.status = MilestoneStatus::Disputed;
+        set_milestone(&env, project_id, milestone_id, &milestone);
+        if disputed_status == MilestoneStatus::Rejected {
+            require_allocation_covered(&env, &escrow, &milestone)?;
+        } else {
+            count_open_dispute(&env, &escrow, milestone_id, true);
+        }
+
+        TokenClient::new(&env, &config.bond_token).transfer(
+            &challenger,
+            &env.current_contract_address(),
+            &config.bond_amount,
+        );
+
+        let dispute = Dispute {
+            project_id,
+            milestone_id,
+            challenger: challenger.clone(),
+            evidence_hash,
+            disputed_status,
+            bond: config.bond_amount,
+            uphold_votes: 0,
+            dismiss_votes: 0,
+            status: DisputeStatus::Open,
+            opened_at: env.ledger().timestamp(),
+        };
+        set_dispute(&env, &dispute);
+
+        publish(
+            &env,
+            DisputeOpened {
+                project_id,
+                milestone_id,
+                challenger,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Vote on an open dispute as a member of the arbitrator panel
+    ///
+    /// The dispute is settled as soon as a majority of the panel agrees.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    /// * `arbitrator` - Address of the arbitrator
+    /// * `uphold` - True to overturn the disputed decision
+    pub fn vote_dispute(
+        env: Env,
+        project_id: u64,
+        milestone_id: u64,
+        arbitrator: Address,
+        uphold: bool,
+    ) -> Result<(), Error> {
+        arbitrator.require_auth();
+        let config = get_dispute_config(&env)?;
+        if !config.arbitrators.contains(&arbitrator) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut dispute = get_dispute(&env, project_id, milestone_id)?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::InvalidMilestoneStatus);
+        }
+        let votes = get_dispute_votes(&env, project_id, milestone_id);
+        if votes.iter().any(|vote| vote.arbitrator == arbitrator) {
+            return Err(Error::AlreadyVoted);
+        }
+
+        add_dispute_vote(
+            &env,
+            project_id,
+            milestone_id,
+            &ArbitratorVote {
+                arbitrator: arbitrator.clone(),
+                uphold,
+            },
+        );
+        if uphold {
+            dispute.uphold_votes += 1;
+        } else {
+            dispute.dismiss_votes += 1;
+        }
+
+        publish(
+            &env,
+            DisputeVoteCast {
+                project_id,
+                milestone_id,
+                arbitrator,
+                uphold,
+            },
+        );
+
+        let majority = config.arbitrators.len() / 2 + 1;
+        if dispute.uphold_votes >= majority {
+            settle_dispute(&env, &config, &mut dispute, true)
+        } else if dispute.dismiss_votes >= majority {
+            settle_dispute(&env, &config, &mut dispute, false)
+        } else {
+            set_dispute(&env, &dispute);
+            Ok(())
+        }
+    }
+
+    /// Rule on an open dispute directly (admin or governance override)
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    /// * `upheld` - True to overturn the disputed decision
+    pub fn resolve_dispute(
+        env: Env,
+        project_id: u64,
+        milestone_id: u64,
+        upheld: bool,
+    ) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        let config = get_dispute_config(&env)?;
+
+        let mut dispute = get_dispute(&env, project_id, milestone_id)?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::InvalidMilestoneStatus);
+        }
+
+        settle_dispute(&env, &config, &mut dispute, upheld)
+    }
+
+    /// Get the dispute raised against a milestone
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `milestone_id` - Milestone identifier
+    pub fn get_dispute(env: Env, project_id: u64, milestone_id: u64) -> Result<Dispute, Error> {
+        get_dispute(&env, project_id, milestone_id)
+    }
+
+    /// Allow or disallow a vault to hold idle escrow funds
+    ///
+    /// # Arguments
+    /// * `vault` - Vault contract address
+    /// * `whitelisted` - Whether escrows may deposit into the vault
+    pub fn set_vault_whitelisted(env: Env, vault: Address, whitelisted: bool) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        set_vault_whitelisted(&env, &vault, whitelisted);
+        Ok(())
+    }
+
+    /// Check if a vault may hold idle escrow funds
+    pub fn is_vault_whitelisted(env: Env, vault: Address) -> bool {
+        is_vault_whitelisted(&env, &vault)
+    }
+
+    /// Opt an escrow into earning yield on its idle balance
+    ///
+    /// Only the admin can set the vault and the backers' share of the yield.
+    /// The vault must be whitelisted and hold the escrow token. The vault can
+    /// only be changed while nothing is deposited in it.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    /// * `config` - Vault and the backers' share of accrued yield
+    pub fn configure_yield(env: Env, project_id: u64, config: YieldConfig) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        let escrow = get_escrow(&env, project_id)?;
+
+        if !is_vault_whitelisted(&env, &config.vault) {
+            return Err(Error::Unauthorized);
+        }
+        if !validate_basis_points(config.backer_share)
+            || VaultClient::new(&env, &config.vault).asset() != escrow.token
+        {
+            return Err(Error::InvalidInput);
+        }
+        if let Ok(position) = get_yield_position(&env, project_id) {
+            if position.vault != config.vault {
+                return Err(Error::InvalidInput);
+            }
+        }
+
+        set_yield_config(&env, project_id, &config);
+        Ok(())
+    }
+
+    /// Get the yield settings of an escrow
+    pub fn get_yield_config(env: Env, project_id: u64) -> Result<YieldConfig, Error> {
+        get_yield_config(&env, project_id)
+    }
+
+    /// Get the funds an escrow has deposited in its vault
+    pub fn get_yield_position(env: Env, project_id: u64) -> Result<YieldPosition, Error> {
+        get_yield_position(&env, project_id)
+    }
+
+    /// Deposit the escrow's idle balance into its vault
+    ///
+    /// Only funds that are neither released nor allocated to a milestone are
+    /// deposited. They are withdrawn again as approved milestones are paid out.
+    /// Anyone may call this. Returns the amount deposited.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    pub fn invest_idle(env: Env, project_id: u64) -> Result<Amount, Error> {
+        let escrow = get_escrow(&env, project_id)?;
+        let config = get_yield_config(&env, project_id)?;
+        if !is_vault_whitelisted(&env, &config.vault) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut position = get_yield_position(&env, project_id).unwrap_or(YieldPosition {
+            vault: config.vault.clone(),
+            shares: 0,
+            principal: 0,
+            harvested: 0,
+        });
+        let allocated = get_total_milestone_amount(&env, project_id)?;
+        let amount = escrow.total_deposited - allocated - position.principal;
+        if amount <= 0 {
+            return Err(Error::InsufficientEscrowBalance);
+        }
+
+        // The vault pulls the deposit from the escrow
+        let contract = env.current_contract_address();
+        env.authorize_as_current_contract(vec![
+            &env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: escrow.token.clone(),
+                    fn_name: Symbol::new(&env, "transfer"),
+                    args: (contract.clone(), config.vault.clone(), amount).into_val(&env),
+                },
+                sub_invocations: Vec::new(&env),
+            }),
+        ]);
+        let shares = VaultClient::new(&env, &config.vault).deposit(&contract, &amount);
+
+        position.shares += shares;
+        position.principal += amount;
+        set_yield_position(&env, project_id, &position);
+
+        publish(
+            &env,
+            YieldInvested {
+                project_id,
+                vault: config.vault,
+                amount,
+            },
+        );
+
+        Ok(amount)
+    }
+
+    /// Pay out the yield accrued on an escrow's vault deposit
+    ///
+    /// The backers' share goes to the configured recipient and the rest to
+    /// the creator. Anyone may call this. Returns the yield harvested.
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    pub fn harvest_yield(env: Env, project_id: u64) -> Result<Amount, Error> {
+        let escrow = get_escrow(&env, project_id)?;
+        let config = get_yield_config(&env, project_id)?;
+        let mut position = get_yield_position(&env, project_id)?;
+
+        let harvested = harvest_position(&env, &escrow, &config, &mut position)?;
+        if harvested == 0 {
+            return Err(Error::NoClaimableAmount);
+        }
+        set_yield_position(&env, project_id, &position);
+        Ok(harvested)
+    }
+
+    /// Withdraw an escrow's whole vault deposit, paying out any accrued yield
+    ///
+    /// # Arguments
+    /// * `project_id` - Project identifier
+    pub fn divest(env: Env, project_id: u64) -> Result<Amount, Error> {
+        let escrow = get_escrow(&env, project_id)?;
+        escrow.creator.require_auth();
+        let config = get_yield_config(&env, project_id)?;
+        let mut position = get_yield_position(&env, project_id)?;
+
+        harvest_position(&env, &escrow, &config, &mut position)?;
+        let principal = position.principal;
+        withdraw_from_vault(&env, project_id, &mut position, principal);
+        remove_yield_position(&env, project_id);
+        Ok(principal)
+    }
+}
+
+/// Validate the primary token allocation and store a new milestone
+fn add_milestone(
+    env: &Env,
+    escrow: &EscrowInfo,
+    description_hash: Hash,
+    amount: Amount,
+    basket: Vec<BasketAmount>,
+    dependencies: Vec<u64>,
+) -> Result<(), Error> {
+    let project_id = escrow.project_id;
+
+    // Validate that total milestone amounts don't exceed escrow total
+    let total_milestones = get_total_milestone_amount(env, project_id)?;
+    let new_total = total_milestones
+        .checked_add(amount)
+        .ok_or(Error::InvalidInput)?;
+
+    if new_total > escrow.total_deposited {
+        return Err(Error::InsufficientEscrowBalance);
+    }
+
+    // Get next milestone ID
+    let milestone_id = get_milestone_counter(env, project_id)?;
+    let next_id = milestone_id.checked_add(1).ok_or(Error::InvalidInput)?;
+
+    // Dependencies must already exist, which keeps the graph acyclic
+    validate_dependencies(&dependencies, milestone_id)?;
+
+    // Create milestone (with empty proof hash)
+    let empty_hash = BytesN::from_array(env, &[0u8; 32]);
+    let milestone = Milestone {
+        id: milestone_id,
+        project_id,
+        description_hash: description_hash.clone(),
+        amount,
+        basket,
+        status: MilestoneStatus::Pending,
+        proof_hash: empty_hash,
+        approval_count: 0,
+        rejection_count: 0,
+        approval_weight: 0,
+        rejection_weight: 0,
+        voting_ends_at: 0,
+        approved_amount: 0,
+        released_amount: 0,
+        decided_at: 0,
+        dependencies,
+        created_at: env.ledger().timestamp(),
+    };
+
+    // Store milestone
+    set_milestone(env, project_id, milestone_id, &milestone);
+    set_milestone_counter(env, project_id, next_id);
+
+    // Emit event
+    publish(
+        env,
+        MilestoneCreated {
+            project_id,
+            milestone_id,
+            amount,
+            description_hash,
+        },
+    );
+
+    Ok(())
+}
+
+/// Check whether every prerequisite of a milestone has been approved
+fn dependencies_approved(env: &Env, milestone: &Milestone) -> bool {
+    milestone.dependencies.iter().all(|dependency| {
+        get_milestone(env, milestone.project_id, dependency)
+            .map(|prerequisite| prerequisite.status == MilestoneStatus::Approved)
+            .unwrap_or(false)
+    })
+}
+
+/// End of the window during which a milestone decision can be disputed
+fn dispute_window_end(config: &DisputeConfig, milestone: &Milestone) -> Result<u64, Error> {
+    milestone
+        .decided_at
+        .checked_add(config.window)
+        .ok_or(Error::InvalidInput)
+}
+
+/// Apply a ruling on a dispute and redistribute the challenger's bond
+///
+/// An upheld dispute on an approval rejects the milestone, returning its
+/// unreleased funds to the escrow, slashing the approving validators and
+/// forfeiting the fees they have not yet claimed for it; an
+/// upheld dispute on a rejection approves the full milestone amount. The bond is refunded when
+/// the dispute is upheld and otherwise split between the arbitrators who voted
+/// to dismiss, with any remainder going to the treasury.
+fn settle_dispute(
+    env: &Env,
+    config: &DisputeConfig,
+    dispute: &mut Dispute,
+    upheld: bool,
+) -> Result<(), Error> {
+    let project_id = dispute.project_id;
+    let milestone_id = dispute.milestone_id;
+    let mut escrow = get_escrow(env, project_id)?;
+    let mut milestone = get_milestone(env, project_id, milestone_id)?;
+    let bond_token = TokenClient::new(env, &config.bond_token);
+    if dispute.disputed_status == MilestoneStatus::Approved {
+        count_open_dispute(env, &escrow, milestone_id, false);
+    }
+
+    if upheld {
+        dispute.status = DisputeStatus::Upheld;
+        milestone.decided_at = env.ledger().timestamp();
+        if dispute.disputed_status == MilestoneStatus::Approved {
+            milestone.status = MilestoneStatus::Rejected;
+            milestone.approved_amount = milestone.released_amount;
+            milestone.basket = scale_basket(env, &milestone.basket, |entry| entry.released_amount);
+            set_milestone(env, project_id, milestone_id, &milestone);
+            slash_approvers(env, &escrow, milestone_id)?;
+            forfeit_validator_fees(env, &escrow, &milestone);
+        } else {
+            // The full amount was reserved when the dispute was opened
+            milestone.status = MilestoneStatus::Approved;
+            milestone.approved_amount = milestone.amount;
+            milestone.basket = scale_basket(env, &milestone.basket, |entry| entry.amount);
+            set_milestone(env, project_id, milestone_id, &milestone);
+            set_dispute(env, dispute);
+            release_due_funds(env, &mut escrow, &mut milestone)?;
+        }
+
+        bond_token.transfer(
+            &env.current_contract_address(),
+            &dispute.challenger,
+            &dispute.bond,
+        );
+    } else {
+        dispute.status = DisputeStatus::Dismissed;
+        milestone.status = dispute.disputed_status;
+        if dispute.disputed_status == MilestoneStatus::Rejected {
+            // Release the reservation made when the dispute was opened
+            milestone.approved_amount = milestone.released_amount;
+            milestone.basket = scale_basket(env, &milestone.basket, |entry| entry.released_amount);
+        }
+        set_milestone(env, project_id, milestone_id, &milestone);
+
+        let mut remaining = dispute.bond;
+        let votes = get_dispute_votes(env, project_id, milestone_id);
+        let dismissers = votes.iter().filter(|vote| !vote.uphold).count() as i128;
+        if dismissers > 0 {
+            let reward = dispute.bond / dismissers;
+            for vote in votes.iter().filter(|vote| !vote.uphold) {
+                if reward > 0 {
+                    bond_token.transfer(&env.current_contract_address(), &vote.arbitrator, &reward);
+                }
+                remaining -= reward;
+            }
+        }
+        if remaining > 0 {
+            bond_token.transfer(
+                &env.current_contract_address(),
+                &config.treasury,
+                &remaining,
+            );
+        }
+    }
+    set_dispute(env, dispute);
+
+    publish(
+        env,
+        DisputeResolved {
+            project_id,
+            milestone_id,
+            upheld,
+        },
+    );
+
+    Ok(())
+}
+
+/// Check that the escrow's deposits cover everything allocated to milestones,
+/// in the primary token and in each token of a milestone's basket
+fn require_allocation_covered(
+    env: &Env,
+    escrow: &EscrowInfo,
+    milestone: &Milestone,
+) -> Result<(), Error> {
+    if get_total_milestone_amount(env, escrow.project_id)? > escrow.total_deposited {
+        return Err(Error::InsufficientEscrowBalance);
+    }
+    for entry in milestone.basket.iter() {
+        let allocated = get_total_token_allocation(env, escrow.project_id, &entry.token)?;
+        let deposited = get_token_balance(env, escrow.project_id, &entry.token).total_deposited;
+        if allocated > deposited {
+            return Err(Error::InsufficientEscrowBalance);
+        }
+    }
+    Ok(())
+}
+
+/// Check that a validator is bonded, if the validator registry is configured
+fn require_bonded_validator(env: &Env, validator: &Address) -> Result<(), Error> {
+    if let Ok(config) = get_validator_registry(env) {
+        let bond = get_validator_bond(env, validator).map_err(|_| Error::InsufficientBond)?;
+        validate_bond(&config, &bond)?;
+    }
+    Ok(())
+}
+
+/// Check that every validator in a set is bonded
+fn require_bonded_validators(env: &Env, validators: &Vec<Address>) -> Result<(), Error> {
+    for validator in validators.iter() {
+        require_bonded_validator(env, &validator)?;
+    }
+    Ok(())
+}
+
+/// Credit the registry fee share of a milestone release to its approving validators
+///
+/// Returns the total fee withheld from the creator's payout.
+fn pay_validator_fees(
+    env: &Env,
+    escrow: &EscrowInfo,
+    milestone: &Milestone,
+    token: &Address,
+    released: Amount,
+) -> Amount {
+    if escrow.voting_mode != VotingMode::Validators {
+        return 0;
+    }
+    let config = match get_validator_registry(env) {
+        Ok(config) => config,
+        Err(_) => return 0,
+    };
+
+    let approvers = get_milestone_voters(env, milestone.project_id, milestone.id);
+    let approver_count = approvers.iter().filter(|vote| vote.approve).count() as i128;
+    if approver_count == 0 {
+        return 0;
+    }
+
+    let fee_per_validator = calculate_percentage(released, config.fee_share) / approver_count;
+    if fee_per_validator == 0 {
+        return 0;
+    }
+
+    for vote in approvers.iter().filter(|vote| vote.approve) {
+        let earned = get_validator_fees(env, &vote.validator, token);
+        set_validator_fees(env, &vote.validator, token, earned + fee_per_validator);
+    }
+    let credited = get_milestone_fees(env, milestone.project_id, milestone.id, token);
+    set_milestone_fees(
+        env,
+        milestone.project_id,
+        milestone.id,
+        token,
+        credited + fee_per_validator,
+    );
+
+    fee_per_validator * approver_count
+}
+
+/// Validators that approved a milestone; backer-voted escrows have none
+fn validator_approvers(env: &Env, escrow: &EscrowInfo, milestone_id: u64) -> Vec<Address> {
+    let mut approvers = Vec::new(env);
+    if escrow.voting_mode != VotingMode::Validators {
+        return approvers;
+    }
+    for vote in get_milestone_voters(env, escrow.project_id, milestone_id).iter() {
+        if vote.approve {
+            approvers.push_back(vote.validator);
+        }
+    }
+    approvers
+}
+
+/// Keep the stake of a milestone's approving validators from being withdrawn
+/// until its dispute window has passed
+fn hold_approver_stake(env: &Env, escrow: &EscrowInfo, milestone: &Milestone) {
+    let window_end = match get_dispute_config(env) {
+        Ok(config) => milestone.decided_at.saturating_add(config.window),
+        Err(_) => return,
+    };
+    for validator in validator_approvers(env, escrow, milestone.id).iter() {
+        if let Ok(mut bond) = get_validator_bond(env, &validator) {
+            if bond.slashable_until < window_end {
+                bond.slashable_until = window_end;
+                set_validator_bond(env, &bond);
+            }
+        }
+    }
+}
+
+/// Count a dispute against a milestone's approval as opened or settled for
+/// each validator that approved it
+fn count_open_dispute(env: &Env, escrow: &EscrowInfo, milestone_id: u64, opened: bool) {
+    for validator in validator_approvers(env, escrow, milestone_id).iter() {
+        if let Ok(mut bond) = get_validator_bond(env, &validator) {
+            bond.open_disputes = if opened {
+                bond.open_disputes + 1
+            } else {
+                bond.open_disputes.saturating_sub(1)
+            };
+            set_validator_bond(env, &bond);
+        }
+    }
+}
+
+/// Take back the fees approving validators have not yet claimed for a
+/// milestone whose approval was overturned, sending them to the registry
+/// treasury
+fn forfeit_validator_fees(env: &Env, escrow: &EscrowInfo, milestone: &Milestone) {
+    let config = match get_validator_registry(env) {
+        Ok(config) => config,
+        Err(_) => return,
+    };
+    let approvers = validator_approvers(env, escrow, milestone.id);
+
+    let mut tokens = vec![env, escrow.token.clone()];
+    for entry in milestone.basket.iter() {
+        tokens.push_back(entry.token);
+    }
+    for token in tokens.iter() {
+        let credited = get_milestone_fees(env, milestone.project_id, milestone.id, &token);
+        if credited <= 0 {
+            continue;
+        }
+        set_milestone_fees(env, milestone.project_id, milestone.id, &token, 0);
+
+        let mut total_forfeited: Amount = 0;
+        for validator in approvers.iter() {
+            let unclaimed = get_validator_fees(env, &validator, &token);
+            let forfeited = credited.min(unclaimed);
+            if forfeited <= 0 {
+                continue;
+            }
+            set_validator_fees(env, &validator, &token, unclaimed - forfeited);
+            total_forfeited += forfeited;
+
+            publish(
+                env,
+                ValidatorFeesForfeited {
+                    project_id: milestone.project_id,
+                    milestone_id: milestone.id,
+                    validator,
+                    token: token.clone(),
+                    amount: forfeited,
+                },
+            );
+        }
+
+        if total_forfeited > 0 {
+            TokenClient::new(env, &token).transfer(
+                &env.current_contract_address(),
+                &config.treasury,
+                &total_forfeited,
+            );
+        }
+    }
+}
+
+/// Slash every validator that approved a milestone and send the stake to the
+/// treasury; backer-voted milestones have no validators to slash
+fn slash_approvers(env: &Env, escrow: &EscrowInfo, milestone_id: u64) -> Result<(), Error> {
+    let config = match get_validator_registry(env) {
+        Ok(config) => config,
+        Err(_) => return Ok(()),
+    };
+
+    let mut total_slashed: Amount = 0;
+    for validator in validator_approvers(env, escrow, milestone_id).iter() {
+        let mut bond = match get_validator_bond(env, &validator) {
+            Ok(bond) => bond,
+            Err(_) => continue,
+        };
+
+        // Unbonding stake is still at risk; take from the bonded stake first
+        let stake = bond
+            .bonded
+            .checked_add(bond.unbonding)
+            .ok_or(Error::InvalidInput)?;
+        let slashed = calculate_percentage(stake, config.slash_rate);
+        if slashed == 0 {
+            continue;
+        }
+        let from_bonded = slashed.min(bond.bonded);
+        bond.bonded -= from_bonded;
+        bond.unbonding -= slashed - from_bonded;
+        set_validator_bond(env, &bond);
+
+        total_slashed = total_slashed
+            .checked_add(slashed)
+            .ok_or(Error::InvalidInput)?;
+
+        publish(
+            env,
+            ValidatorSlashed {
+                project_id: escrow.project_id,
+                milestone_id,
+                validator,
+                amount: slashed,
+            },
+        );
+    }
+
+    if total_slashed > 0 {
+        TokenClient::new(env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &config.treasury,
+            &total_slashed,
+        );
+    }
+
+    Ok(())
+}
+
+/// Record a validator's vote and decide the milestone once a majority is reached
+fn cast_validator_vote(
+    env: &Env,
+    escrow: &mut EscrowInfo,
+    milestone_id: u64,
+    voter: &Address,
+    share: BasisPoints,
+) -> Result<(), Error> {
+    let project_id = escrow.project_id;
+
+    // Verify voter was a validator when the milestone was submitted and is still bonded
+    let validators = get_milestone_validators(env, project_id, milestone_id)
+        .unwrap_or(escrow.validators.clone());
+    validate_validator(&validators, voter)?;
+    require_bonded_validator(env, voter)?;
+
+    // Get milestone
+    let mut milestone = get_milestone(env, project_id, milestone_id)?;
+
+    // Validate milestone status
+    if milestone.status != MilestoneStatus::Submitted {
+        return Err(Error::InvalidMilestoneStatus);
+    }
+
+    // Check if validator already voted
+    if has_validator_voted(env, project_id, milestone_id, voter)? {
+        return Err(Error::AlreadyVoted);
+    }
+
+    // Update vote counts
+    if share > 0 {
+        milestone.approval_count = milestone
+            .approval_count
+            .checked_add(1)
+            .ok_or(Error::InvalidInput)?;
+    } else {
+        milestone.rejection_count = milestone
+            .rejection_count
+            .checked_add(1)
+            .ok_or(Error::InvalidInput)?;
+    }
+
+    // Record that this validator voted
+    set_validator_vote(env, project_id, milestone_id, voter, share)?;
+
+    // Check if milestone is approved or rejected
+    let required_approvals = (validators.len() * MILESTONE_APPROVAL_THRESHOLD) / 10000;
+
+    // Check for majority approval
+    if milestone.approval_count >= required_approvals {
+        approve_milestone(env, escrow, &mut milestone)?;
+    } else if milestone.rejection_count > validators.len() - required_approvals {
+        // Majority has rejected
+        reject_milestone(env, &mut milestone);
+    } else {
+        // Store updated milestone (vote recorded, but not yet finalized)
+        set_milestone(env, project_id, milestone_id, &milestone);
+    }
+
+    Ok(())
+}
+
+/// Record a backer's contribution-weighted vote on a submitted milestone
+fn cast_backer_vote(
+    env: &Env,
+    project_id: u64,
+    milestone_id: u64,
+    voter: &Address,
+    approve: bool,
+) -> Result<(), Error> {
+    let project_launch = get_project_launch(env)?;
+
+    // Get milestone
+    let mut milestone = get_milestone(env, project_id, milestone_id)?;
+
+    // Validate milestone status and voting window
+    if milestone.status != MilestoneStatus::Submitted {
+        return Err(Error::InvalidMilestoneStatus);
+    }
+    if env.ledger().timestamp() > milestone.voting_ends_at {
+        return Err(Error::VotingPeriodEnded);
+    }
+
+    // Check if backer already voted
+    if has_validator_voted(env, project_id, milestone_id, voter)? {
+        return Err(Error::AlreadyVoted);
+    }
+
+    // Voting weight is the backer's contribution to the project
+    let weight =
+        ProjectLaunchClient::new(env, &project_launch).get_user_contribution(&project_id, voter);
+    if weight <= 0 {
+        return Err(Error::NotABacker);
+    }
+
+    // Update vote counts and weights
+    if approve {
+        milestone.approval_count = milestone
+            .approval_count
+            .checked_add(1)
+            .ok_or(Error::InvalidInput)?;
+        milestone.approval_weight = milestone
+            .approval_weight
+            .checked_add(weight)
+            .ok_or(Error::InvalidInput)?;
+    } else {
+        milestone.rejection_count = milestone
+            .rejection_count
+            .checked_add(1)
+            .ok_or(Error::InvalidInput)?;
+        milestone.rejection_weight = milestone
+            .rejection_weight
+            .checked_add(weight)
+            .ok_or(Error::InvalidInput)?;
+    }
+
+    // Record that this backer voted
+    let share = if approve { FULL_SHARE } else { 0 };
+    set_validator_vote(env, project_id, milestone_id, voter, share)?;
+    set_milestone(env, project_id, milestone_id, &milestone);
+
+    publish(
+        env,
+        BackerVoteCast {
+            project_id,
+            milestone_id,
+            voter: voter.clone(),
+            approve,
+            weight,
+        },
+    );
+
+    Ok(())
+}
+
+/// Approve a milestone and pay out whatever part of it is due immediately
+///
+/// Validator-voted milestones are approved for the smallest share granted by
+/// an approving validator.
+fn approve_milestone(
+    env: &Env,
+    escrow: &mut EscrowInfo,
+    milestone: &mut Milestone,
+) -> Result<(), Error> {
+    let mut share = FULL_SHARE;
+    for vote in get_milestone_voters(env, milestone.project_id, milestone.id).iter() {
+        if vote.approve && vote.share < share {
+            share = vote.share;
+        }
+    }
+
+    milestone.status = MilestoneStatus::Approved;
+    milestone.approved_amount = calculate_percentage(milestone.amount, share);
+    milestone.basket = scale_basket(env, &milestone.basket, |entry| {
+        calculate_percentage(entry.amount, share)
+    });
+    milestone.decided_at = env.ledger().timestamp();
+    set_milestone(env, milestone.project_id, milestone.id, milestone);
+    hold_approver_stake(env, escrow, milestone);
+
+    // Emit approval event
+    publish(
+        env,
+        MilestoneApproved {
+            project_id: milestone.project_id,
+            milestone_id: milestone.id,
+            approval_count: milestone.approval_count,
+        },
+    );
+
+    release_due_funds(env, escrow, milestone)?;
+    Ok(())
+}
+
+/// Pay the due and unreleased part of an approved milestone, less validator
+/// fees, to the creator
+///
+/// Without a tranche schedule the whole approved amount is due at approval, or
+/// once the dispute window has passed when disputes are configured and the
+/// approval did not come from an upheld dispute. Returns the amount released
+/// in each token.
+fn release_due_funds(
+    env: &Env,
+    escrow: &mut EscrowInfo,
+    milestone: &mut Milestone,
+) -> Result<Vec<TokenAmount>, Error> {
+    // Nothing is paid out while the decision can still be disputed
+    let holdback = match get_dispute_config(env) {
+        Ok(config) => match get_dispute(env, milestone.project_id, milestone.id) {
+            Ok(dispute) if dispute.status == DisputeStatus::Upheld => 0,
+            _ => config.window,
+        },
+        Err(_) => 0,
+    };
+    let mut tranches = get_milestone_tranches(env, milestone.project_id, milestone.id);
+    if tranches.is_empty() {
+        tranches.push_back(Tranche {
+            share: FULL_SHARE,
+            delay: 0,
+        });
+    }
+
+    // Share of the approved amount whose tranches are due
+    let now = env.ledger().timestamp();
+    let mut due_share: BasisPoints = 0;
+    for tranche in tranches.iter() {
+        let due_at = milestone
+            .decided_at
+            .checked_add(tranche.delay.max(holdback))
+            .ok_or(Error::InvalidInput)?;
+        if now >= due_at {
+            due_share += tranche.share;
+        }
+    }
+    let mut released: Vec<TokenAmount> = Vec::new(env);
+    let due = due_amount(milestone.approved_amount, due_share);
+    let amount = due - milestone.released_amount;
+    if amount > 0 {
+        // Pull the funds back from the vault if they were parked there
+        ensure_liquid(env, escrow, amount);
+
+        // Release funds
+        release_milestone_funds(env, escrow, amount)?;
+        milestone.released_amount = due;
+
+        // Approving validators keep their fee share in escrow until claimed
+        let token = escrow.token.clone();
+        let validator_fees = pay_validator_fees(env, escrow, milestone, &token, amount);
+
+        // Perform token transfer to creator
+        let token_client = TokenClient::new(env, &token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &escrow.creator,
+            &(amount - validator_fees),
+        );
+
+        // Emit fund release event
+        publish(
+            env,
+            FundsReleased {
+                project_id: milestone.project_id,
+                milestone_id: milestone.id,
+                amount,
+            },
+        );
+        released.push_back(TokenAmount { token, amount });
+    }
+
+    // Each basket token is released in the same proportion
+    let mut basket = milestone.basket.clone();
+    for (index, mut entry) in milestone.basket.iter().enumerate() {
+        let due = due_amount(entry.approved_amount, due_share);
+        let amount = due - entry.released_amount;
+        if amount <= 0 {
+            continue;
+        }
+
+        let mut balance = get_token_balance(env, milestone.project_id, &entry.token);
+        balance.released_amount = balance
+            .released_amount
+            .checked_add(amount)
+            .ok_or(Error::InvalidInput)?;
+        if balance.released_amount > balance.total_deposited {
+            return Err(Error::InsufficientEscrowBalance);
+        }
+        set_token_balance(env, milestone.project_id, &balance);
+        entry.released_amount = due;
+        basket.set(index as u32, entry.clone());
+
+        let validator_fees = pay_validator_fees(env, escrow, milestone, &entry.token, amount);
+        TokenClient::new(env, &entry.token).transfer(
+            &env.current_contract_address(),
+            &escrow.creator,
+            &(amount - validator_fees),
+        );
+
+        publish(
+            env,
+            TokenFundsReleased {
+                project_id: milestone.project_id,
+                milestone_id: milestone.id,
+                token: entry.token.clone(),
+                amount,
+            },
+        );
+        released.push_back(TokenAmount {
+            token: entry.token,
+            amount,
+        });
+    }
+    milestone.basket = basket;
+
+    // Store updated escrow and milestone
+    set_escrow(env, escrow.project_id, escrow);
+    set_milestone(env, milestone.project_id, milestone.id, milestone);
+
+    Ok(released)
+}
+
+/// Part of an approved amount covered by the due share
+fn due_amount(approved: Amount, due_share: BasisPoints) -> Amount {
+    if due_share >= FULL_SHARE {
+        approved
+    } else {
+        calculate_percentage(approved, due_share)
+    }
+}
+
+/// Check whether an approved milestone has paid out everything in every token
+fn fully_released(milestone: &Milestone) -> bool {
+    milestone.released_amount >= milestone.approved_amount
+        && milestone
+            .basket
+            .iter()
+            .all(|entry| entry.released_amount >= entry.approved_amount)
+}
+
+/// Copy a milestone basket with each approved amount set by `approved`
+fn scale_basket(
+    env: &Env,
+    basket: &Vec<BasketAmount>,
+    approved: impl Fn(&BasketAmount) -> Amount,
+) -> Vec<BasketAmount> {
+    let mut scaled = Vec::new(env);
+    for mut entry in basket.iter() {
+        entry.approved_amount = approved(&entry);
+        scaled.push_back(entry);
+    }
+    scaled
+}
+
+/// Reject a milestone
+fn reject_milestone(env: &Env, milestone: &mut Milestone) {
+    milestone.status = MilestoneStatus::Rejected;
+    milestone.decided_at = env.ledger().timestamp();
+    set_milestone(env, milestone.project_id, milestone.id, milestone);
+
+    // Emit rejection event
+    publish(
+        env,
+        MilestoneRejected {
+            project_id: milestone.project_id,
+            milestone_id: milestone.id,
+            rejection_count: milestone.rejection_count,
+        },
+    );
+}
+
+/// Helper function to release milestone funds
+fn release_milestone_funds(
+    _env: &Env,
+    escrow: &mut EscrowInfo,
+    amount: Amount,
+) -> Result<(), Error> {
+    // Verify funds are not released more than once
+    let new_released = escrow
+        .released_amount
+        .checked_add(amount)
+        .ok_or(Error::InvalidInput)?;
+
+    if new_released > escrow.total_deposited {
         return Err(Error::InsufficientEscrowBalance);
     }
 
     escrow.released_amount = new_released;
     Ok(())
 }
+
+/// Withdraw principal from an escrow's vault position
+fn withdraw_from_vault(env: &Env, project_id: u64, position: &mut YieldPosition, amount: Amount) {
+    if amount <= 0 {
+        return;
+    }
+    let burned =
+        VaultClient::new(env, &position.vault).withdraw(&env.current_contract_address(), &amount);
+    position.shares -= burned;
+    position.principal -= amount;
+
+    publish(
+        env,
+        YieldDivested {
+            project_id,
+            vault: position.vault.clone(),
+            amount,
+        },
+    );
+}
+
+/// Make sure enough of the escrow's funds are outside its vault to pay `amount`
+fn ensure_liquid(env: &Env, escrow: &EscrowInfo, amount: Amount) {
+    let Ok(mut position) = get_yield_position(env, escrow.project_id) else {
+        return;
+    };
+    let liquid = escrow.total_deposited - escrow.released_amount - position.principal;
+    let shortfall = (amount - liquid).min(position.principal);
+    if shortfall > 0 {
+        withdraw_from_vault(env, escrow.project_id, &mut position, shortfall);
+        set_yield_position(env, escrow.project_id, &position);
+    }
+}
+
+/// Withdraw the yield accrued on a vault position and split it between the
+/// backers and the creator
+///
+/// Returns the yield paid out, or 0 when the deposit has not grown.
+fn harvest_position(
+    env: &Env,
+    escrow: &EscrowInfo,
+    config: &YieldConfig,
+    position: &mut YieldPosition,
+) -> Result<Amount, Error> {
+    let vault = VaultClient::new(env, &position.vault);
+    let accrued = vault.preview_redeem(&position.shares) - position.principal;
+    if accrued <= 0 {
+        return Ok(0);
+    }
+
+    let contract = env.current_contract_address();
+    position.shares -= vault.withdraw(&contract, &accrued);
+    position.harvested = position
+        .harvested
+        .checked_add(accrued)
+        .ok_or(Error::InvalidInput)?;
+
+    let backer_amount = calculate_percentage(accrued, config.backer_share);
+    let creator_amount = accrued - backer_amount;
+    let token_client = TokenClient::new(env, &escrow.token);
+    if backer_amount > 0 {
+        token_client.transfer(&contract, &config.backer_recipient, &backer_amount);
+    }
+    if creator_amount > 0 {
+        token_client.transfer(&contract, &escrow.creator, &creator_amount);
+    }
+
+    publish(
+        env,
+        YieldHarvested {
+            project_id: escrow.project_id,
+            backer_amount,
+            creator_amount,
+        },
+    );
+
+    Ok(accrued)
+}
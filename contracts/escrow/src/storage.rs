@@ -1,13 +1,34 @@
 use shared::errors::Error;
-use shared::types::{Amount, EscrowInfo, Milestone};
-use soroban_sdk::{Address, Env};
+use shared::types::{
+    Amount, ArbitratorVote, BackerVotingConfig, BasisPoints, BasketAmount, Dispute, DisputeConfig,
+    EscrowInfo, Milestone, MilestoneStatus, TokenBalance, Tranche, ValidatorBond,
+    ValidatorChangeProposal, ValidatorRegistryConfig, ValidatorVote, YieldConfig, YieldPosition,
+};
+use soroban_sdk::{Address, Env, Vec};
 
 /// Storage keys for escrow data structures
 const ESCROW_PREFIX: &str = "escrow";
 const MILESTONE_PREFIX: &str = "milestone";
 const MILESTONE_COUNTER_PREFIX: &str = "m_counter";
 const VALIDATOR_VOTE_PREFIX: &str = "v_vote";
+const MILESTONE_VOTERS_PREFIX: &str = "m_voters";
+const MILESTONE_VALIDATORS_PREFIX: &str = "m_valset";
+const VALIDATOR_CHANGE_PREFIX: &str = "v_change";
+const TRANCHES_PREFIX: &str = "tranches";
+const BACKER_VOTING_PREFIX: &str = "b_voting";
+const VALIDATOR_BOND_PREFIX: &str = "v_bond";
+const VALIDATOR_FEES_PREFIX: &str = "v_fees";
+const MILESTONE_FEES_PREFIX: &str = "m_fees";
+const DISPUTE_PREFIX: &str = "dispute";
+const DISPUTE_VOTES_PREFIX: &str = "d_votes";
+const VAULT_WHITELIST_PREFIX: &str = "vault_ok";
+const YIELD_CONFIG_PREFIX: &str = "y_config";
+const YIELD_POSITION_PREFIX: &str = "y_pos";
+const TOKEN_BALANCE_PREFIX: &str = "t_bal";
 const ADMIN_KEY: &str = "admin";
+const VALIDATOR_REGISTRY_KEY: &str = "v_registry";
+const DISPUTE_CONFIG_KEY: &str = "d_config";
+const PROJECT_LAUNCH_KEY: &str = "p_launch";
 
 /// Store platform admin
 pub fn set_admin(env: &Env, admin: &Address) {
@@ -48,6 +69,298 @@ pub fn escrow_exists(env: &Env, project_id: u64) -> bool {
     env.storage().persistent().has(&key)
 }
 
+/// Store deposits and releases of one of an escrow's other tokens
+pub fn set_token_balance(env: &Env, project_id: u64, balance: &TokenBalance) {
+    let key = (TOKEN_BALANCE_PREFIX, project_id, &balance.token);
+    env.storage().persistent().set(&key, balance);
+}
+
+/// Retrieve deposits and releases of one of an escrow's other tokens
+pub fn get_token_balance(env: &Env, project_id: u64, token: &Address) -> TokenBalance {
+    let key = (TOKEN_BALANCE_PREFIX, project_id, token);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64, &Address), TokenBalance>(&key)
+        .unwrap_or(TokenBalance {
+            token: token.clone(),
+            total_deposited: 0,
+            released_amount: 0,
+        })
+}
+
+/// Store backer voting configuration for an escrow
+pub fn set_backer_voting_config(env: &Env, project_id: u64, config: &BackerVotingConfig) {
+    let key = (BACKER_VOTING_PREFIX, project_id);
+    env.storage().persistent().set(&key, config);
+}
+
+/// Retrieve backer voting configuration for an escrow
+pub fn get_backer_voting_config(env: &Env, project_id: u64) -> Result<BackerVotingConfig, Error> {
+    let key = (BACKER_VOTING_PREFIX, project_id);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64), BackerVotingConfig>(&key)
+        .ok_or(Error::NotFound)
+}
+
+/// Store the validator set a milestone submission is voted on by
+pub fn set_milestone_validators(
+    env: &Env,
+    project_id: u64,
+    milestone_id: u64,
+    validators: &Vec<Address>,
+) {
+    let key = (MILESTONE_VALIDATORS_PREFIX, project_id, milestone_id);
+    env.storage().persistent().set(&key, validators);
+}
+
+/// Retrieve the validator set a milestone submission is voted on by
+pub fn get_milestone_validators(
+    env: &Env,
+    project_id: u64,
+    milestone_id: u64,
+) -> Result<Vec<Address>, Error> {
+    let key = (MILESTONE_VALIDATORS_PREFIX, project_id, milestone_id);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64, u64), Vec<Address>>(&key)
+        .ok_or(Error::NotFound)
+}
+
+/// Store a pending validator change for an escrow
+pub fn set_validator_change(env: &Env, proposal: &ValidatorChangeProposal) {
+    let key = (VALIDATOR_CHANGE_PREFIX, proposal.project_id);
+    env.storage().persistent().set(&key, proposal);
+}
+
+/// Retrieve the pending validator change for an escrow
+pub fn get_validator_change(env: &Env, project_id: u64) -> Result<ValidatorChangeProposal, Error> {
+    let key = (VALIDATOR_CHANGE_PREFIX, project_id);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64), ValidatorChangeProposal>(&key)
+        .ok_or(Error::NotFound)
+}
+
+/// Remove the pending validator change for an escrow
+pub fn remove_validator_change(env: &Env, project_id: u64) {
+    let key = (VALIDATOR_CHANGE_PREFIX, project_id);
+    env.storage().persistent().remove(&key);
+}
+
+/// Store the release schedule of a milestone
+pub fn set_milestone_tranches(
+    env: &Env,
+    project_id: u64,
+    milestone_id: u64,
+    tranches: &Vec<Tranche>,
+) {
+    let key = (TRANCHES_PREFIX, project_id, milestone_id);
+    env.storage().persistent().set(&key, tranches);
+}
+
+/// Retrieve the release schedule of a milestone (empty when paid in one go)
+pub fn get_milestone_tranches(env: &Env, project_id: u64, milestone_id: u64) -> Vec<Tranche> {
+    let key = (TRANCHES_PREFIX, project_id, milestone_id);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64, u64), Vec<Tranche>>(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Store validator registry configuration
+pub fn set_validator_registry(env: &Env, config: &ValidatorRegistryConfig) {
+    env.storage()
+        .instance()
+        .set(&VALIDATOR_REGISTRY_KEY, config);
+}
+
+/// Retrieve validator registry configuration
+pub fn get_validator_registry(env: &Env) -> Result<ValidatorRegistryConfig, Error> {
+    env.storage()
+        .instance()
+        .get::<&str, ValidatorRegistryConfig>(&VALIDATOR_REGISTRY_KEY)
+        .ok_or(Error::NotInitialized)
+}
+
+/// Store a validator's bond
+pub fn set_validator_bond(env: &Env, bond: &ValidatorBond) {
+    let key = (VALIDATOR_BOND_PREFIX, bond.validator.clone());
+    env.storage().persistent().set(&key, bond);
+}
+
+/// Retrieve a validator's bond
+pub fn get_validator_bond(env: &Env, validator: &Address) -> Result<ValidatorBond, Error> {
+    let key = (VALIDATOR_BOND_PREFIX, validator.clone());
+    env.storage()
+        .persistent()
+        .get::<(&str, Address), ValidatorBond>(&key)
+        .ok_or(Error::NotFound)
+}
+
+/// Store the unclaimed fees a validator has earned in a token
+pub fn set_validator_fees(env: &Env, validator: &Address, token: &Address, amount: Amount) {
+    let key = (VALIDATOR_FEES_PREFIX, validator.clone(), token.clone());
+    env.storage().persistent().set(&key, &amount);
+}
+
+/// Retrieve the unclaimed fees a validator has earned in a token
+pub fn get_validator_fees(env: &Env, validator: &Address, token: &Address) -> Amount {
+    let key = (VALIDATOR_FEES_PREFIX, validator.clone(), token.clone());
+    env.storage()
+        .persistent()
+        .get::<(&str, Address, Address), Amount>(&key)
+        .unwrap_or(0)
+}
+
+/// Store the fees each approving validator has been credited for a milestone in a token
+pub fn set_milestone_fees(
+    env: &Env,
+    project_id: u64,
+    milestone_id: u64,
+    token: &Address,
+    amount: Amount,
+) {
+    let key = (
+        MILESTONE_FEES_PREFIX,
+        project_id,
+        milestone_id,
+        token.clone(),
+    );
+    env.storage().persistent().set(&key, &amount);
+}
+
+/// Retrieve the fees each approving validator has been credited for a milestone in a token
+pub fn get_milestone_fees(
+    env: &Env,
+    project_id: u64,
+    milestone_id: u64,
+    token: &Address,
+) -> Amount {
+    let key = (
+        MILESTONE_FEES_PREFIX,
+        project_id,
+        milestone_id,
+        token.clone(),
+    );
+    env.storage()
+        .persistent()
+        .get::<(&str, u64, u64, Address), Amount>(&key)
+        .unwrap_or(0)
+}
+
+/// Store a milestone dispute
+pub fn set_dispute(env: &Env, dispute: &Dispute) {
+    let key = (DISPUTE_PREFIX, dispute.project_id, dispute.milestone_id);
+    env.storage().persistent().set(&key, dispute);
+}
+
+/// Retrieve a milestone dispute
+pub fn get_dispute(env: &Env, project_id: u64, milestone_id: u64) -> Result<Dispute, Error> {
+    let key = (DISPUTE_PREFIX, project_id, milestone_id);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64, u64), Dispute>(&key)
+        .ok_or(Error::NotFound)
+}
+
+/// Store the ProjectLaunch contract backer voting reads contributions from
+pub fn set_project_launch(env: &Env, project_launch: &Address) {
+    env.storage()
+        .instance()
+        .set(&PROJECT_LAUNCH_KEY, project_launch);
+}
+
+/// Retrieve the ProjectLaunch contract backer voting reads contributions from
+pub fn get_project_launch(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get::<&str, Address>(&PROJECT_LAUNCH_KEY)
+        .ok_or(Error::NotInitialized)
+}
+
+/// Store dispute configuration
+pub fn set_dispute_config(env: &Env, config: &DisputeConfig) {
+    env.storage().instance().set(&DISPUTE_CONFIG_KEY, config);
+}
+
+/// Retrieve dispute configuration
+pub fn get_dispute_config(env: &Env) -> Result<DisputeConfig, Error> {
+    env.storage()
+        .instance()
+        .get::<&str, DisputeConfig>(&DISPUTE_CONFIG_KEY)
+        .ok_or(Error::NotInitialized)
+}
+
+/// Record an arbitrator's vote on a dispute
+pub fn add_dispute_vote(env: &Env, project_id: u64, milestone_id: u64, vote: &ArbitratorVote) {
+    let mut votes = get_dispute_votes(env, project_id, milestone_id);
+    votes.push_back(vote.clone());
+    let key = (DISPUTE_VOTES_PREFIX, project_id, milestone_id);
+    env.storage().persistent().set(&key, &votes);
+}
+
+/// Retrieve the arbitrator votes cast on a dispute
+pub fn get_dispute_votes(env: &Env, project_id: u64, milestone_id: u64) -> Vec<ArbitratorVote> {
+    let key = (DISPUTE_VOTES_PREFIX, project_id, milestone_id);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64, u64), Vec<ArbitratorVote>>(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Allow or disallow a vault for escrow yield
+pub fn set_vault_whitelisted(env: &Env, vault: &Address, whitelisted: bool) {
+    let key = (VAULT_WHITELIST_PREFIX, vault);
+    if whitelisted {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Check if a vault may hold escrow funds
+pub fn is_vault_whitelisted(env: &Env, vault: &Address) -> bool {
+    let key = (VAULT_WHITELIST_PREFIX, vault);
+    env.storage().persistent().has(&key)
+}
+
+/// Store yield settings for an escrow
+pub fn set_yield_config(env: &Env, project_id: u64, config: &YieldConfig) {
+    let key = (YIELD_CONFIG_PREFIX, project_id);
+    env.storage().persistent().set(&key, config);
+}
+
+/// Retrieve yield settings for an escrow
+pub fn get_yield_config(env: &Env, project_id: u64) -> Result<YieldConfig, Error> {
+    let key = (YIELD_CONFIG_PREFIX, project_id);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64), YieldConfig>(&key)
+        .ok_or(Error::NotFound)
+}
+
+/// Store an escrow's vault position
+pub fn set_yield_position(env: &Env, project_id: u64, position: &YieldPosition) {
+    let key = (YIELD_POSITION_PREFIX, project_id);
+    env.storage().persistent().set(&key, position);
+}
+
+/// Retrieve an escrow's vault position
+pub fn get_yield_position(env: &Env, project_id: u64) -> Result<YieldPosition, Error> {
+    let key = (YIELD_POSITION_PREFIX, project_id);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64), YieldPosition>(&key)
+        .ok_or(Error::NotFound)
+}
+
+/// Remove an escrow's vault position once it is fully withdrawn
+pub fn remove_yield_position(env: &Env, project_id: u64) {
+    let key = (YIELD_POSITION_PREFIX, project_id);
+    env.storage().persistent().remove(&key);
+}
+
 /// Store milestone information
 pub fn set_milestone(env: &Env, project_id: u64, milestone_id: u64, milestone: &Milestone) {
     let key = (MILESTONE_PREFIX, project_id, milestone_id);
@@ -78,18 +391,38 @@ pub fn get_milestone_counter(env: &Env, project_id: u64) -> Result<u64, Error> {
         .ok_or(Error::NotFound)
 }
 
-/// Record that a validator voted on a milestone
+/// Record a validator's vote on a milestone; a zero share is a rejection
 pub fn set_validator_vote(
     env: &Env,
     project_id: u64,
     milestone_id: u64,
     validator: &Address,
+    share: BasisPoints,
 ) -> Result<(), Error> {
+    let approve = share > 0;
     let key = (VALIDATOR_VOTE_PREFIX, project_id, milestone_id, validator);
-    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().set(&key, &approve);
+
+    let mut voters = get_milestone_voters(env, project_id, milestone_id);
+    voters.push_back(ValidatorVote {
+        validator: validator.clone(),
+        approve,
+        share,
+    });
+    let voters_key = (MILESTONE_VOTERS_PREFIX, project_id, milestone_id);
+    env.storage().persistent().set(&voters_key, &voters);
     Ok(())
 }
 
+/// Retrieve the votes cast on the current submission of a milestone
+pub fn get_milestone_voters(env: &Env, project_id: u64, milestone_id: u64) -> Vec<ValidatorVote> {
+    let key = (MILESTONE_VOTERS_PREFIX, project_id, milestone_id);
+    env.storage()
+        .persistent()
+        .get::<(&str, u64, u64), Vec<ValidatorVote>>(&key)
+        .unwrap_or(Vec::new(env))
+}
+
 /// Check if a validator has already voted on a milestone
 pub fn has_validator_voted(
     env: &Env,
@@ -103,13 +436,17 @@ pub fn has_validator_voted(
 
 /// Clear all validator votes for a milestone (used when resubmitting)
 pub fn clear_milestone_voters(env: &Env, project_id: u64, milestone_id: u64) {
-    // Get the escrow to know how many validators there are
-    if let Ok(escrow) = get_escrow(env, project_id) {
-        for validator in escrow.validators.iter() {
-            let key = (VALIDATOR_VOTE_PREFIX, project_id, milestone_id, validator);
-            env.storage().persistent().remove(&key);
-        }
+    for vote in get_milestone_voters(env, project_id, milestone_id).iter() {
+        let key = (
+            VALIDATOR_VOTE_PREFIX,
+            project_id,
+            milestone_id,
+            vote.validator,
+        );
+        env.storage().persistent().remove(&key);
     }
+    let voters_key = (MILESTONE_VOTERS_PREFIX, project_id, milestone_id);
+    env.storage().persistent().remove(&voters_key);
 }
 
 /// Update vote counts for a milestone (alternative approach if needed)
@@ -130,7 +467,57 @@ pub fn set_milestone_votes(
     set_milestone(env, project_id, milestone_id, &milestone);
 }
 
-/// Calculate total amount allocated to approved and submitted milestones
+/// Amount of the escrow a milestone still holds on to
+///
+/// Decided milestones keep only what was approved; a rejected milestone keeps
+/// only what was paid out before the rejection. A disputed rejection reserves
+/// the full amount as approved until the dispute is settled.
+pub fn milestone_allocation(milestone: &Milestone) -> Amount {
+    match milestone.status {
+        MilestoneStatus::Approved | MilestoneStatus::Rejected | MilestoneStatus::Disputed => {
+            milestone.approved_amount
+        }
+        _ => milestone.amount,
+    }
+}
+
+/// Amount of one of the escrow's other tokens a milestone still holds on to
+pub fn basket_allocation(milestone: &Milestone, entry: &BasketAmount) -> Amount {
+    match milestone.status {
+        MilestoneStatus::Approved | MilestoneStatus::Rejected | MilestoneStatus::Disputed => {
+            entry.approved_amount
+        }
+        _ => entry.amount,
+    }
+}
+
+/// Calculate total amount of one of the escrow's other tokens allocated to milestones
+pub fn get_total_token_allocation(
+    env: &Env,
+    project_id: u64,
+    token: &Address,
+) -> Result<Amount, Error> {
+    let counter = get_milestone_counter(env, project_id)?;
+    let mut total: Amount = 0;
+
+    for milestone_id in 0..counter {
+        if let Ok(milestone) = get_milestone(env, project_id, milestone_id) {
+            for entry in milestone
+                .basket
+                .iter()
+                .filter(|entry| entry.token == *token)
+            {
+                total = total
+                    .checked_add(basket_allocation(&milestone, &entry))
+                    .ok_or(Error::InvalidInput)?;
+            }
+        }
+    }
+
+    Ok(total)
+}
+
+/// Calculate total amount allocated to milestones
 pub fn get_total_milestone_amount(env: &Env, project_id: u64) -> Result<Amount, Error> {
     // Get milestone counter to know how many milestones exist
     let counter = get_milestone_counter(env, project_id)?;
@@ -139,7 +526,7 @@ pub fn get_total_milestone_amount(env: &Env, project_id: u64) -> Result<Amount,
     for milestone_id in 0..counter {
         if let Ok(milestone) = get_milestone(env, project_id, milestone_id) {
             total = total
-                .checked_add(milestone.amount)
+                .checked_add(milestone_allocation(&milestone))
                 .ok_or(Error::InvalidInput)?;
         }
     }
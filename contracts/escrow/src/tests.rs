@@ -1,13 +1,290 @@
 #![cfg(test)]
 
+#[allow(clippy::module_inception)]
 mod tests {
     use crate::{EscrowContract, EscrowContractClient};
-    use shared::types::MilestoneStatus;
+    use mock_vault::{MockVault, MockVaultClient};
+    use shared::{
+        errors::Error,
+        types::{
+            BackerVotingConfig, DisputeConfig, DisputeStatus, MilestoneStatus, Project,
+            ProjectStatus, TokenAmount, Tranche, ValidatorRegistryConfig, VotingMode, YieldConfig,
+        },
+    };
     use soroban_sdk::{
+        contract, contractimpl, contracttype,
         testutils::{Address as _, Ledger},
-        Address, BytesN, Env, Vec,
+        token, Address, Bytes, BytesN, Env, Vec,
     };
 
+    #[contracttype]
+    enum MockKey {
+        TotalRaised,
+        Creator,
+        Status,
+        Contribution(Address),
+    }
+
+    /// Stand-in for the ProjectLaunch contract that records contributions directly
+    #[contract]
+    pub struct MockProjectLaunch;
+
+    #[contractimpl]
+    impl MockProjectLaunch {
+        pub fn set_contribution(env: Env, contributor: Address, amount: i128) {
+            let raised: i128 = env
+                .storage()
+                .instance()
+                .get(&MockKey::TotalRaised)
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&MockKey::TotalRaised, &(raised + amount));
+            env.storage()
+                .instance()
+                .set(&MockKey::Contribution(contributor), &amount);
+        }
+
+        pub fn set_project(env: Env, creator: Address, status: ProjectStatus) {
+            env.storage().instance().set(&MockKey::Creator, &creator);
+            env.storage().instance().set(&MockKey::Status, &status);
+        }
+
+        pub fn get_project(env: Env, _project_id: u64) -> Project {
+            Project {
+                creator: env
+                    .storage()
+                    .instance()
+                    .get(&MockKey::Creator)
+                    .unwrap_or(env.current_contract_address()),
+                funding_goal: 0,
+                deadline: 0,
+                token: env.current_contract_address(),
+                status: env
+                    .storage()
+                    .instance()
+                    .get(&MockKey::Status)
+                    .unwrap_or(ProjectStatus::Completed),
+                metadata_hash: Bytes::new(&env),
+                total_raised: env
+                    .storage()
+                    .instance()
+                    .get(&MockKey::TotalRaised)
+                    .unwrap_or(0),
+                created_at: 0,
+            }
+        }
+
+        pub fn get_user_contribution(env: Env, _project_id: u64, contributor: Address) -> i128 {
+            env.storage()
+                .instance()
+                .get(&MockKey::Contribution(contributor))
+                .unwrap_or(0)
+        }
+    }
+
+    struct BackerSetup<'a> {
+        env: Env,
+        client: EscrowContractClient<'a>,
+        launch: MockProjectLaunchClient<'a>,
+        token: token::Client<'a>,
+        creator: Address,
+    }
+
+    /// Escrow for project 1 in backer voting mode, funded with 1000 tokens
+    fn setup_backer_escrow<'a>(quorum: u32, threshold: u32) -> BackerSetup<'a> {
+        let env = Env::default();
+        env.ledger().set_timestamp(1000);
+        env.mock_all_auths();
+
+        let creator = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let client = EscrowContractClient::new(&env, &env.register_contract(None, EscrowContract));
+        let launch =
+            MockProjectLaunchClient::new(&env, &env.register_contract(None, MockProjectLaunch));
+
+        client.initialize_admin(&Address::generate(&env));
+        client.set_project_launch(&launch.address);
+        launch.set_project(&creator, &ProjectStatus::Completed);
+
+        let config = BackerVotingConfig {
+            quorum,
+            threshold,
+            voting_period: 1000,
+        };
+        client.initialize_backer_voting(&1, &creator, &token_id, &config);
+        client.deposit(&1, &1000);
+        token::StellarAssetClient::new(&env, &token_id).mint(&client.address, &1000);
+
+        BackerSetup {
+            token: token::Client::new(&env, &token_id),
+            env,
+            client,
+            launch,
+            creator,
+        }
+    }
+
+    struct StakingSetup<'a> {
+        env: Env,
+        client: EscrowContractClient<'a>,
+        token: token::Client<'a>,
+        stake_token: token::Client<'a>,
+        creator: Address,
+        treasury: Address,
+        validators: Vec<Address>,
+    }
+
+    /// Validator registry (min bond 100, 10% fee share, 50% slash rate) with
+    /// three validators bonded 500 each; nothing is escrowed yet
+    fn setup_staking<'a>() -> StakingSetup<'a> {
+        let (env, creator, _, _, validators) = create_test_env();
+        env.mock_all_auths();
+        let client = EscrowContractClient::new(&env, &env.register_contract(None, EscrowContract));
+
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin.clone())
+            .address();
+        let stake_token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        let treasury = Address::generate(&env);
+
+        client.initialize_admin(&Address::generate(&env));
+        client.configure_validator_registry(&ValidatorRegistryConfig {
+            stake_token: stake_token_id.clone(),
+            min_bond: 100,
+            unbonding_period: 500,
+            fee_share: 1000,
+            slash_rate: 5000,
+            treasury: treasury.clone(),
+        });
+
+        let stake_admin = token::StellarAssetClient::new(&env, &stake_token_id);
+        for validator in validators.iter() {
+            stake_admin.mint(&validator, &500);
+            client.bond_validator(&validator, &500);
+        }
+
+        StakingSetup {
+            token: token::Client::new(&env, &token_id),
+            stake_token: token::Client::new(&env, &stake_token_id),
+            env,
+            client,
+            creator,
+            treasury,
+            validators,
+        }
+    }
+
+    /// Escrow project 1 with 1000 tokens and submit a 400 milestone
+    fn submit_staked_milestone(setup: &StakingSetup) {
+        let client = &setup.client;
+        client.initialize(&1, &setup.creator, &setup.token.address, &setup.validators);
+        client.deposit(&1, &1000);
+        token::StellarAssetClient::new(&setup.env, &setup.token.address)
+            .mint(&client.address, &1000);
+
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&setup.env, &[1u8; 32]),
+            &400,
+            &Vec::new(&setup.env),
+        );
+        client.submit_milestone(&1, &0, &BytesN::from_array(&setup.env, &[9u8; 32]));
+    }
+
+    /// Escrow project 1 and approve its 400 milestone with the first validator
+    fn approve_staked_milestone(setup: &StakingSetup) {
+        submit_staked_milestone(setup);
+        setup
+            .client
+            .vote_milestone(&1, &0, &setup.validators.get(0).unwrap(), &true);
+    }
+
+    struct DisputeSetup<'a> {
+        bond_token: token::Client<'a>,
+        arbitrators: Vec<Address>,
+        backer: Address,
+        dispute_treasury: Address,
+    }
+
+    /// Disputes with a 50 bond, 100 second window and three arbitrators; the
+    /// backer and the creator hold 100 bond tokens each
+    fn setup_disputes<'a>(setup: &StakingSetup) -> DisputeSetup<'a> {
+        let env = &setup.env;
+        let bond_token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        let launch =
+            MockProjectLaunchClient::new(env, &env.register_contract(None, MockProjectLaunch));
+
+        let backer = Address::generate(env);
+        launch.set_contribution(&backer, &500);
+        setup.client.set_project_launch(&launch.address);
+
+        let bond_admin = token::StellarAssetClient::new(env, &bond_token_id);
+        bond_admin.mint(&backer, &100);
+        bond_admin.mint(&setup.creator, &100);
+
+        let mut arbitrators = Vec::new(env);
+        for _ in 0..3 {
+            arbitrators.push_back(Address::generate(env));
+        }
+        let dispute_treasury = Address::generate(env);
+
+        setup.client.configure_disputes(&DisputeConfig {
+            bond_token: bond_token_id.clone(),
+            bond_amount: 50,
+            window: 100,
+            arbitrators: arbitrators.clone(),
+            treasury: dispute_treasury.clone(),
+        });
+
+        DisputeSetup {
+            bond_token: token::Client::new(env, &bond_token_id),
+            arbitrators,
+            backer,
+            dispute_treasury,
+        }
+    }
+
+    /// Validator escrow for project 1 funded with 1000 tokens and a pending 400 milestone
+    fn setup_funded_escrow<'a>(
+        validators: &Vec<Address>,
+    ) -> (Env, EscrowContractClient<'a>, token::Client<'a>, Address) {
+        let env = validators.env().clone();
+        env.ledger().set_timestamp(1000);
+        env.mock_all_auths();
+        let client = EscrowContractClient::new(&env, &env.register_contract(None, EscrowContract));
+        let creator = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.initialize(&1, &creator, &token_id, validators);
+        client.deposit(&1, &1000);
+        token::StellarAssetClient::new(&env, &token_id).mint(&client.address, &1000);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &400,
+            &Vec::new(&env),
+        );
+
+        (
+            env.clone(),
+            client,
+            token::Client::new(&env, &token_id),
+            creator,
+        )
+    }
+
     fn create_test_env() -> (Env, Address, Address, Address, Vec<Address>) {
         let env = Env::default();
         env.ledger().set_timestamp(1000);
@@ -26,7 +303,7 @@ mod tests {
         (env, creator, token, validator3, validators)
     }
 
-    fn create_client(env: &Env) -> EscrowContractClient {
+    fn create_client(env: &Env) -> EscrowContractClient<'_> {
         EscrowContractClient::new(env, &env.register_contract(None, EscrowContract))
     }
 
@@ -36,7 +313,7 @@ mod tests {
         let client = create_client(&env);
         env.mock_all_auths();
 
-        let result = client.initialize(&1, &creator, &token, &validators);
+        client.initialize(&1, &creator, &token, &validators);
 
         // Verify escrow was created
         let escrow = client.get_escrow(&1);
@@ -117,7 +394,7 @@ mod tests {
         client.deposit(&1, &1000);
 
         let description_hash = BytesN::from_array(&env, &[1u8; 32]);
-        client.create_milestone(&1, &description_hash, &500);
+        client.create_milestone(&1, &description_hash, &500, &Vec::new(&env));
 
         let milestone = client.get_milestone(&1, &0);
         assert_eq!(milestone.id, 0);
@@ -137,7 +414,7 @@ mod tests {
         client.deposit(&1, &500);
 
         let description_hash = BytesN::from_array(&env, &[2u8; 32]);
-        let result = client.try_create_milestone(&1, &description_hash, &1000);
+        let result = client.try_create_milestone(&1, &description_hash, &1000, &Vec::new(&env));
 
         assert!(result.is_err());
     }
@@ -155,9 +432,9 @@ mod tests {
         let desc2 = BytesN::from_array(&env, &[2u8; 32]);
         let desc3 = BytesN::from_array(&env, &[3u8; 32]);
 
-        client.create_milestone(&1, &desc1, &1000);
-        client.create_milestone(&1, &desc2, &1000);
-        client.create_milestone(&1, &desc3, &1000);
+        client.create_milestone(&1, &desc1, &1000, &Vec::new(&env));
+        client.create_milestone(&1, &desc2, &1000, &Vec::new(&env));
+        client.create_milestone(&1, &desc3, &1000, &Vec::new(&env));
 
         // Verify all milestones exist
         assert!(client.get_milestone(&1, &0).id == 0);
@@ -178,7 +455,7 @@ mod tests {
         client.deposit(&1, &1000);
 
         let description_hash = BytesN::from_array(&env, &[1u8; 32]);
-        client.create_milestone(&1, &description_hash, &500);
+        client.create_milestone(&1, &description_hash, &500, &Vec::new(&env));
 
         let proof_hash = BytesN::from_array(&env, &[9u8; 32]);
         client.submit_milestone(&1, &0, &proof_hash);
@@ -199,7 +476,7 @@ mod tests {
         client.deposit(&1, &1000);
 
         let description_hash = BytesN::from_array(&env, &[1u8; 32]);
-        client.create_milestone(&1, &description_hash, &500);
+        client.create_milestone(&1, &description_hash, &500, &Vec::new(&env));
 
         let proof_hash = BytesN::from_array(&env, &[9u8; 32]);
         client.submit_milestone(&1, &0, &proof_hash);
@@ -262,7 +539,7 @@ mod tests {
         client.deposit(&1, &1000);
 
         let description_hash = BytesN::from_array(&env, &[1u8; 32]);
-        client.create_milestone(&1, &description_hash, &500);
+        client.create_milestone(&1, &description_hash, &500, &Vec::new(&env));
 
         // Check initial status is Pending
         let milestone = client.get_milestone(&1, &0);
@@ -333,4 +610,1481 @@ mod tests {
         let escrow1 = client.get_escrow(&1);
         assert_eq!(escrow1.project_id, 1);
     }
+
+    #[test]
+    fn test_validator_vote_releases_funds() {
+        let (env, creator, _, _, validators) = create_test_env();
+        let client = create_client(&env);
+        env.mock_all_auths();
+
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        let token = token::Client::new(&env, &token_id);
+
+        client.initialize(&1, &creator, &token_id, &validators);
+        client.deposit(&1, &1000);
+        token::StellarAssetClient::new(&env, &token_id).mint(&client.address, &1000);
+
+        let description_hash = BytesN::from_array(&env, &[1u8; 32]);
+        client.create_milestone(&1, &description_hash, &400, &Vec::new(&env));
+        client.submit_milestone(&1, &0, &BytesN::from_array(&env, &[9u8; 32]));
+
+        client.vote_milestone(&1, &0, &validators.get(0).unwrap(), &true);
+        let milestone = client.get_milestone(&1, &0);
+        assert_eq!(milestone.status, MilestoneStatus::Approved);
+        assert_eq!(milestone.approval_count, 1);
+        assert_eq!(token.balance(&creator), 400);
+        assert_eq!(client.get_escrow(&1).released_amount, 400);
+    }
+
+    #[test]
+    fn test_initialize_backer_voting() {
+        let setup = setup_backer_escrow(5000, 6000);
+
+        let escrow = setup.client.get_escrow(&1);
+        assert_eq!(escrow.voting_mode, VotingMode::Backers);
+        assert_eq!(escrow.validators.len(), 0);
+
+        assert_eq!(setup.client.get_project_launch(), setup.launch.address);
+        let config = setup.client.get_backer_voting_config(&1);
+        assert_eq!(config.quorum, 5000);
+        assert_eq!(config.threshold, 6000);
+    }
+
+    #[test]
+    fn test_initialize_backer_voting_invalid_config() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = create_client(&env);
+        let creator = Address::generate(&env);
+        let token = Address::generate(&env);
+
+        let mut config = BackerVotingConfig {
+            quorum: 5000,
+            threshold: 0,
+            voting_period: 1000,
+        };
+        let result = client.try_initialize_backer_voting(&1, &creator, &token, &config);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        config.threshold = 6000;
+        config.quorum = 10001;
+        let result = client.try_initialize_backer_voting(&1, &creator, &token, &config);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        config.quorum = 5000;
+        config.voting_period = 0;
+        let result = client.try_initialize_backer_voting(&1, &creator, &token, &config);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+    }
+
+    #[test]
+    fn test_initialize_backer_voting_checks_project() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = create_client(&env);
+        let creator = Address::generate(&env);
+        let token = Address::generate(&env);
+        let config = BackerVotingConfig {
+            quorum: 5000,
+            threshold: 6000,
+            voting_period: 1000,
+        };
+        client.initialize_admin(&Address::generate(&env));
+
+        // Projects and contributions only come from the admin's ProjectLaunch
+        let result = client.try_initialize_backer_voting(&1, &creator, &token, &config);
+        assert_eq!(result, Err(Ok(Error::NotInitialized)));
+
+        let launch =
+            MockProjectLaunchClient::new(&env, &env.register_contract(None, MockProjectLaunch));
+        client.set_project_launch(&launch.address);
+        launch.set_project(&Address::generate(&env), &ProjectStatus::Completed);
+        let result = client.try_initialize_backer_voting(&1, &creator, &token, &config);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        launch.set_project(&creator, &ProjectStatus::Active);
+        let result = client.try_initialize_backer_voting(&1, &creator, &token, &config);
+        assert_eq!(result, Err(Ok(Error::FundingGoalNotReached)));
+
+        launch.set_project(&creator, &ProjectStatus::Completed);
+        client.initialize_backer_voting(&1, &creator, &token, &config);
+        assert_eq!(client.get_escrow(&1).voting_mode, VotingMode::Backers);
+    }
+
+    #[test]
+    fn test_backer_vote_weighted_approval() {
+        let setup = setup_backer_escrow(5000, 6000);
+        let env = &setup.env;
+        let client = &setup.client;
+
+        let backer1 = Address::generate(env);
+        let backer2 = Address::generate(env);
+        let backer3 = Address::generate(env);
+        setup.launch.set_contribution(&backer1, &600);
+        setup.launch.set_contribution(&backer2, &300);
+        setup.launch.set_contribution(&backer3, &100);
+
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(env, &[1u8; 32]),
+            &500,
+            &Vec::new(env),
+        );
+        client.submit_milestone(&1, &0, &BytesN::from_array(env, &[9u8; 32]));
+        assert_eq!(client.get_milestone(&1, &0).voting_ends_at, 2000);
+
+        client.vote_milestone(&1, &0, &backer1, &true);
+        client.vote_milestone(&1, &0, &backer2, &false);
+
+        let milestone = client.get_milestone(&1, &0);
+        assert_eq!(milestone.status, MilestoneStatus::Submitted);
+        assert_eq!(milestone.approval_weight, 600);
+        assert_eq!(milestone.rejection_weight, 300);
+
+        // Votes are only counted once the window has closed
+        let result = client.try_finalize_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::VotingPeriodActive)));
+
+        env.ledger().set_timestamp(2001);
+        assert_eq!(client.finalize_milestone(&1, &0), MilestoneStatus::Approved);
+        assert_eq!(setup.token.balance(&setup.creator), 500);
+        assert_eq!(client.get_escrow(&1).released_amount, 500);
+    }
+
+    #[test]
+    fn test_backer_vote_rejected_below_threshold() {
+        let setup = setup_backer_escrow(5000, 6000);
+        let env = &setup.env;
+        let client = &setup.client;
+
+        let backer1 = Address::generate(env);
+        let backer2 = Address::generate(env);
+        setup.launch.set_contribution(&backer1, &500);
+        setup.launch.set_contribution(&backer2, &500);
+
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(env, &[1u8; 32]),
+            &500,
+            &Vec::new(env),
+        );
+        client.submit_milestone(&1, &0, &BytesN::from_array(env, &[9u8; 32]));
+
+        client.vote_milestone(&1, &0, &backer1, &true);
+        client.vote_milestone(&1, &0, &backer2, &false);
+
+        env.ledger().set_timestamp(2001);
+        assert_eq!(client.finalize_milestone(&1, &0), MilestoneStatus::Rejected);
+        assert_eq!(setup.token.balance(&setup.creator), 0);
+    }
+
+    #[test]
+    fn test_backer_vote_rejected_without_quorum() {
+        let setup = setup_backer_escrow(5000, 6000);
+        let env = &setup.env;
+        let client = &setup.client;
+
+        let backer1 = Address::generate(env);
+        let backer2 = Address::generate(env);
+        setup.launch.set_contribution(&backer1, &400);
+        setup.launch.set_contribution(&backer2, &600);
+
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(env, &[1u8; 32]),
+            &500,
+            &Vec::new(env),
+        );
+        client.submit_milestone(&1, &0, &BytesN::from_array(env, &[9u8; 32]));
+
+        // Unanimous, but only 40% of the raised amount took part
+        client.vote_milestone(&1, &0, &backer1, &true);
+
+        env.ledger().set_timestamp(2001);
+        assert_eq!(client.finalize_milestone(&1, &0), MilestoneStatus::Rejected);
+    }
+
+    #[test]
+    fn test_backer_vote_restrictions() {
+        let setup = setup_backer_escrow(5000, 6000);
+        let env = &setup.env;
+        let client = &setup.client;
+
+        let backer = Address::generate(env);
+        let outsider = Address::generate(env);
+        setup.launch.set_contribution(&backer, &1000);
+
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(env, &[1u8; 32]),
+            &500,
+            &Vec::new(env),
+        );
+
+        // Voting requires a submitted milestone
+        let result = client.try_vote_milestone(&1, &0, &backer, &true);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+
+        client.submit_milestone(&1, &0, &BytesN::from_array(env, &[9u8; 32]));
+
+        // Only contributors may vote
+        let result = client.try_vote_milestone(&1, &0, &outsider, &true);
+        assert_eq!(result, Err(Ok(Error::NotABacker)));
+
+        // One vote per backer
+        client.vote_milestone(&1, &0, &backer, &true);
+        let result = client.try_vote_milestone(&1, &0, &backer, &false);
+        assert_eq!(result, Err(Ok(Error::AlreadyVoted)));
+
+        // No votes after the window closes
+        let late_backer = Address::generate(env);
+        setup.launch.set_contribution(&late_backer, &10);
+        env.ledger().set_timestamp(2001);
+        let result = client.try_vote_milestone(&1, &0, &late_backer, &true);
+        assert_eq!(result, Err(Ok(Error::VotingPeriodEnded)));
+    }
+
+    #[test]
+    fn test_backer_escrow_rejects_validator_operations() {
+        let setup = setup_backer_escrow(5000, 6000);
+        let env = &setup.env;
+
+        let mut validators = Vec::new(env);
+        validators.push_back(Address::generate(env));
+        validators.push_back(Address::generate(env));
+        validators.push_back(Address::generate(env));
+
+        let result = setup.client.try_update_validators(&1, &validators);
+        assert_eq!(result, Err(Ok(Error::InvalidVotingMode)));
+    }
+
+    #[test]
+    fn test_finalize_requires_backer_voting() {
+        let (env, creator, token, _, validators) = create_test_env();
+        let client = create_client(&env);
+        env.mock_all_auths();
+
+        client.initialize(&1, &creator, &token, &validators);
+        client.deposit(&1, &1000);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &500,
+            &Vec::new(&env),
+        );
+        client.submit_milestone(&1, &0, &BytesN::from_array(&env, &[9u8; 32]));
+
+        let result = client.try_finalize_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidVotingMode)));
+    }
+
+    #[test]
+    fn test_registry_requires_bonded_validators() {
+        let setup = setup_staking();
+        let client = &setup.client;
+
+        let mut validators = setup.validators.clone();
+        validators.set(2, Address::generate(&setup.env));
+        let result = client.try_initialize(&1, &setup.creator, &setup.token.address, &validators);
+        assert_eq!(result, Err(Ok(Error::InsufficientBond)));
+
+        client.initialize(&1, &setup.creator, &setup.token.address, &setup.validators);
+        let result = client.try_update_validators(&1, &validators);
+        assert_eq!(result, Err(Ok(Error::InsufficientBond)));
+
+        let bond = client.get_validator_bond(&setup.validators.get(0).unwrap());
+        assert_eq!(bond.bonded, 500);
+        assert_eq!(setup.stake_token.balance(&client.address), 1500);
+    }
+
+    #[test]
+    fn test_validator_earns_fee_share() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        approve_staked_milestone(&setup);
+
+        let validator = setup.validators.get(0).unwrap();
+        assert_eq!(setup.token.balance(&setup.creator), 360);
+        assert_eq!(
+            client.get_validator_fees(&validator, &setup.token.address),
+            40
+        );
+        assert_eq!(client.get_escrow(&1).released_amount, 400);
+
+        assert_eq!(
+            client.claim_validator_fees(&validator, &setup.token.address),
+            40
+        );
+        assert_eq!(setup.token.balance(&validator), 40);
+
+        let result = client.try_claim_validator_fees(&validator, &setup.token.address);
+        assert_eq!(result, Err(Ok(Error::NoClaimableAmount)));
+    }
+
+    #[test]
+    fn test_unbond_and_withdraw() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        let validator = setup.validators.get(0).unwrap();
+        client.initialize(&1, &setup.creator, &setup.token.address, &setup.validators);
+        client.deposit(&1, &1000);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&setup.env, &[1u8; 32]),
+            &400,
+            &Vec::new(&setup.env),
+        );
+        client.submit_milestone(&1, &0, &BytesN::from_array(&setup.env, &[9u8; 32]));
+
+        client.unbond_validator(&validator, &450);
+        let bond = client.get_validator_bond(&validator);
+        assert_eq!(bond.bonded, 50);
+        assert_eq!(bond.unbonding, 450);
+        assert_eq!(bond.unbonding_ends_at, 1500);
+
+        let result = client.try_withdraw_unbonded(&validator);
+        assert_eq!(result, Err(Ok(Error::UnbondingPeriodActive)));
+
+        // Below the minimum bond the validator can no longer vote
+        let result = client.try_vote_milestone(&1, &0, &validator, &true);
+        assert_eq!(result, Err(Ok(Error::InsufficientBond)));
+
+        setup.env.ledger().set_timestamp(1500);
+        assert_eq!(client.withdraw_unbonded(&validator), 450);
+        assert_eq!(setup.stake_token.balance(&validator), 450);
+
+        let result = client.try_withdraw_unbonded(&validator);
+        assert_eq!(result, Err(Ok(Error::NoClaimableAmount)));
+    }
+
+    #[test]
+    fn test_upheld_dispute_slashes_approvers() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        let disputes = setup_disputes(&setup);
+        approve_staked_milestone(&setup);
+
+        // Funds are held during the dispute window
+        assert_eq!(
+            client.get_milestone(&1, &0).status,
+            MilestoneStatus::Approved
+        );
+        assert_eq!(setup.token.balance(&setup.creator), 0);
+
+        let approver = setup.validators.get(0).unwrap();
+        client.unbond_validator(&approver, &400);
+
+        let evidence = BytesN::from_array(&setup.env, &[7u8; 32]);
+        client.open_dispute(&1, &0, &disputes.backer, &evidence);
+        assert_eq!(
+            client.get_milestone(&1, &0).status,
+            MilestoneStatus::Disputed
+        );
+        assert_eq!(disputes.bond_token.balance(&disputes.backer), 50);
+
+        let result = client.try_open_dispute(&1, &0, &setup.creator, &evidence);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+
+        client.vote_dispute(&1, &0, &disputes.arbitrators.get(0).unwrap(), &true);
+        assert_eq!(client.get_dispute(&1, &0).status, DisputeStatus::Open);
+        client.vote_dispute(&1, &0, &disputes.arbitrators.get(1).unwrap(), &true);
+
+        let dispute = client.get_dispute(&1, &0);
+        assert_eq!(dispute.status, DisputeStatus::Upheld);
+        assert_eq!(dispute.uphold_votes, 2);
+        assert_eq!(
+            client.get_milestone(&1, &0).status,
+            MilestoneStatus::Rejected
+        );
+        assert_eq!(disputes.bond_token.balance(&disputes.backer), 100);
+
+        // Held funds go back to the escrow
+        assert_eq!(client.get_total_milestone_amount(&1), 0);
+        assert_eq!(client.get_available_balance(&1), 1000);
+        let result = client.try_release_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+
+        // Half of the 500 stake is slashed, bonded stake first
+        let bond = client.get_validator_bond(&approver);
+        assert_eq!(bond.bonded, 0);
+        assert_eq!(bond.unbonding, 250);
+        assert_eq!(setup.stake_token.balance(&setup.treasury), 250);
+
+        let untouched = client.get_validator_bond(&setup.validators.get(1).unwrap());
+        assert_eq!(untouched.bonded, 500);
+    }
+
+    #[test]
+    fn test_upheld_backer_vote_slashes_no_validator() {
+        let setup = setup_staking();
+        let env = &setup.env;
+        let client = &setup.client;
+        let disputes = setup_disputes(&setup);
+
+        // A bonded validator who also backed the project votes as a backer
+        let validator = setup.validators.get(0).unwrap();
+        let launch = MockProjectLaunchClient::new(env, &client.get_project_launch());
+        launch.set_project(&setup.creator, &ProjectStatus::Completed);
+        launch.set_contribution(&validator, &500);
+
+        let config = BackerVotingConfig {
+            quorum: 5000,
+            threshold: 6000,
+            voting_period: 1000,
+        };
+        client.initialize_backer_voting(&1, &setup.creator, &setup.token.address, &config);
+        client.deposit(&1, &1000);
+        token::StellarAssetClient::new(env, &setup.token.address).mint(&client.address, &1000);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(env, &[1u8; 32]),
+            &400,
+            &Vec::new(env),
+        );
+        client.submit_milestone(&1, &0, &BytesN::from_array(env, &[9u8; 32]));
+        client.vote_milestone(&1, &0, &validator, &true);
+        env.ledger().set_timestamp(2001);
+        assert_eq!(client.finalize_milestone(&1, &0), MilestoneStatus::Approved);
+
+        client.open_dispute(
+            &1,
+            &0,
+            &disputes.backer,
+            &BytesN::from_array(env, &[7u8; 32]),
+        );
+        client.resolve_dispute(&1, &0, &true);
+        assert_eq!(
+            client.get_milestone(&1, &0).status,
+            MilestoneStatus::Rejected
+        );
+
+        assert_eq!(client.get_validator_bond(&validator).bonded, 500);
+        assert_eq!(setup.stake_token.balance(&setup.treasury), 0);
+    }
+
+    #[test]
+    fn test_stake_held_while_approval_disputable() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        let disputes = setup_disputes(&setup);
+        let approver = setup.validators.get(0).unwrap();
+
+        client.unbond_validator(&approver, &300);
+        setup.env.ledger().set_timestamp(1450);
+        approve_staked_milestone(&setup);
+        assert_eq!(client.get_validator_bond(&approver).slashable_until, 1550);
+
+        // Unbonding has ended but the approval can still be disputed
+        setup.env.ledger().set_timestamp(1500);
+        let result = client.try_withdraw_unbonded(&approver);
+        assert_eq!(result, Err(Ok(Error::UnbondingPeriodActive)));
+
+        setup.env.ledger().set_timestamp(1540);
+        client.open_dispute(
+            &1,
+            &0,
+            &disputes.backer,
+            &BytesN::from_array(&setup.env, &[7u8; 32]),
+        );
+        assert_eq!(client.get_validator_bond(&approver).open_disputes, 1);
+
+        // The window has closed but the dispute is still open
+        setup.env.ledger().set_timestamp(1600);
+        let result = client.try_withdraw_unbonded(&approver);
+        assert_eq!(result, Err(Ok(Error::UnbondingPeriodActive)));
+
+        client.resolve_dispute(&1, &0, &false);
+        assert_eq!(client.get_validator_bond(&approver).open_disputes, 0);
+        assert_eq!(client.withdraw_unbonded(&approver), 300);
+        assert_eq!(setup.stake_token.balance(&approver), 300);
+    }
+
+    #[test]
+    fn test_upheld_dispute_forfeits_unclaimed_fees() {
+        let setup = setup_staking();
+        let client = &setup.client;
+
+        client.initialize(&1, &setup.creator, &setup.token.address, &setup.validators);
+        client.deposit(&1, &1000);
+        token::StellarAssetClient::new(&setup.env, &setup.token.address)
+            .mint(&client.address, &1000);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&setup.env, &[1u8; 32]),
+            &400,
+            &Vec::new(&setup.env),
+        );
+        let mut tranches = Vec::new(&setup.env);
+        tranches.push_back(Tranche {
+            share: 5000,
+            delay: 0,
+        });
+        tranches.push_back(Tranche {
+            share: 5000,
+            delay: 1000,
+        });
+        client.set_milestone_tranches(&1, &0, &tranches);
+        client.submit_milestone(&1, &0, &BytesN::from_array(&setup.env, &[9u8; 32]));
+
+        // No disputes yet, so the first tranche and its fee are paid out
+        let approver = setup.validators.get(0).unwrap();
+        client.vote_milestone(&1, &0, &approver, &true);
+        assert_eq!(setup.token.balance(&setup.creator), 180);
+        assert_eq!(
+            client.get_validator_fees(&approver, &setup.token.address),
+            20
+        );
+
+        let disputes = setup_disputes(&setup);
+        client.open_dispute(
+            &1,
+            &0,
+            &disputes.backer,
+            &BytesN::from_array(&setup.env, &[7u8; 32]),
+        );
+        client.resolve_dispute(&1, &0, &true);
+
+        assert_eq!(
+            client.get_validator_fees(&approver, &setup.token.address),
+            0
+        );
+        assert_eq!(setup.token.balance(&setup.treasury), 20);
+        let result = client.try_claim_validator_fees(&approver, &setup.token.address);
+        assert_eq!(result, Err(Ok(Error::NoClaimableAmount)));
+    }
+
+    #[test]
+    fn test_dismissed_dispute_forfeits_bond() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        let disputes = setup_disputes(&setup);
+        approve_staked_milestone(&setup);
+
+        let evidence = BytesN::from_array(&setup.env, &[7u8; 32]);
+        client.open_dispute(&1, &0, &disputes.backer, &evidence);
+        client.vote_dispute(&1, &0, &disputes.arbitrators.get(0).unwrap(), &false);
+        client.vote_dispute(&1, &0, &disputes.arbitrators.get(1).unwrap(), &true);
+        client.vote_dispute(&1, &0, &disputes.arbitrators.get(2).unwrap(), &false);
+
+        assert_eq!(client.get_dispute(&1, &0).status, DisputeStatus::Dismissed);
+        assert_eq!(
+            client.get_milestone(&1, &0).status,
+            MilestoneStatus::Approved
+        );
+        assert_eq!(
+            client
+                .get_validator_bond(&setup.validators.get(0).unwrap())
+                .bonded,
+            500
+        );
+
+        // The bond is split between the arbitrators who dismissed
+        assert_eq!(
+            disputes
+                .bond_token
+                .balance(&disputes.arbitrators.get(0).unwrap()),
+            25
+        );
+        assert_eq!(
+            disputes
+                .bond_token
+                .balance(&disputes.arbitrators.get(1).unwrap()),
+            0
+        );
+        assert_eq!(
+            disputes
+                .bond_token
+                .balance(&disputes.arbitrators.get(2).unwrap()),
+            25
+        );
+        assert_eq!(disputes.bond_token.balance(&disputes.dispute_treasury), 0);
+
+        let result = client.try_resolve_dispute(&1, &0, &true);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+
+        // Funds are released once the dispute window has passed
+        let result = client.try_release_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::HoldbackActive)));
+        setup.env.ledger().set_timestamp(1101);
+        client.release_milestone(&1, &0);
+        assert_eq!(setup.token.balance(&setup.creator), 360);
+        assert_eq!(client.get_milestone(&1, &0).released_amount, 400);
+
+        let result = client.try_release_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+    }
+
+    #[test]
+    fn test_creator_disputes_rejection() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        let disputes = setup_disputes(&setup);
+        submit_staked_milestone(&setup);
+        for validator in setup.validators.iter() {
+            client.vote_milestone(&1, &0, &validator, &false);
+        }
+        assert_eq!(
+            client.get_milestone(&1, &0).status,
+            MilestoneStatus::Rejected
+        );
+
+        client.open_dispute(
+            &1,
+            &0,
+            &setup.creator,
+            &BytesN::from_array(&setup.env, &[7u8; 32]),
+        );
+        assert_eq!(client.get_total_milestone_amount(&1), 400);
+
+        // Admin override reverses the rejection and pays out immediately
+        client.resolve_dispute(&1, &0, &true);
+        let milestone = client.get_milestone(&1, &0);
+        assert_eq!(milestone.status, MilestoneStatus::Approved);
+        assert_eq!(milestone.released_amount, 400);
+        assert_eq!(setup.token.balance(&setup.creator), 400);
+        assert_eq!(disputes.bond_token.balance(&setup.creator), 100);
+
+        // Rejecting validators are not slashed
+        for validator in setup.validators.iter() {
+            assert_eq!(client.get_validator_bond(&validator).bonded, 500);
+        }
+    }
+
+    #[test]
+    fn test_disputed_rejection_reserves_allocation() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        setup_disputes(&setup);
+        submit_staked_milestone(&setup);
+        for validator in setup.validators.iter() {
+            client.vote_milestone(&1, &0, &validator, &false);
+        }
+        let evidence = BytesN::from_array(&setup.env, &[7u8; 32]);
+        let description = BytesN::from_array(&setup.env, &[2u8; 32]);
+
+        // The rejected allocation was reused, so the rejection cannot be contested
+        client.create_milestone(&1, &description, &700, &Vec::new(&setup.env));
+        let result = client.try_open_dispute(&1, &0, &setup.creator, &evidence);
+        assert_eq!(result, Err(Ok(Error::InsufficientEscrowBalance)));
+        assert_eq!(
+            client.get_milestone(&1, &0).status,
+            MilestoneStatus::Rejected
+        );
+
+        // An open dispute keeps the full amount reserved
+        client.create_milestone(&1, &description, &200, &Vec::new(&setup.env));
+        let result = client.try_create_milestone(&1, &description, &200, &Vec::new(&setup.env));
+        assert_eq!(result, Err(Ok(Error::InsufficientEscrowBalance)));
+        client.submit_milestone(&1, &2, &BytesN::from_array(&setup.env, &[9u8; 32]));
+        for validator in setup.validators.iter() {
+            client.vote_milestone(&1, &2, &validator, &false);
+        }
+        client.open_dispute(&1, &2, &setup.creator, &evidence);
+        assert_eq!(client.get_total_milestone_amount(&1), 900);
+        let result = client.try_create_milestone(&1, &description, &200, &Vec::new(&setup.env));
+        assert_eq!(result, Err(Ok(Error::InsufficientEscrowBalance)));
+
+        // Dismissal releases the reservation
+        client.resolve_dispute(&1, &2, &false);
+        let milestone = client.get_milestone(&1, &2);
+        assert_eq!(milestone.status, MilestoneStatus::Rejected);
+        assert_eq!(milestone.approved_amount, 0);
+        assert_eq!(client.get_total_milestone_amount(&1), 700);
+    }
+
+    #[test]
+    fn test_dispute_restrictions() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        approve_staked_milestone(&setup);
+        let evidence = BytesN::from_array(&setup.env, &[7u8; 32]);
+
+        // Disputes need to be configured; approvals without them pay out directly
+        let result = client.try_open_dispute(&1, &0, &setup.creator, &evidence);
+        assert_eq!(result, Err(Ok(Error::NotInitialized)));
+        assert_eq!(client.get_milestone(&1, &0).released_amount, 400);
+
+        let disputes = setup_disputes(&setup);
+        let result = client.try_open_dispute(&1, &0, &disputes.backer, &evidence);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&setup.env, &[2u8; 32]),
+            &200,
+            &Vec::new(&setup.env),
+        );
+        client.submit_milestone(&1, &1, &BytesN::from_array(&setup.env, &[9u8; 32]));
+        client.vote_milestone(&1, &1, &setup.validators.get(0).unwrap(), &true);
+
+        let outsider = Address::generate(&setup.env);
+        let result = client.try_open_dispute(&1, &1, &outsider, &evidence);
+        assert_eq!(result, Err(Ok(Error::NotABacker)));
+
+        client.open_dispute(&1, &1, &disputes.backer, &evidence);
+        let result = client.try_vote_dispute(&1, &1, &outsider, &true);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        let arbitrator = disputes.arbitrators.get(0).unwrap();
+        client.vote_dispute(&1, &1, &arbitrator, &true);
+        let result = client.try_vote_dispute(&1, &1, &arbitrator, &true);
+        assert_eq!(result, Err(Ok(Error::AlreadyVoted)));
+
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&setup.env, &[3u8; 32]),
+            &100,
+            &Vec::new(&setup.env),
+        );
+        client.submit_milestone(&1, &2, &BytesN::from_array(&setup.env, &[9u8; 32]));
+        client.vote_milestone(&1, &2, &setup.validators.get(0).unwrap(), &true);
+        setup.env.ledger().set_timestamp(1101);
+        let result = client.try_open_dispute(&1, &2, &disputes.backer, &evidence);
+        assert_eq!(result, Err(Ok(Error::VotingPeriodEnded)));
+    }
+
+    #[test]
+    fn test_tranche_release() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, token, creator) = setup_funded_escrow(&validators);
+
+        let mut tranches = Vec::new(&env);
+        tranches.push_back(Tranche {
+            share: 3000,
+            delay: 0,
+        });
+        tranches.push_back(Tranche {
+            share: 6000,
+            delay: 100,
+        });
+        let result = client.try_set_milestone_tranches(&1, &0, &tranches);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        tranches.set(
+            1,
+            Tranche {
+                share: 7000,
+                delay: 100,
+            },
+        );
+        client.set_milestone_tranches(&1, &0, &tranches);
+        assert_eq!(client.get_milestone_tranches(&1, &0).len(), 2);
+
+        client.submit_milestone(&1, &0, &BytesN::from_array(&env, &[9u8; 32]));
+        let result = client.try_set_milestone_tranches(&1, &0, &tranches);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+
+        // 30% is paid on approval, the rest after the holdback
+        client.vote_milestone(&1, &0, &validators.get(0).unwrap(), &true);
+        assert_eq!(token.balance(&creator), 120);
+        let milestone = client.get_milestone(&1, &0);
+        assert_eq!(milestone.approved_amount, 400);
+        assert_eq!(milestone.released_amount, 120);
+
+        let result = client.try_release_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::HoldbackActive)));
+
+        env.ledger().set_timestamp(1100);
+        assert_eq!(client.release_milestone(&1, &0), 280);
+        assert_eq!(token.balance(&creator), 400);
+        assert_eq!(client.get_escrow(&1).released_amount, 400);
+
+        let result = client.try_release_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+    }
+
+    #[test]
+    fn test_partial_approval_uses_smallest_share() {
+        let env = Env::default();
+        let mut validators = Vec::new(&env);
+        for _ in 0..5 {
+            validators.push_back(Address::generate(&env));
+        }
+        let (env, client, token, creator) = setup_funded_escrow(&validators);
+        client.submit_milestone(&1, &0, &BytesN::from_array(&env, &[9u8; 32]));
+
+        let result = client.try_vote_milestone_partial(&1, &0, &validators.get(0).unwrap(), &0);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        // Three of five approvals are needed
+        client.vote_milestone_partial(&1, &0, &validators.get(0).unwrap(), &8000);
+        client.vote_milestone(&1, &0, &validators.get(1).unwrap(), &false);
+        client.vote_milestone_partial(&1, &0, &validators.get(2).unwrap(), &5000);
+        assert_eq!(
+            client.get_milestone(&1, &0).status,
+            MilestoneStatus::Submitted
+        );
+        client.vote_milestone(&1, &0, &validators.get(3).unwrap(), &true);
+
+        let milestone = client.get_milestone(&1, &0);
+        assert_eq!(milestone.status, MilestoneStatus::Approved);
+        assert_eq!(milestone.approved_amount, 200);
+        assert_eq!(milestone.released_amount, 200);
+        assert_eq!(token.balance(&creator), 200);
+
+        // The unapproved remainder goes back to the escrow
+        assert_eq!(client.get_total_milestone_amount(&1), 200);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &800,
+            &Vec::new(&env),
+        );
+    }
+
+    #[test]
+    fn test_dispute_window_delays_early_tranches() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        setup_disputes(&setup);
+
+        client.initialize(&1, &setup.creator, &setup.token.address, &setup.validators);
+        client.deposit(&1, &1000);
+        token::StellarAssetClient::new(&setup.env, &setup.token.address)
+            .mint(&client.address, &1000);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&setup.env, &[1u8; 32]),
+            &400,
+            &Vec::new(&setup.env),
+        );
+        let mut tranches = Vec::new(&setup.env);
+        tranches.push_back(Tranche {
+            share: 3000,
+            delay: 0,
+        });
+        tranches.push_back(Tranche {
+            share: 7000,
+            delay: 200,
+        });
+        client.set_milestone_tranches(&1, &0, &tranches);
+        client.submit_milestone(&1, &0, &BytesN::from_array(&setup.env, &[9u8; 32]));
+        client.vote_milestone(&1, &0, &setup.validators.get(0).unwrap(), &true);
+
+        // The 100 second dispute window is the shortest delay
+        setup.env.ledger().set_timestamp(1099);
+        let result = client.try_release_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::HoldbackActive)));
+
+        setup.env.ledger().set_timestamp(1100);
+        assert_eq!(client.release_milestone(&1, &0), 120);
+        setup.env.ledger().set_timestamp(1200);
+        assert_eq!(client.release_milestone(&1, &0), 280);
+        assert_eq!(setup.token.balance(&setup.creator), 360);
+    }
+
+    #[test]
+    fn test_upheld_dispute_withholds_remaining_tranches() {
+        let setup = setup_staking();
+        let client = &setup.client;
+        let disputes = setup_disputes(&setup);
+
+        client.initialize(&1, &setup.creator, &setup.token.address, &setup.validators);
+        client.deposit(&1, &1000);
+        token::StellarAssetClient::new(&setup.env, &setup.token.address)
+            .mint(&client.address, &1000);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&setup.env, &[1u8; 32]),
+            &400,
+            &Vec::new(&setup.env),
+        );
+
+        let mut tranches = Vec::new(&setup.env);
+        tranches.push_back(Tranche {
+            share: 3000,
+            delay: 0,
+        });
+        tranches.push_back(Tranche {
+            share: 7000,
+            delay: 200,
+        });
+        client.set_milestone_tranches(&1, &0, &tranches);
+        client.submit_milestone(&1, &0, &BytesN::from_array(&setup.env, &[9u8; 32]));
+        client.vote_milestone(&1, &0, &setup.validators.get(0).unwrap(), &true);
+
+        // Even the first tranche is held for the dispute window
+        assert_eq!(setup.token.balance(&setup.creator), 0);
+        let result = client.try_release_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::HoldbackActive)));
+
+        client.open_dispute(
+            &1,
+            &0,
+            &disputes.backer,
+            &BytesN::from_array(&setup.env, &[7u8; 32]),
+        );
+        client.resolve_dispute(&1, &0, &true);
+
+        let milestone = client.get_milestone(&1, &0);
+        assert_eq!(milestone.status, MilestoneStatus::Rejected);
+        assert_eq!(milestone.approved_amount, 0);
+        assert_eq!(client.get_total_milestone_amount(&1), 0);
+
+        setup.env.ledger().set_timestamp(1200);
+        let result = client.try_release_milestone(&1, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+        assert_eq!(setup.token.balance(&setup.creator), 0);
+    }
+
+    #[test]
+    fn test_validator_change_applies_to_future_submissions() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, token, creator) = setup_funded_escrow(&validators);
+        client.initialize_admin(&Address::generate(&env));
+        client.submit_milestone(&1, &0, &BytesN::from_array(&env, &[9u8; 32]));
+
+        let mut new_validators = Vec::new(&env);
+        for _ in 0..3 {
+            new_validators.push_back(Address::generate(&env));
+        }
+        client.update_validators(&1, &new_validators);
+        assert_eq!(client.get_milestone_validators(&1, &0), validators);
+
+        // The in-flight submission is still voted on by the old set
+        let result = client.try_vote_milestone(&1, &0, &new_validators.get(0).unwrap(), &true);
+        assert_eq!(result, Err(Ok(Error::NotAValidator)));
+        client.vote_milestone(&1, &0, &validators.get(0).unwrap(), &true);
+        assert_eq!(token.balance(&creator), 400);
+
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &100,
+            &Vec::new(&env),
+        );
+        client.submit_milestone(&1, &1, &BytesN::from_array(&env, &[9u8; 32]));
+        let result = client.try_vote_milestone(&1, &1, &validators.get(0).unwrap(), &true);
+        assert_eq!(result, Err(Ok(Error::NotAValidator)));
+        client.vote_milestone(&1, &1, &new_validators.get(0).unwrap(), &true);
+        assert_eq!(token.balance(&creator), 500);
+    }
+
+    #[test]
+    fn test_proposed_validator_change() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, _, _) = setup_funded_escrow(&validators);
+        let admin = Address::generate(&env);
+        client.initialize_admin(&admin);
+
+        let mut new_validators = Vec::new(&env);
+        for _ in 0..2 {
+            new_validators.push_back(Address::generate(&env));
+        }
+        let result = client.try_propose_validator_change(&1, &new_validators);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        new_validators.push_back(Address::generate(&env));
+        client.propose_validator_change(&1, &new_validators);
+
+        let outsider = Address::generate(&env);
+        let result = client.try_approve_validator_change(&1, &outsider);
+        assert_eq!(result, Err(Ok(Error::NotAValidator)));
+
+        // A majority of the current validators applies the change
+        client.approve_validator_change(&1, &validators.get(0).unwrap());
+        let result = client.try_approve_validator_change(&1, &validators.get(0).unwrap());
+        assert_eq!(result, Err(Ok(Error::AlreadyVoted)));
+        assert_eq!(client.get_validator_change(&1).approvals.len(), 1);
+        assert_eq!(client.get_escrow(&1).validators, validators);
+
+        client.approve_validator_change(&1, &validators.get(1).unwrap());
+        assert_eq!(client.get_escrow(&1).validators, new_validators);
+        let result = client.try_get_validator_change(&1);
+        assert_eq!(result.err(), Some(Ok(Error::NotFound)));
+
+        // The admin can approve on its own
+        client.propose_validator_change(&1, &validators);
+        client.approve_validator_change(&1, &admin);
+        assert_eq!(client.get_escrow(&1).validators, validators);
+    }
+
+    #[test]
+    fn test_validator_change_ignores_removed_approvers() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, _, _) = setup_funded_escrow(&validators);
+        client.initialize_admin(&Address::generate(&env));
+
+        let mut new_validators = Vec::new(&env);
+        for _ in 0..3 {
+            new_validators.push_back(Address::generate(&env));
+        }
+        client.propose_validator_change(&1, &new_validators);
+        client.approve_validator_change(&1, &validators.get(0).unwrap());
+
+        // The first approver is removed before a second validator approves
+        let mut current = validators.clone();
+        current.set(0, Address::generate(&env));
+        client.update_validators(&1, &current);
+
+        client.approve_validator_change(&1, &validators.get(1).unwrap());
+        assert_eq!(client.get_validator_change(&1).approvals.len(), 1);
+        assert_eq!(client.get_escrow(&1).validators, current);
+
+        client.approve_validator_change(&1, &validators.get(2).unwrap());
+        assert_eq!(client.get_escrow(&1).validators, new_validators);
+    }
+
+    #[test]
+    fn test_milestone_dependencies() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, _, _) = setup_funded_escrow(&validators);
+        let description_hash = BytesN::from_array(&env, &[2u8; 32]);
+        let proof_hash = BytesN::from_array(&env, &[9u8; 32]);
+
+        let mut dependencies = Vec::new(&env);
+        dependencies.push_back(1);
+        let result = client.try_create_milestone(&1, &description_hash, &200, &dependencies);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        dependencies.set(0, 0);
+        dependencies.push_back(0);
+        let result = client.try_create_milestone(&1, &description_hash, &200, &dependencies);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        dependencies.pop_back();
+        client.create_milestone(&1, &description_hash, &200, &dependencies);
+        assert_eq!(client.get_milestone(&1, &1).dependencies, dependencies);
+
+        // Milestone 1 waits for milestone 0
+        let result = client.try_submit_milestone(&1, &1, &proof_hash);
+        assert_eq!(result, Err(Ok(Error::MilestoneNotApproved)));
+
+        client.submit_milestone(&1, &0, &proof_hash);
+        let result = client.try_submit_milestone(&1, &1, &proof_hash);
+        assert_eq!(result, Err(Ok(Error::MilestoneNotApproved)));
+
+        client.vote_milestone(&1, &0, &validators.get(0).unwrap(), &true);
+        client.submit_milestone(&1, &1, &proof_hash);
+        assert_eq!(
+            client.get_milestone(&1, &1).status,
+            MilestoneStatus::Submitted
+        );
+    }
+
+    #[test]
+    fn test_roadmap_progress() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, _, _) = setup_funded_escrow(&validators);
+
+        let mut dependencies = Vec::new(&env);
+        dependencies.push_back(0);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &200,
+            &dependencies,
+        );
+
+        let roadmap = client.get_roadmap(&1);
+        assert_eq!(roadmap.items.len(), 2);
+        assert_eq!(roadmap.total_amount, 600);
+        assert_eq!(roadmap.approved_count, 0);
+        assert!(roadmap.items.get(0).unwrap().unlocked);
+        assert!(!roadmap.items.get(1).unwrap().unlocked);
+
+        client.submit_milestone(&1, &0, &BytesN::from_array(&env, &[9u8; 32]));
+        client.vote_milestone(&1, &0, &validators.get(0).unwrap(), &true);
+
+        let roadmap = client.get_roadmap(&1);
+        assert_eq!(roadmap.approved_count, 1);
+        assert_eq!(roadmap.released_amount, 400);
+        let item = roadmap.items.get(1).unwrap();
+        assert_eq!(item.status, MilestoneStatus::Pending);
+        assert_eq!(item.dependencies, dependencies);
+        assert!(item.unlocked);
+    }
+
+    #[test]
+    fn test_list_milestones() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, _, _) = setup_funded_escrow(&validators);
+        for i in 1..5u8 {
+            client.create_milestone(
+                &1,
+                &BytesN::from_array(&env, &[i; 32]),
+                &100,
+                &Vec::new(&env),
+            );
+        }
+        for milestone_id in [1u64, 3] {
+            client.submit_milestone(&1, &milestone_id, &BytesN::from_array(&env, &[9u8; 32]));
+            client.vote_milestone(&1, &milestone_id, &validators.get(0).unwrap(), &true);
+        }
+
+        let page = client.list_milestones(&1, &0, &2, &None);
+        assert_eq!(page.milestones.len(), 2);
+        assert_eq!(page.next_cursor, Some(2));
+        let page = client.list_milestones(&1, &2, &2, &None);
+        assert_eq!(page.milestones.get(0).unwrap().id, 2);
+        assert_eq!(page.next_cursor, Some(4));
+        let page = client.list_milestones(&1, &4, &2, &None);
+        assert_eq!(page.milestones.len(), 1);
+        assert_eq!(page.next_cursor, None);
+
+        let page = client.list_milestones(&1, &0, &10, &Some(MilestoneStatus::Approved));
+        assert_eq!(page.milestones.len(), 2);
+        assert_eq!(page.milestones.get(1).unwrap().id, 3);
+
+        let result = client.try_list_milestones(&1, &0, &0, &None);
+        assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    }
+
+    #[test]
+    fn test_get_votes() {
+        let env = Env::default();
+        let mut validators = Vec::new(&env);
+        for _ in 0..5 {
+            validators.push_back(Address::generate(&env));
+        }
+        let (env, client, _, _) = setup_funded_escrow(&validators);
+        client.submit_milestone(&1, &0, &BytesN::from_array(&env, &[9u8; 32]));
+        assert_eq!(client.get_votes(&1, &0).len(), 0);
+
+        client.vote_milestone_partial(&1, &0, &validators.get(0).unwrap(), &6000);
+        client.vote_milestone(&1, &0, &validators.get(1).unwrap(), &false);
+
+        let votes = client.get_votes(&1, &0);
+        assert_eq!(votes.len(), 2);
+        let first = votes.get(0).unwrap();
+        assert_eq!(first.validator, validators.get(0).unwrap());
+        assert!(first.approve);
+        assert_eq!(first.share, 6000);
+        assert!(!votes.get(1).unwrap().approve);
+
+        let result = client.try_get_votes(&1, &5);
+        assert_eq!(result.err(), Some(Ok(Error::NotFound)));
+    }
+
+    #[test]
+    fn test_escrow_summary() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, _, _) = setup_funded_escrow(&validators);
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &250,
+            &Vec::new(&env),
+        );
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&env, &[3u8; 32]),
+            &150,
+            &Vec::new(&env),
+        );
+
+        client.submit_milestone(&1, &0, &BytesN::from_array(&env, &[9u8; 32]));
+        client.vote_milestone(&1, &0, &validators.get(0).unwrap(), &true);
+        client.submit_milestone(&1, &1, &BytesN::from_array(&env, &[9u8; 32]));
+
+        let summary = client.get_escrow_summary(&1);
+        assert_eq!(summary.total_deposited, 1000);
+        assert_eq!(summary.released_amount, 400);
+        assert_eq!(summary.allocated_amount, 800);
+        assert_eq!(summary.milestone_count, 3);
+        assert_eq!(summary.approved_count, 1);
+        assert_eq!(summary.approved_amount, 400);
+        assert_eq!(summary.submitted_count, 1);
+        assert_eq!(summary.submitted_amount, 250);
+        assert_eq!(summary.pending_count, 1);
+        assert_eq!(summary.pending_amount, 150);
+        assert_eq!(summary.rejected_count, 0);
+    }
+
+    /// Whitelist a mock vault for the escrow token and opt project 1 into it,
+    /// giving backers a quarter of the yield
+    fn setup_yield<'a>(
+        env: &Env,
+        client: &EscrowContractClient<'a>,
+        token: &token::Client<'a>,
+    ) -> (MockVaultClient<'a>, Address) {
+        let vault = MockVaultClient::new(env, &env.register_contract(None, MockVault));
+        vault.initialize(&token.address);
+        client.initialize_admin(&Address::generate(env));
+        client.set_vault_whitelisted(&vault.address, &true);
+
+        let backer_recipient = Address::generate(env);
+        client.configure_yield(
+            &1,
+            &YieldConfig {
+                vault: vault.address.clone(),
+                backer_share: 2500,
+                backer_recipient: backer_recipient.clone(),
+            },
+        );
+        (vault, backer_recipient)
+    }
+
+    #[test]
+    fn test_configure_yield_requires_whitelisted_vault() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, token, _) = setup_funded_escrow(&validators);
+
+        // The split is set by the admin, not the creator
+        let result = client.try_configure_yield(
+            &1,
+            &YieldConfig {
+                vault: Address::generate(&env),
+                backer_share: 0,
+                backer_recipient: Address::generate(&env),
+            },
+        );
+        assert_eq!(result, Err(Ok(Error::NotInitialized)));
+        let (vault, recipient) = setup_yield(&env, &client, &token);
+
+        let other = MockVaultClient::new(&env, &env.register_contract(None, MockVault));
+        other.initialize(&token.address);
+        let mut config = YieldConfig {
+            vault: other.address.clone(),
+            backer_share: 2500,
+            backer_recipient: recipient,
+        };
+        let result = client.try_configure_yield(&1, &config);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        // The vault cannot be swapped while funds sit in the current one
+        client.set_vault_whitelisted(&other.address, &true);
+        client.invest_idle(&1);
+        let result = client.try_configure_yield(&1, &config);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        config.vault = vault.address.clone();
+        config.backer_share = 10001;
+        let result = client.try_configure_yield(&1, &config);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+    }
+
+    #[test]
+    fn test_invest_idle_and_withdraw_on_release() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, token, creator) = setup_funded_escrow(&validators);
+        let (vault, _) = setup_yield(&env, &client, &token);
+
+        // Only the 600 not allocated to milestone 0 is deposited
+        assert_eq!(client.invest_idle(&1), 600);
+        assert_eq!(token.balance(&vault.address), 600);
+        assert_eq!(token.balance(&client.address), 400);
+        let result = client.try_invest_idle(&1);
+        assert_eq!(result, Err(Ok(Error::InsufficientEscrowBalance)));
+
+        // A new milestone draws on the deposit; paying it withdraws the shortfall
+        client.create_milestone(
+            &1,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &500,
+            &Vec::new(&env),
+        );
+        client.submit_milestone(&1, &1, &BytesN::from_array(&env, &[9u8; 32]));
+        client.vote_milestone(&1, &1, &validators.get(0).unwrap(), &true);
+        assert_eq!(token.balance(&creator), 500);
+        assert_eq!(client.get_yield_position(&1).principal, 500);
+        assert_eq!(token.balance(&vault.address), 500);
+
+        client.submit_milestone(&1, &0, &BytesN::from_array(&env, &[9u8; 32]));
+        client.vote_milestone(&1, &0, &validators.get(0).unwrap(), &true);
+        assert_eq!(token.balance(&creator), 900);
+        assert_eq!(client.get_yield_position(&1).principal, 100);
+        assert_eq!(token.balance(&vault.address), 100);
+        assert_eq!(client.get_available_balance(&1), 100);
+    }
+
+    #[test]
+    fn test_harvest_yield_splits_between_backers_and_creator() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, token, creator) = setup_funded_escrow(&validators);
+        let (vault, backer_recipient) = setup_yield(&env, &client, &token);
+
+        client.invest_idle(&1);
+        let result = client.try_harvest_yield(&1);
+        assert_eq!(result, Err(Ok(Error::NoClaimableAmount)));
+
+        // The vault earns 10%
+        token::StellarAssetClient::new(&env, &token.address).mint(&vault.address, &60);
+        assert_eq!(client.harvest_yield(&1), 60);
+        assert_eq!(token.balance(&backer_recipient), 15);
+        assert_eq!(token.balance(&creator), 45);
+
+        let position = client.get_yield_position(&1);
+        assert_eq!(position.principal, 600);
+        assert_eq!(position.harvested, 60);
+        assert_eq!(vault.preview_redeem(&position.shares), 600);
+        assert_eq!(client.get_escrow(&1).released_amount, 0);
+    }
+
+    #[test]
+    fn test_divest_returns_principal_and_yield() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, token, creator) = setup_funded_escrow(&validators);
+        let (vault, backer_recipient) = setup_yield(&env, &client, &token);
+
+        client.invest_idle(&1);
+        token::StellarAssetClient::new(&env, &token.address).mint(&vault.address, &40);
+
+        assert_eq!(client.divest(&1), 600);
+        assert_eq!(token.balance(&client.address), 1000);
+        assert_eq!(token.balance(&backer_recipient), 10);
+        assert_eq!(token.balance(&creator), 30);
+        assert_eq!(
+            client.try_get_yield_position(&1).err(),
+            Some(Ok(Error::NotFound))
+        );
+    }
+
+    /// Register a second token and deposit 500 of it into project 1
+    fn setup_second_token<'a>(env: &Env, client: &EscrowContractClient<'a>) -> token::Client<'a> {
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        token::StellarAssetClient::new(env, &token_id).mint(&client.address, &500);
+        client.deposit_token(&1, &token_id, &500);
+        token::Client::new(env, &token_id)
+    }
+
+    fn token_amounts(env: &Env, amounts: &[(&Address, i128)]) -> Vec<TokenAmount> {
+        let mut result = Vec::new(env);
+        for (token, amount) in amounts {
+            result.push_back(TokenAmount {
+                token: (*token).clone(),
+                amount: *amount,
+            });
+        }
+        result
+    }
+
+    #[test]
+    fn test_basket_milestone_releases_each_token() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, token, creator) = setup_funded_escrow(&validators);
+        let second = setup_second_token(&env, &client);
+        assert_eq!(client.get_escrow(&1).tokens.len(), 1);
+
+        let amounts = token_amounts(&env, &[(&token.address, 200), (&second.address, 300)]);
+        client.create_basket_milestone(
+            &1,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &amounts,
+            &Vec::new(&env),
+        );
+        let milestone = client.get_milestone(&1, &1);
+        assert_eq!(milestone.amount, 200);
+        assert_eq!(milestone.basket.len(), 1);
+        assert_eq!(client.get_total_milestone_amount(&1), 600);
+
+        client.submit_milestone(&1, &1, &BytesN::from_array(&env, &[9u8; 32]));
+        client.vote_milestone(&1, &1, &validators.get(0).unwrap(), &true);
+        assert_eq!(token.balance(&creator), 200);
+        assert_eq!(second.balance(&creator), 300);
+
+        let balances = client.get_token_balances(&1);
+        assert_eq!(balances.get(0).unwrap().released_amount, 200);
+        let second_balance = balances.get(1).unwrap();
+        assert_eq!(second_balance.token, second.address);
+        assert_eq!(second_balance.total_deposited, 500);
+        assert_eq!(second_balance.released_amount, 300);
+
+        let result = client.try_release_milestone(&1, &1);
+        assert_eq!(result, Err(Ok(Error::InvalidMilestoneStatus)));
+    }
+
+    #[test]
+    fn test_deposit_token_basket_limit() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, _, _) = setup_funded_escrow(&validators);
+        let second = setup_second_token(&env, &client);
+
+        for _ in 1..10 {
+            client.deposit_token(&1, &Address::generate(&env), &1);
+        }
+        assert_eq!(client.get_escrow(&1).tokens.len(), 10);
+
+        let result = client.try_deposit_token(&1, &Address::generate(&env), &1);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        // Tokens already in the basket can still be topped up
+        client.deposit_token(&1, &second.address, &100);
+        assert_eq!(
+            client
+                .get_token_balances(&1)
+                .get(1)
+                .unwrap()
+                .total_deposited,
+            600
+        );
+    }
+
+    #[test]
+    fn test_basket_milestone_validation() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, token, _) = setup_funded_escrow(&validators);
+        let second = setup_second_token(&env, &client);
+        let hash = BytesN::from_array(&env, &[2u8; 32]);
+
+        let unknown = Address::generate(&env);
+        let result = client.try_create_basket_milestone(
+            &1,
+            &hash,
+            &token_amounts(&env, &[(&unknown, 100)]),
+            &Vec::new(&env),
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        let result = client.try_create_basket_milestone(
+            &1,
+            &hash,
+            &token_amounts(&env, &[(&second.address, 100), (&second.address, 100)]),
+            &Vec::new(&env),
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+        let result = client.try_create_basket_milestone(
+            &1,
+            &hash,
+            &token_amounts(&env, &[(&second.address, 501)]),
+            &Vec::new(&env),
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientEscrowBalance)));
+
+        // Primary token allocations are still capped by primary deposits
+        let result = client.try_create_basket_milestone(
+            &1,
+            &hash,
+            &token_amounts(&env, &[(&token.address, 601), (&second.address, 100)]),
+            &Vec::new(&env),
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientEscrowBalance)));
+    }
+
+    #[test]
+    fn test_partial_approval_scales_basket() {
+        let (_, _, _, _, validators) = create_test_env();
+        let (env, client, token, creator) = setup_funded_escrow(&validators);
+        let second = setup_second_token(&env, &client);
+
+        // A milestone paid only in the second token
+        client.create_basket_milestone(
+            &1,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &token_amounts(&env, &[(&second.address, 400)]),
+            &Vec::new(&env),
+        );
+        client.submit_milestone(&1, &1, &BytesN::from_array(&env, &[9u8; 32]));
+        client.vote_milestone_partial(&1, &1, &validators.get(0).unwrap(), &5000);
+
+        let milestone = client.get_milestone(&1, &1);
+        assert_eq!(milestone.status, MilestoneStatus::Approved);
+        assert_eq!(milestone.basket.get(0).unwrap().approved_amount, 200);
+        assert_eq!(second.balance(&creator), 200);
+        assert_eq!(token.balance(&creator), 0);
+
+        // The unapproved half can fund another milestone
+        client.create_basket_milestone(
+            &1,
+            &BytesN::from_array(&env, &[3u8; 32]),
+            &token_amounts(&env, &[(&second.address, 300)]),
+            &Vec::new(&env),
+        );
+    }
 }
@@ -1,12 +1,118 @@
 use shared::errors::Error;
-use shared::types::EscrowInfo;
-use soroban_sdk::Address;
+use shared::types::{
+    Amount, BackerVotingConfig, DisputeConfig, Milestone, Tranche, ValidatorBond,
+    ValidatorRegistryConfig,
+};
+use shared::utils::validate_basis_points;
+use soroban_sdk::{Address, Vec};
 
-/// Validate that an address is a validator in the escrow
-pub fn validate_validator(escrow: &EscrowInfo, validator: &Address) -> Result<(), Error> {
-    if escrow.validators.iter().any(|v| v == *validator) {
+/// Validate that an address is in a validator set
+pub fn validate_validator(validators: &Vec<Address>, validator: &Address) -> Result<(), Error> {
+    if validators.iter().any(|v| v == *validator) {
         Ok(())
     } else {
         Err(Error::NotAValidator)
     }
 }
+
+/// Validate validator registry parameters
+pub fn validate_validator_registry_config(config: &ValidatorRegistryConfig) -> Result<(), Error> {
+    if config.min_bond <= 0
+        || !validate_basis_points(config.fee_share)
+        || !validate_basis_points(config.slash_rate)
+    {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Validate that a validator's bond meets the registry minimum
+pub fn validate_bond(config: &ValidatorRegistryConfig, bond: &ValidatorBond) -> Result<(), Error> {
+    if bond.bonded < config.min_bond {
+        return Err(Error::InsufficientBond);
+    }
+    Ok(())
+}
+
+/// Validate dispute parameters
+pub fn validate_dispute_config(config: &DisputeConfig) -> Result<(), Error> {
+    if config.bond_amount <= 0 || config.window == 0 || config.arbitrators.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Validate that milestone dependencies refer to distinct, earlier milestones
+pub fn validate_dependencies(dependencies: &Vec<u64>, milestone_id: u64) -> Result<(), Error> {
+    for (index, dependency) in dependencies.iter().enumerate() {
+        if dependency >= milestone_id {
+            return Err(Error::InvalidInput);
+        }
+        if dependencies
+            .iter()
+            .skip(index + 1)
+            .any(|other| other == dependency)
+        {
+            return Err(Error::InvalidInput);
+        }
+    }
+    Ok(())
+}
+
+/// Validate a milestone release schedule: positive shares adding up to 10000
+pub fn validate_tranches(tranches: &Vec<Tranche>) -> Result<(), Error> {
+    let mut total: u32 = 0;
+    for tranche in tranches.iter() {
+        if tranche.share == 0 {
+            return Err(Error::InvalidInput);
+        }
+        total = total
+            .checked_add(tranche.share)
+            .ok_or(Error::InvalidInput)?;
+    }
+    if total != 10000 {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Validate backer voting parameters
+pub fn validate_backer_voting_config(config: &BackerVotingConfig) -> Result<(), Error> {
+    if config.threshold == 0
+        || !validate_basis_points(config.threshold)
+        || !validate_basis_points(config.quorum)
+        || config.voting_period == 0
+    {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Check whether a closed backer vote reached quorum and the approval threshold
+pub fn backer_vote_passes(
+    config: &BackerVotingConfig,
+    milestone: &Milestone,
+    total_raised: Amount,
+) -> Result<bool, Error> {
+    let cast = milestone
+        .approval_weight
+        .checked_add(milestone.rejection_weight)
+        .ok_or(Error::InvalidInput)?;
+    if cast == 0 {
+        return Ok(false);
+    }
+
+    let quorum_met = cast.checked_mul(10000).ok_or(Error::InvalidInput)?
+        >= total_raised
+            .checked_mul(config.quorum as i128)
+            .ok_or(Error::InvalidInput)?;
+    let threshold_met = milestone
+        .approval_weight
+        .checked_mul(10000)
+        .ok_or(Error::InvalidInput)?
+        >= cast
+            .checked_mul(config.threshold as i128)
+            .ok_or(Error::InvalidInput)?;
+
+    Ok(quorum_met && threshold_met)
+}
@@ -3,7 +3,7 @@
 use shared::{
     constants::GOVERNANCE_QUORUM,
     errors::Error,
-    events::{PROPOSAL_CREATED, PROPOSAL_EXECUTED, VOTE_CAST},
+    events::{publish, ProposalCreated, ProposalExecuted, VoteCast},
     types::Proposal,
 };
 use soroban_sdk::{contract, contractimpl, contracttype, Address, Bytes, Env};
@@ -61,7 +61,7 @@ impl GovernanceContract {
         if start_time < current_time {
             return Err(Error::InvalidInput);
         }
-        if payload_ref.len() == 0 {
+        if payload_ref.is_empty() {
             return Err(Error::InvalidInput);
         }
 
@@ -90,8 +90,14 @@ impl GovernanceContract {
             .set(&DataKey::NextProposalId, &(proposal_id + 1));
 
         // Emit proposal created event
-        env.events()
-            .publish((PROPOSAL_CREATED,), (proposal_id, creator, payload_ref));
+        publish(
+            &env,
+            ProposalCreated {
+                proposal_id,
+                creator,
+                payload_ref,
+            },
+        );
 
         Ok(proposal_id)
     }
@@ -133,8 +139,14 @@ impl GovernanceContract {
         env.storage().instance().set(&vote_key, &true);
 
         // Emit vote cast event
-        env.events()
-            .publish((VOTE_CAST,), (proposal_id, voter, support));
+        publish(
+            &env,
+            VoteCast {
+                proposal_id,
+                voter,
+                support,
+            },
+        );
 
         Ok(())
     }
@@ -173,19 +185,20 @@ impl GovernanceContract {
             return Err(Error::QuorumNotReached);
         }
 
-        if proposal.yes_votes > proposal.no_votes {
-            proposal.executed = true;
-        } else {
-            proposal.executed = false;
-        }
+        proposal.executed = proposal.yes_votes > proposal.no_votes;
 
         env.storage()
             .instance()
             .set(&DataKey::Proposal(proposal_id), &proposal);
 
         // Emit execution event
-        env.events()
-            .publish((PROPOSAL_EXECUTED,), (proposal_id, proposal.executed));
+        publish(
+            &env,
+            ProposalExecuted {
+                proposal_id,
+                executed: proposal.executed,
+            },
+        );
 
         Ok(())
     }
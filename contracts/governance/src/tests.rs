@@ -42,7 +42,7 @@ fn create_test_proposal(
     end_offset: u64,
 ) -> u64 {
     let current_time = env.ledger().timestamp();
-    let payload = Bytes::from_slice(&env, b"ipfs://QmTest123");
+    let payload = Bytes::from_slice(env, b"ipfs://QmTest123");
 
     client.create_proposal(
         creator,
@@ -99,7 +99,7 @@ fn test_create_proposal_success() {
     assert_eq!(proposal.creator, creator);
     assert_eq!(proposal.yes_votes, 0);
     assert_eq!(proposal.no_votes, 0);
-    assert_eq!(proposal.executed, false);
+    assert!(!proposal.executed);
 }
 
 #[test]
@@ -268,7 +268,7 @@ fn test_finalize_with_quorum_and_majority() {
     client.finalize(&proposal_id);
 
     let proposal = client.get_proposal(&proposal_id);
-    assert_eq!(proposal.executed, true);
+    assert!(proposal.executed);
 }
 
 #[test]
@@ -326,7 +326,7 @@ fn test_finalize_majority_not_reached() {
     client.finalize(&proposal_id);
 
     let proposal = client.get_proposal(&proposal_id);
-    assert_eq!(proposal.executed, false); // Rejected due to no majority
+    assert!(!proposal.executed); // Rejected due to no majority
 }
 
 #[test]
@@ -447,12 +447,12 @@ fn test_has_voted_helper() {
     let proposal_id = create_test_proposal(&env, &client, &creator, 0, 1000);
 
     // Before voting
-    assert_eq!(client.has_voted(&proposal_id, &voter), false);
+    assert!(!client.has_voted(&proposal_id, &voter));
 
     // Vote
     client.vote(&proposal_id, &voter, &true);
 
     // After voting
-    assert_eq!(client.has_voted(&proposal_id, &voter), true);
-    assert_eq!(client.has_voted(&proposal_id, &non_voter), false);
+    assert!(client.has_voted(&proposal_id, &voter));
+    assert!(!client.has_voted(&proposal_id, &non_voter));
 }
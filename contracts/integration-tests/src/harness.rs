@@ -0,0 +1,205 @@
+//! A NovaFund deployment in one `Env`
+
+use cross_chain_bridge::{CrossChainBridge, CrossChainBridgeClient};
+use escrow::{EscrowContract, EscrowContractClient};
+use governance::{GovernanceContract, GovernanceContractClient};
+use multi_party_payment::{MultiPartyPayment, MultiPartyPaymentClient};
+use profit_distribution::{ProfitDistribution, ProfitDistributionClient};
+use project_launch::{ProjectLaunch, ProjectLaunchClient};
+use reputation::{ReputationContract, ReputationContractClient};
+use shared::types::{Amount, MilestoneStatus, ProjectStatus};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Bytes, BytesN, Env, Map, Vec,
+};
+use subscription_pool::{SubscriptionPool, SubscriptionPoolClient};
+
+pub const DAY: u64 = 86_400;
+
+/// Ledger time the platform starts at
+pub const START: u64 = 1_000 * DAY;
+
+/// How long projects launched by [`Platform::launch_project`] take funding
+pub const FUNDING_PERIOD: u64 = 30 * DAY;
+
+/// Governance electorate passed to `initialize`
+pub const VOTERS: u32 = 5;
+
+/// Every contract registered and initialized with one admin, and a payment
+/// token projects raise in
+pub struct Platform<'a> {
+    pub env: Env,
+    pub admin: Address,
+    pub token: TokenClient<'a>,
+    pub token_admin: StellarAssetClient<'a>,
+    pub launch: ProjectLaunchClient<'a>,
+    pub escrow: EscrowContractClient<'a>,
+    pub profits: ProfitDistributionClient<'a>,
+    pub pools: SubscriptionPoolClient<'a>,
+    pub payments: MultiPartyPaymentClient<'a>,
+    pub reputation: ReputationContractClient<'a>,
+    pub governance: GovernanceContractClient<'a>,
+    pub bridge: CrossChainBridgeClient<'a>,
+}
+
+impl<'a> Platform<'a> {
+    pub fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(START);
+        let admin = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let launch = ProjectLaunchClient::new(&env, &env.register_contract(None, ProjectLaunch));
+        let escrow = EscrowContractClient::new(&env, &env.register_contract(None, EscrowContract));
+        let profits =
+            ProfitDistributionClient::new(&env, &env.register_contract(None, ProfitDistribution));
+        let pools =
+            SubscriptionPoolClient::new(&env, &env.register_contract(None, SubscriptionPool));
+        let payments =
+            MultiPartyPaymentClient::new(&env, &env.register_contract(None, MultiPartyPayment));
+        let reputation =
+            ReputationContractClient::new(&env, &env.register_contract(None, ReputationContract));
+        let governance =
+            GovernanceContractClient::new(&env, &env.register_contract(None, GovernanceContract));
+        let bridge =
+            CrossChainBridgeClient::new(&env, &env.register_contract(None, CrossChainBridge));
+
+        launch.initialize(&admin);
+        escrow.initialize_admin(&admin);
+        escrow.set_project_launch(&launch.address);
+        profits.initialize(&admin);
+        pools.initialize(&admin);
+        reputation.initialize(&admin);
+        governance.initialize(&admin, &VOTERS);
+        bridge.initialize(&admin, &0, &1);
+
+        Platform {
+            token: TokenClient::new(&env, &token_id),
+            token_admin: StellarAssetClient::new(&env, &token_id),
+            env,
+            admin,
+            launch,
+            escrow,
+            profits,
+            pools,
+            payments,
+            reputation,
+            governance,
+            bridge,
+        }
+    }
+
+    pub fn users(&self, count: usize) -> std::vec::Vec<Address> {
+        (0..count).map(|_| Address::generate(&self.env)).collect()
+    }
+
+    pub fn now(&self) -> u64 {
+        self.env.ledger().timestamp()
+    }
+
+    pub fn advance(&self, seconds: u64) {
+        self.env.ledger().set_timestamp(self.now() + seconds);
+    }
+
+    pub fn hash(&self, byte: u8) -> BytesN<32> {
+        BytesN::from_array(&self.env, &[byte; 32])
+    }
+
+    pub fn mint(&self, to: &Address, amount: Amount) {
+        self.token_admin.mint(to, &amount);
+    }
+
+    /// Launch a project raising `goal` of the platform token, open for
+    /// [`FUNDING_PERIOD`]
+    pub fn launch_project(&self, creator: &Address, goal: Amount) -> u64 {
+        self.launch_project_in(creator, goal, &self.token.address)
+    }
+
+    /// Launch a project raising `goal` of `token`
+    pub fn launch_project_in(&self, creator: &Address, goal: Amount, token: &Address) -> u64 {
+        self.launch.create_project(
+            creator,
+            &goal,
+            &(self.now() + FUNDING_PERIOD),
+            token,
+            &Bytes::from_slice(&self.env, b"ipfs://project"),
+        )
+    }
+
+    /// Mint `amount` to a backer and contribute it to a project
+    pub fn fund(&self, project_id: u64, backer: &Address, amount: Amount) {
+        self.mint(backer, amount);
+        self.launch.contribute(&project_id, backer, &amount);
+    }
+
+    /// Let a project's funding period run out and settle it
+    pub fn close_funding(&self, project_id: u64) -> ProjectStatus {
+        let project = self.launch.get_project(&project_id);
+        if self.now() <= project.deadline {
+            self.env.ledger().set_timestamp(project.deadline + 1);
+        }
+        self.launch.mark_project_failed(&project_id);
+        self.launch.get_project(&project_id).status
+    }
+
+    /// Open a validator-voted escrow for a funded project and lock its raise
+    ///
+    /// ProjectLaunch keeps what it raised, as it has no payout for completed
+    /// projects yet, so the raise is minted into the escrow in its place.
+    pub fn open_escrow(&self, project_id: u64, validators: &[Address]) {
+        let project = self.launch.get_project(&project_id);
+        self.escrow.initialize(
+            &project_id,
+            &project.creator,
+            &project.token,
+            &Vec::from_slice(&self.env, validators),
+        );
+        self.lock_raise(project_id);
+    }
+
+    /// Lock a project's raise in its already opened escrow
+    pub fn lock_raise(&self, project_id: u64) {
+        let project = self.launch.get_project(&project_id);
+        StellarAssetClient::new(&self.env, &project.token)
+            .mint(&self.escrow.address, &project.total_raised);
+        self.escrow.deposit(&project_id, &project.total_raised);
+    }
+
+    /// Submit a milestone and have the validators vote on it until it is
+    /// decided
+    pub fn vote_milestone(
+        &self,
+        project_id: u64,
+        milestone_id: u64,
+        validators: &[Address],
+        approve: bool,
+    ) -> MilestoneStatus {
+        self.escrow
+            .submit_milestone(&project_id, &milestone_id, &self.hash(milestone_id as u8));
+        for validator in validators {
+            self.escrow
+                .vote_milestone(&project_id, &milestone_id, validator, &approve);
+            let status = self.escrow.get_milestone(&project_id, &milestone_id).status;
+            if status != MilestoneStatus::Submitted {
+                return status;
+            }
+        }
+        MilestoneStatus::Submitted
+    }
+
+    /// Register a project's backers with profit shares in proportion to
+    /// their contributions
+    pub fn register_backers(&self, project_id: u64, backers: &[Address]) -> Map<Address, u32> {
+        let token = self.launch.get_project(&project_id).token;
+        self.profits.set_token(&project_id, &token);
+        self.profits.register_investors_from_launch(
+            &project_id,
+            &self.launch.address,
+            &Vec::from_slice(&self.env, backers),
+        )
+    }
+}
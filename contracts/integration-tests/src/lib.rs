@@ -0,0 +1,10 @@
+//! End-to-end tests across the NovaFund contracts
+//!
+//! Every contract is registered in one `Env` by [`harness::Platform`] and
+//! driven through whole project lifecycles: launch, funding, escrow and
+//! milestones, profit distribution, reputation and governance, as well as
+//! failed projects and funding that arrives over the bridge.
+#![cfg(test)]
+
+mod harness;
+mod tests;
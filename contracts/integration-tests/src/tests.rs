@@ -0,0 +1,460 @@
+use multi_party_payment::Party;
+use profit_distribution::CompoundTarget;
+use reputation::BadgeType;
+use shared::{
+    errors::{DistributionError, Error},
+    types::{BackerVotingConfig, ChainId, MilestoneStatus, ProjectStatus},
+};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Bytes, String,
+};
+use subscription_pool::SubscriptionPeriod;
+
+use crate::harness::{Platform, DAY};
+
+const GOAL: i128 = 10_000_000_000;
+
+#[test]
+fn test_funded_project_lifecycle() {
+    let platform = Platform::new();
+    let [creator, engineer, alice, bob, carol] = platform.users(5).try_into().unwrap();
+    let backers = [alice.clone(), bob.clone(), carol.clone()];
+    let validators = platform.users(3);
+    for user in [&creator, &alice, &bob, &carol] {
+        platform.reputation.register_user(user);
+    }
+
+    // Launch and fund
+    let project_id = platform.launch_project(&creator, GOAL);
+    platform.fund(project_id, &alice, 5_000_000_000);
+    platform.fund(project_id, &bob, 3_000_000_000);
+    platform.fund(project_id, &carol, 2_000_000_000);
+    assert_eq!(platform.token.balance(&platform.launch.address), GOAL);
+    assert_eq!(platform.close_funding(project_id), ProjectStatus::Completed);
+    assert_eq!(
+        platform
+            .launch
+            .try_contribute(&project_id, &alice, &1_000_000_000),
+        Err(Ok(Error::ProjectNotActive))
+    );
+
+    // Escrow the raise and release it milestone by milestone
+    platform.open_escrow(project_id, &validators);
+    platform.escrow.create_milestone(
+        &project_id,
+        &platform.hash(1),
+        &6_000_000_000,
+        &vec![&platform.env],
+    );
+    platform.escrow.create_milestone(
+        &project_id,
+        &platform.hash(2),
+        &4_000_000_000,
+        &vec![&platform.env, 0],
+    );
+    assert_eq!(
+        platform
+            .escrow
+            .try_submit_milestone(&project_id, &1, &platform.hash(3)),
+        Err(Ok(Error::MilestoneNotApproved))
+    );
+    assert_eq!(
+        platform.vote_milestone(project_id, 0, &validators, true),
+        MilestoneStatus::Approved
+    );
+    assert_eq!(platform.token.balance(&creator), 6_000_000_000);
+    assert_eq!(
+        platform.vote_milestone(project_id, 1, &validators, true),
+        MilestoneStatus::Approved
+    );
+    assert_eq!(platform.token.balance(&creator), GOAL);
+    let summary = platform.escrow.get_escrow_summary(&project_id);
+    assert_eq!(summary.released_amount, GOAL);
+    assert_eq!(summary.approved_count, 2);
+    assert_eq!(platform.token.balance(&platform.escrow.address), 0);
+
+    // The creator pays the team out of the released funds
+    platform.payments.setup_parties(
+        &project_id,
+        &vec![
+            &platform.env,
+            Party {
+                address: creator.clone(),
+                share_percentage: 7_000,
+                claimable: 0,
+                total_received: 0,
+            },
+            Party {
+                address: engineer.clone(),
+                share_percentage: 3_000,
+                claimable: 0,
+                total_received: 0,
+            },
+        ],
+    );
+    platform.payments.receive_payment(&project_id, &GOAL);
+    assert_eq!(
+        platform.payments.withdraw_share(&project_id, &1, &engineer),
+        3_000_000_000
+    );
+    assert_eq!(
+        platform.payments.get_party(&project_id, &0).claimable,
+        7_000_000_000
+    );
+
+    // Profits are shared with the backers in proportion to what they put in
+    let shares = platform.register_backers(project_id, &backers);
+    assert_eq!(shares.get(alice.clone()), Some(5_000));
+    platform.mint(&creator, 1_000_000_000);
+    platform
+        .profits
+        .deposit_profits(&project_id, &creator, &1_000_000_000);
+    for (backer, profit) in backers.iter().zip([500_000_000, 300_000_000, 200_000_000]) {
+        assert_eq!(
+            platform.profits.claim_dividends(&project_id, backer),
+            profit
+        );
+        assert_eq!(platform.token.balance(backer), profit);
+    }
+    assert_eq!(
+        platform.profits.try_claim_dividends(&project_id, &alice),
+        Err(Ok(DistributionError::NothingToClaim))
+    );
+
+    // Delivering the project earns reputation
+    platform.reputation.update_score(&creator, &50);
+    platform
+        .reputation
+        .award_badge(&creator, &BadgeType::FirstProject);
+    for backer in &backers {
+        platform.reputation.update_score(backer, &10);
+        platform
+            .reputation
+            .award_badge(backer, &BadgeType::Contributor);
+    }
+    let profile = platform.reputation.get_profile(&creator);
+    assert_eq!(profile.score, 150);
+    assert_eq!(profile.badges, vec![&platform.env, BadgeType::FirstProject]);
+    assert_eq!(platform.reputation.get_profile(&alice).score, 110);
+
+    // Backers take the next decision to governance
+    let start = platform.now();
+    let proposal_id = platform.governance.create_proposal(
+        &alice,
+        &Bytes::from_slice(&platform.env, b"ipfs://second-round"),
+        &start,
+        &(start + 7 * DAY),
+    );
+    platform.governance.vote(&proposal_id, &alice, &true);
+    platform.governance.vote(&proposal_id, &bob, &true);
+    platform.governance.vote(&proposal_id, &carol, &false);
+    assert_eq!(
+        platform.governance.try_vote(&proposal_id, &bob, &false),
+        Err(Ok(Error::AlreadyVoted))
+    );
+    platform.advance(7 * DAY + 1);
+    platform.governance.finalize(&proposal_id);
+    let proposal = platform.governance.get_proposal(&proposal_id);
+    assert_eq!((proposal.yes_votes, proposal.no_votes), (2, 1));
+    assert!(proposal.executed);
+}
+
+#[test]
+fn test_backers_vote_milestones_by_contribution() {
+    let platform = Platform::new();
+    let [creator, alice, bob, outsider] = platform.users(4).try_into().unwrap();
+    let project_id = platform.launch_project(&creator, GOAL);
+    platform.fund(project_id, &alice, 6_000_000_000);
+    platform.fund(project_id, &bob, 4_000_000_000);
+    platform.close_funding(project_id);
+
+    // Votes are weighted by the contributions ProjectLaunch recorded
+    platform.escrow.initialize_backer_voting(
+        &project_id,
+        &creator,
+        &platform.token.address,
+        &BackerVotingConfig {
+            quorum: 5_000,
+            threshold: 5_000,
+            voting_period: 3 * DAY,
+        },
+    );
+    platform.lock_raise(project_id);
+    for (byte, amount) in [(1, 5_000_000_000), (2, 5_000_000_000)] {
+        platform.escrow.create_milestone(
+            &project_id,
+            &platform.hash(byte),
+            &amount,
+            &vec![&platform.env],
+        );
+    }
+
+    platform
+        .escrow
+        .submit_milestone(&project_id, &0, &platform.hash(10));
+    assert_eq!(
+        platform
+            .escrow
+            .try_vote_milestone(&project_id, &0, &outsider, &true),
+        Err(Ok(Error::NotABacker))
+    );
+    platform
+        .escrow
+        .vote_milestone(&project_id, &0, &alice, &true);
+    platform
+        .escrow
+        .vote_milestone(&project_id, &0, &bob, &false);
+    assert_eq!(
+        platform.escrow.try_finalize_milestone(&project_id, &0),
+        Err(Ok(Error::VotingPeriodActive))
+    );
+    platform.advance(3 * DAY + 1);
+    assert_eq!(
+        platform.escrow.finalize_milestone(&project_id, &0),
+        MilestoneStatus::Approved
+    );
+    assert_eq!(platform.token.balance(&creator), 5_000_000_000);
+
+    // The larger backer turns down the second milestone and its funds stay locked
+    platform
+        .escrow
+        .submit_milestone(&project_id, &1, &platform.hash(11));
+    platform
+        .escrow
+        .vote_milestone(&project_id, &1, &alice, &false);
+    platform.escrow.vote_milestone(&project_id, &1, &bob, &true);
+    platform.advance(3 * DAY + 1);
+    assert_eq!(
+        platform.escrow.finalize_milestone(&project_id, &1),
+        MilestoneStatus::Rejected
+    );
+    assert_eq!(platform.token.balance(&creator), 5_000_000_000);
+    assert_eq!(
+        platform.escrow.get_available_balance(&project_id),
+        5_000_000_000
+    );
+    assert_eq!(
+        platform.token.balance(&platform.escrow.address),
+        5_000_000_000
+    );
+}
+
+#[test]
+fn test_failed_project_refunds_backers() {
+    let platform = Platform::new();
+    let [creator, alice, bob, outsider] = platform.users(4).try_into().unwrap();
+    platform.reputation.register_user(&creator);
+    let project_id = platform.launch_project(&creator, GOAL);
+    platform.fund(project_id, &alice, 3_000_000_000);
+    platform.fund(project_id, &bob, 2_000_000_000);
+
+    assert_eq!(
+        platform.launch.try_mark_project_failed(&project_id),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(platform.close_funding(project_id), ProjectStatus::Failed);
+    assert!(platform.launch.is_failure_processed(&project_id));
+    assert_eq!(
+        platform
+            .launch
+            .try_contribute(&project_id, &outsider, &1_000_000_000),
+        Err(Ok(Error::ProjectNotActive))
+    );
+
+    // Refunds are permissionless and paid once
+    assert_eq!(
+        platform.launch.refund_contributor(&project_id, &alice),
+        3_000_000_000
+    );
+    assert_eq!(
+        platform.launch.refund_contributor(&project_id, &bob),
+        2_000_000_000
+    );
+    assert_eq!(
+        platform.launch.try_refund_contributor(&project_id, &alice),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        platform
+            .launch
+            .try_refund_contributor(&project_id, &outsider),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert!(platform.launch.is_refunded(&project_id, &bob));
+    assert_eq!(platform.token.balance(&alice), 3_000_000_000);
+    assert_eq!(platform.token.balance(&bob), 2_000_000_000);
+    assert_eq!(platform.token.balance(&platform.launch.address), 0);
+
+    // A failed project costs its creator reputation
+    assert_eq!(platform.reputation.update_score(&creator, &-50), 50);
+}
+
+#[test]
+fn test_cross_chain_backer_funds_and_withdraws() {
+    let platform = Platform::new();
+    let [creator, backer, relayer] = platform.users(3).try_into().unwrap();
+    let ethereum_backer = platform.hash(0xee);
+    let env = &platform.env;
+
+    // Bridged USDC, issued by a token the bridge admin controls
+    let usdc = env
+        .register_stellar_asset_contract_v2(platform.admin.clone())
+        .address();
+    platform.bridge.add_supported_chain(
+        &ChainId::Ethereum,
+        &String::from_str(env, "Ethereum"),
+        &platform.hash(0xb1),
+        &12,
+        &50_000,
+    );
+    platform.bridge.register_wrapped_asset(
+        &String::from_str(env, "USDC"),
+        &usdc,
+        &ChainId::Ethereum,
+        &platform.hash(0xc0),
+        &7,
+    );
+    platform.bridge.register_relayer(&relayer, &0);
+
+    // A relayer brings the backer's deposit over; the bridge only records
+    // it, so the mint stands in for the wrapped token's issuance
+    let tx_id = platform.bridge.deposit(
+        &ChainId::Ethereum,
+        &platform.hash(1),
+        &ethereum_backer,
+        &backer,
+        &usdc,
+        &GOAL,
+    );
+    StellarAssetClient::new(env, &usdc).mint(&backer, &GOAL);
+    assert_eq!(platform.bridge.get_transaction(&tx_id).amount, GOAL);
+    assert_eq!(platform.bridge.get_total_wrapped(&usdc), GOAL);
+    assert_eq!(
+        platform.bridge.try_deposit(
+            &ChainId::Ethereum,
+            &platform.hash(1),
+            &ethereum_backer,
+            &backer,
+            &usdc,
+            &GOAL,
+        ),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    // The bridged funds back a project raising in USDC, which falls short
+    let project_id = platform.launch_project_in(&creator, 2 * GOAL, &usdc);
+    platform.launch.contribute(&project_id, &backer, &GOAL);
+    assert_eq!(platform.close_funding(project_id), ProjectStatus::Failed);
+    assert_eq!(
+        platform.launch.refund_contributor(&project_id, &backer),
+        GOAL
+    );
+
+    // The refund goes back to Ethereum, but not while the bridge is paused
+    platform.bridge.pause_bridge();
+    assert_eq!(
+        platform
+            .bridge
+            .try_withdraw(&backer, &ChainId::Ethereum, &ethereum_backer, &usdc, &GOAL),
+        Err(Ok(Error::InvalidInput))
+    );
+    platform.bridge.unpause_bridge();
+    let withdrawal =
+        platform
+            .bridge
+            .withdraw(&backer, &ChainId::Ethereum, &ethereum_backer, &usdc, &GOAL);
+    TokenClient::new(env, &usdc).burn(&backer, &GOAL);
+    platform
+        .bridge
+        .confirm_withdrawal(&relayer, &withdrawal, &platform.hash(2));
+    assert_eq!(platform.bridge.get_total_wrapped(&usdc), 0);
+    assert_eq!(
+        platform.bridge.try_confirm_withdrawal(
+            &Address::generate(env),
+            &withdrawal,
+            &platform.hash(2)
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_subscription_pool_funds_project() {
+    let platform = Platform::new();
+    let [creator, alice, bob] = platform.users(3).try_into().unwrap();
+    // The pool pulls each payment from its subscribers
+    platform.env.mock_all_auths_allowing_non_root_auth();
+    let pool_id = platform.pools.create_pool(
+        &String::from_str(&platform.env, "Monthly backers"),
+        &platform.token.address,
+    );
+    for backer in [&alice, &bob] {
+        platform.mint(backer, 6_000_000_000);
+        platform.pools.subscribe(
+            &pool_id,
+            backer,
+            &2_500_000_000,
+            &SubscriptionPeriod::Monthly,
+        );
+    }
+
+    // Payments are collected once a month, and two months pool enough to
+    // fund a project
+    platform.pools.process_deposits(&pool_id);
+    platform.pools.process_deposits(&pool_id);
+    assert_eq!(
+        platform.pools.get_pool(&pool_id).total_balance,
+        5_000_000_000
+    );
+    platform.advance(SubscriptionPeriod::Monthly as u64);
+    platform.pools.process_deposits(&pool_id);
+    assert_eq!(platform.pools.get_pool(&pool_id).total_balance, GOAL);
+    assert_eq!(platform.token.balance(&alice), 1_000_000_000);
+
+    let project_id = platform.launch_project(&creator, GOAL);
+    platform.pools.withdraw(&pool_id, &alice, &GOAL);
+    platform.launch.contribute(&project_id, &alice, &GOAL);
+    assert_eq!(platform.launch.get_project(&project_id).total_raised, GOAL);
+    assert_eq!(platform.close_funding(project_id), ProjectStatus::Completed);
+}
+
+#[test]
+fn test_dividends_compound_into_next_project() {
+    let platform = Platform::new();
+    let [creator, alice, bob, keeper] = platform.users(4).try_into().unwrap();
+    let first = platform.launch_project(&creator, GOAL);
+    platform.fund(first, &alice, 6_000_000_000);
+    platform.fund(first, &bob, 4_000_000_000);
+    assert_eq!(platform.close_funding(first), ProjectStatus::Completed);
+    platform.register_backers(first, &[alice.clone(), bob.clone()]);
+
+    // Alice reinvests her dividends in the creator's next project
+    let next = platform.launch_project(&creator, GOAL);
+    platform.profits.set_keeper_fee(&100);
+    platform.profits.set_compounding(
+        &first,
+        &alice,
+        &Some(CompoundTarget::Project(
+            platform.launch.address.clone(),
+            next,
+        )),
+    );
+    platform.mint(&creator, 1_000_000_000);
+    platform
+        .profits
+        .deposit_profits(&first, &creator, &1_000_000_000);
+
+    assert_eq!(
+        platform.profits.compound(&first, &alice, &keeper),
+        594_000_000
+    );
+    assert_eq!(platform.token.balance(&keeper), 6_000_000);
+    assert_eq!(
+        platform.launch.get_user_contribution(&next, &alice),
+        594_000_000
+    );
+    assert_eq!(platform.launch.get_project(&next).total_raised, 594_000_000);
+    assert_eq!(platform.profits.claim_dividends(&first, &bob), 400_000_000);
+}
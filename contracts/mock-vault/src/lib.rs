@@ -0,0 +1,137 @@
+#![no_std]
+
+//! Minimal yield vault used to exercise the escrow yield adapter in tests.
+//!
+//! Deposits mint shares against the vault's asset balance, so any tokens sent
+//! straight to the vault raise the value of every share, which is how tests
+//! simulate accrued yield.
+
+use soroban_sdk::{contract, contractimpl, contracttype, token::TokenClient, Address, Env};
+
+/// Storage keys for the mock vault
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    /// Token the vault accepts
+    Asset,
+    /// Shares issued across all owners
+    TotalShares,
+    /// Shares held by an owner
+    Shares(Address),
+}
+
+#[contract]
+pub struct MockVault;
+
+#[contractimpl]
+impl MockVault {
+    /// Set the token the vault accepts
+    pub fn initialize(env: Env, asset: Address) {
+        if env.storage().instance().has(&DataKey::Asset) {
+            panic!("already initialized");
+        }
+        env.storage().instance().set(&DataKey::Asset, &asset);
+    }
+
+    /// Token the vault accepts
+    pub fn asset(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::Asset).unwrap()
+    }
+
+    /// Move `amount` of the asset from `from` into the vault, returning the shares minted
+    pub fn deposit(env: Env, from: Address, amount: i128) -> i128 {
+        from.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+
+        let total_assets = Self::total_assets(env.clone());
+        let total_shares = total_shares(&env);
+        let shares = if total_shares == 0 || total_assets == 0 {
+            amount
+        } else {
+            amount * total_shares / total_assets
+        };
+
+        TokenClient::new(&env, &Self::asset(env.clone())).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+        set_shares(&env, &from, shares_of(&env, &from) + shares);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalShares, &(total_shares + shares));
+        shares
+    }
+
+    /// Pay `amount` of the asset to `owner`, returning the shares burned
+    pub fn withdraw(env: Env, owner: Address, amount: i128) -> i128 {
+        owner.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+
+        let total_assets = Self::total_assets(env.clone());
+        let total_shares = total_shares(&env);
+        if total_assets == 0 {
+            panic!("empty vault");
+        }
+        // Round up so withdrawals never dilute the remaining owners
+        let shares = (amount * total_shares + total_assets - 1) / total_assets;
+        let owned = shares_of(&env, &owner);
+        if shares > owned {
+            panic!("insufficient shares");
+        }
+
+        set_shares(&env, &owner, owned - shares);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalShares, &(total_shares - shares));
+        TokenClient::new(&env, &Self::asset(env.clone())).transfer(
+            &env.current_contract_address(),
+            &owner,
+            &amount,
+        );
+        shares
+    }
+
+    /// Current asset value of `shares`
+    pub fn preview_redeem(env: Env, shares: i128) -> i128 {
+        let total_shares = total_shares(&env);
+        if total_shares == 0 {
+            return 0;
+        }
+        shares * Self::total_assets(env) / total_shares
+    }
+
+    /// Shares held by `owner`
+    pub fn shares(env: Env, owner: Address) -> i128 {
+        shares_of(&env, &owner)
+    }
+
+    /// Asset balance held by the vault, including accrued yield
+    pub fn total_assets(env: Env) -> i128 {
+        TokenClient::new(&env, &Self::asset(env.clone())).balance(&env.current_contract_address())
+    }
+}
+
+fn total_shares(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::TotalShares)
+        .unwrap_or(0)
+}
+
+fn shares_of(env: &Env, owner: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Shares(owner.clone()))
+        .unwrap_or(0)
+}
+
+fn set_shares(env: &Env, owner: &Address, shares: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Shares(owner.clone()), &shares);
+}
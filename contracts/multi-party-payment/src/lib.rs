@@ -1,10 +1,14 @@
 #![no_std]
 
  use soroban_sdk::{
-     contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec,
+     contract, contractimpl, contracttype, Address, Env, Vec,
  };
 
- use shared::utils::calculate_share;
+ use shared::{
+     errors::PaymentError,
+     events::{publish, PaymentReceived, PaymentSetup, PaymentWithdrawn},
+     utils::calculate_share,
+ };
 
 // TODO: Implement multi-party payment contract
 // This contract will handle:
@@ -13,10 +17,6 @@
 // - Party withdrawal mechanism
 // - Optional vesting schedules
 
- pub const PARTIES_SETUP: Symbol = symbol_short!("mp_setup");
- pub const PAYMENT_RECEIVED: Symbol = symbol_short!("mp_pay");
- pub const SHARE_WITHDRAWN: Symbol = symbol_short!("mp_with");
-
  #[contracttype]
  #[derive(Clone)]
  pub struct Party {
@@ -33,18 +33,6 @@
      Parties = 0,
  }
 
- #[contracterror]
- #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
- #[repr(u32)]
- pub enum MultiPartyPaymentError {
-     InvalidInput = 1000,
-     InvalidShareTotal = 1001,
-     PartyIndexOutOfBounds = 1002,
-     PartiesNotSetup = 1003,
-     PartyMismatch = 1004,
-     NoClaimableAmount = 1005,
- }
-
  #[contract]
  pub struct MultiPartyPayment;
 
@@ -52,11 +40,11 @@
      (DataKey::Parties, project_id)
  }
 
- fn load_parties(env: &Env, project_id: u64) -> Result<Vec<Party>, MultiPartyPaymentError> {
+ fn load_parties(env: &Env, project_id: u64) -> Result<Vec<Party>, PaymentError> {
      env.storage()
          .persistent()
          .get(&get_parties_key(project_id))
-         .ok_or(MultiPartyPaymentError::PartiesNotSetup)
+         .ok_or(PaymentError::PartiesNotSetup)
  }
 
  fn store_parties(env: &Env, project_id: u64, parties: &Vec<Party>) {
@@ -65,28 +53,28 @@
          .set(&get_parties_key(project_id), parties);
  }
 
- fn validate_parties(parties: &Vec<Party>) -> Result<(), MultiPartyPaymentError> {
+ fn validate_parties(parties: &Vec<Party>) -> Result<(), PaymentError> {
      if parties.is_empty() {
-         return Err(MultiPartyPaymentError::InvalidInput);
+         return Err(PaymentError::InvalidInput);
      }
 
      let mut total: u32 = 0;
      for p in parties.iter() {
          total = total
              .checked_add(p.share_percentage)
-             .ok_or(MultiPartyPaymentError::InvalidInput)?;
+             .ok_or(PaymentError::InvalidInput)?;
      }
 
      if total != 10000 {
-         return Err(MultiPartyPaymentError::InvalidShareTotal);
+         return Err(PaymentError::InvalidShareTotal);
      }
 
      Ok(())
  }
 
- fn get_party_by_index(parties: &Vec<Party>, party_index: u32) -> Result<Party, MultiPartyPaymentError> {
+ fn get_party_by_index(parties: &Vec<Party>, party_index: u32) -> Result<Party, PaymentError> {
      if party_index >= parties.len() {
-         return Err(MultiPartyPaymentError::PartyIndexOutOfBounds);
+         return Err(PaymentError::PartyIndexOutOfBounds);
      }
      Ok(parties.get(party_index).unwrap())
  }
@@ -97,7 +85,7 @@
          env: Env,
          project_id: u64,
          parties: Vec<Party>,
-     ) -> Result<(), MultiPartyPaymentError> {
+     ) -> Result<(), PaymentError> {
          validate_parties(&parties)?;
 
          let mut sanitized = Vec::new(&env);
@@ -112,53 +100,58 @@
 
          store_parties(&env, project_id, &sanitized);
 
-         env.events()
-             .publish((PARTIES_SETUP,), (project_id, sanitized.len()));
+         publish(
+             &env,
+             PaymentSetup {
+                 project_id,
+                 party_count: sanitized.len(),
+             },
+         );
 
          Ok(())
      }
 
-     pub fn receive_payment(env: Env, project_id: u64, amount: i128) -> Result<(), MultiPartyPaymentError> {
+     pub fn receive_payment(env: Env, project_id: u64, amount: i128) -> Result<(), PaymentError> {
          if amount <= 0 {
-             return Err(MultiPartyPaymentError::InvalidInput);
+             return Err(PaymentError::InvalidInput);
          }
 
          let mut parties = load_parties(&env, project_id)?;
 
          let mut allocated_sum: i128 = 0;
-         let last_idx = (parties.len() as u32)
+         let last_idx = parties.len()
             .checked_sub(1)
-            .ok_or(MultiPartyPaymentError::PartiesNotSetup)?;
+            .ok_or(PaymentError::PartiesNotSetup)?;
 
-         for i in 0u32..parties.len() as u32 {
+         for i in 0u32..parties.len() {
              let mut p = parties.get(i).unwrap();
              let mut share_amt: i128 = calculate_share(amount, p.share_percentage);
 
              if i == last_idx {
                  let remainder = amount
                      .checked_sub(allocated_sum)
-                     .ok_or(MultiPartyPaymentError::InvalidInput)?;
+                     .ok_or(PaymentError::InvalidInput)?;
                  share_amt = remainder;
              }
 
              allocated_sum = allocated_sum
                  .checked_add(share_amt)
-                 .ok_or(MultiPartyPaymentError::InvalidInput)?;
+                 .ok_or(PaymentError::InvalidInput)?;
 
              p.claimable = p
                  .claimable
                  .checked_add(share_amt)
-                 .ok_or(MultiPartyPaymentError::InvalidInput)?;
+                 .ok_or(PaymentError::InvalidInput)?;
              p.total_received = p
                  .total_received
                  .checked_add(share_amt)
-                 .ok_or(MultiPartyPaymentError::InvalidInput)?;
+                 .ok_or(PaymentError::InvalidInput)?;
 
              parties.set(i, p);
          }
 
          store_parties(&env, project_id, &parties);
-         env.events().publish((PAYMENT_RECEIVED,), (project_id, amount));
+         publish(&env, PaymentReceived { project_id, amount });
 
          Ok(())
      }
@@ -168,22 +161,22 @@
          project_id: u64,
          party_index: u32,
          party: Address,
-     ) -> Result<i128, MultiPartyPaymentError> {
+     ) -> Result<i128, PaymentError> {
          party.require_auth();
 
          let mut parties = load_parties(&env, project_id)?;
 
-         if party_index >= parties.len() as u32 {
-            return Err(MultiPartyPaymentError::PartyIndexOutOfBounds);
+         if party_index >= parties.len() {
+            return Err(PaymentError::PartyIndexOutOfBounds);
         }
 
          let mut p = parties.get(party_index).unwrap();
          if p.address != party {
-             return Err(MultiPartyPaymentError::PartyMismatch);
+             return Err(PaymentError::PartyMismatch);
          }
 
          if p.claimable <= 0 {
-             return Err(MultiPartyPaymentError::NoClaimableAmount);
+             return Err(PaymentError::NoClaimableAmount);
          }
 
          let withdrawn = p.claimable;
@@ -192,13 +185,20 @@
 
          store_parties(&env, project_id, &parties);
 
-         env.events()
-             .publish((SHARE_WITHDRAWN,), (project_id, party_index, party, withdrawn));
+         publish(
+             &env,
+             PaymentWithdrawn {
+                 project_id,
+                 party_index,
+                 party,
+                 amount: withdrawn,
+             },
+         );
 
          Ok(withdrawn)
      }
 
-     pub fn get_party(env: Env, project_id: u64, party_index: u32) -> Result<Party, MultiPartyPaymentError> {
+     pub fn get_party(env: Env, project_id: u64, party_index: u32) -> Result<Party, PaymentError> {
          let parties = load_parties(&env, project_id)?;
          get_party_by_index(&parties, party_index)
      }
@@ -0,0 +1,459 @@
+//! Operator tooling for NovaFund deployments
+//!
+//! [`Operator`] deploys the workspace contracts and records their addresses
+//! in a [`Manifest`], wires a launched project to its escrow and profit
+//! distribution, runs admin actions and reads state. In [`Mode::DryRun`]
+//! every transaction is built and simulated but returned unsigned instead
+//! of being sent. The `novafund` binary is a thin command line over it.
+use std::{
+    cell::{Cell, RefCell},
+    fmt, fs,
+    path::Path,
+};
+
+use novafund_client::{Backend, Call, ClientError, NovaFundClient, Signer};
+use sha2::{Digest, Sha256};
+use shared::types::{BridgeConfig, ChainId, EscrowInfo, Project};
+use soroban_sdk::{
+    xdr::{Limits, WriteXdr},
+    Address, BytesN, Env, String as SorobanString, TryFromVal, Val, Vec,
+};
+
+mod manifest;
+
+pub use manifest::{Contract, Manifest};
+
+#[derive(Debug)]
+pub enum CliError {
+    Client(ClientError),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The manifest has no address for this contract
+    NotDeployed(&'static str),
+    Usage(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Client(err) => write!(f, "{err}"),
+            CliError::Io(err) => write!(f, "io error: {err}"),
+            CliError::Json(err) => write!(f, "invalid manifest: {err}"),
+            CliError::NotDeployed(name) => write!(f, "{name} is not in the manifest"),
+            CliError::Usage(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<ClientError> for CliError {
+    fn from(err: ClientError) -> Self {
+        CliError::Client(err)
+    }
+}
+
+impl From<stellar_xdr::curr::Error> for CliError {
+    fn from(err: stellar_xdr::curr::Error) -> Self {
+        CliError::Client(err.into())
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(err: std::io::Error) -> Self {
+        CliError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    fn from(err: serde_json::Error) -> Self {
+        CliError::Json(err)
+    }
+}
+
+/// How transactions are handled
+pub enum Mode {
+    /// Sign with this key and send
+    Send(Signer),
+    /// Build unsigned transactions with `source` as the source account
+    DryRun { source: String },
+}
+
+/// A transaction built in a dry run
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Unsigned {
+    pub action: String,
+    /// Base64 `TransactionEnvelope` XDR
+    pub xdr: String,
+}
+
+/// Constructor arguments for [`Operator::deploy`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeployOptions {
+    /// Mixed into the contract address salts, to deploy more than one
+    /// NovaFund from the same account
+    pub salt: String,
+    pub governance_voters: u32,
+    pub bridge_min_relayer_stake: i128,
+    pub bridge_confirmation_threshold: u32,
+}
+
+impl Default for DeployOptions {
+    fn default() -> Self {
+        Self {
+            salt: String::new(),
+            governance_voters: 1,
+            bridge_min_relayer_stake: 0,
+            bridge_confirmation_threshold: 1,
+        }
+    }
+}
+
+/// Parameters [`Operator::set_fees`] changes; `None` leaves one as it is
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FeeUpdate {
+    /// Escrow validators' share of released milestones, in basis points
+    pub validator_fee_share: Option<u32>,
+    /// Escrow validator stake slashed for an upheld dispute, in basis points
+    pub slash_rate: Option<u32>,
+    /// Highest gas price the bridge accepts
+    pub max_gas_price: Option<u64>,
+}
+
+pub struct Operator<B> {
+    client: NovaFundClient<B>,
+    mode: Mode,
+    manifest: Manifest,
+    next_sequence: Cell<Option<i64>>,
+    unsigned: RefCell<std::vec::Vec<Unsigned>>,
+}
+
+impl<B: Backend> Operator<B> {
+    pub fn new(client: NovaFundClient<B>, mode: Mode, manifest: Manifest) -> Self {
+        Self {
+            client,
+            mode,
+            manifest,
+            next_sequence: Cell::new(None),
+            unsigned: RefCell::default(),
+        }
+    }
+
+    pub fn client(&self) -> &NovaFundClient<B> {
+        &self.client
+    }
+
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Transactions built so far in a dry run, in the order to send them
+    pub fn unsigned(&self) -> std::vec::Vec<Unsigned> {
+        self.unsigned.borrow().clone()
+    }
+
+    /// Account paying for and authorizing the transactions
+    pub fn source(&self) -> String {
+        match &self.mode {
+            Mode::Send(signer) => signer.account_id(),
+            Mode::DryRun { source } => source.clone(),
+        }
+    }
+
+    /// Upload and create every contract, then initialize them with the
+    /// source account as admin. Addresses go into the manifest as they are
+    /// created, so a failed deployment can be inspected and finished by
+    /// hand.
+    ///
+    /// Creating and initializing a contract needs its code on the ledger,
+    /// so a dry run stops after the uploads. The addresses the contracts
+    /// will get are returned either way.
+    pub fn deploy(
+        &mut self,
+        wasm_dir: &Path,
+        options: &DeployOptions,
+    ) -> Result<std::vec::Vec<(Contract, String)>, CliError> {
+        let source = self.source();
+        let mut deployed = std::vec::Vec::new();
+        let mut hashes = std::vec::Vec::new();
+        for contract in Contract::ALL {
+            let wasm = fs::read(wasm_dir.join(contract.wasm_file()))?;
+            self.execute(
+                format!("upload {}", contract.name()),
+                self.client.upload_wasm(&wasm),
+            )?;
+            hashes.push(<[u8; 32]>::from(Sha256::digest(&wasm)));
+        }
+
+        for (contract, hash) in Contract::ALL.into_iter().zip(hashes) {
+            let salt = Sha256::digest(format!("novafund:{}:{}", contract.name(), options.salt));
+            let address = self.client.contract_address(&source, salt.into())?;
+            deployed.push((contract, address.clone()));
+            if self.is_dry_run() {
+                continue;
+            }
+            self.execute(
+                format!("create {}", contract.name()),
+                self.client.create_contract(&source, hash, salt.into()),
+            )?;
+            self.manifest.set_address(contract, address);
+        }
+        if self.is_dry_run() {
+            return Ok(deployed);
+        }
+
+        self.manifest.network_passphrase = self.client.backend().network_passphrase();
+        self.manifest.admin = source.clone();
+        let admin = self.client.address(&source)?;
+        for contract in Contract::ALL {
+            let address = self.manifest.address(contract)?;
+            let action = format!("initialize {}", contract.name());
+            match contract {
+                Contract::ProjectLaunch => self.execute(
+                    action,
+                    self.client.project_launch(address)?.initialize(&admin),
+                )?,
+                Contract::Escrow => {
+                    let escrow = self.client.escrow(address)?;
+                    self.execute(action, escrow.initialize_admin(&admin))?;
+                    // Backer voting reads projects from the deployed ProjectLaunch
+                    let project_launch = self
+                        .client
+                        .address(self.manifest.address(Contract::ProjectLaunch)?)?;
+                    self.execute(
+                        "link escrow to project-launch",
+                        escrow.set_project_launch(&project_launch),
+                    )?
+                }
+                Contract::ProfitDistribution => self.execute(
+                    action,
+                    self.client.profit_distribution(address)?.initialize(&admin),
+                )?,
+                Contract::SubscriptionPool => self.execute(
+                    action,
+                    self.client.subscription_pool(address)?.initialize(&admin),
+                )?,
+                Contract::Reputation => {
+                    self.execute(action, self.client.reputation(address)?.initialize(&admin))?
+                }
+                Contract::Governance => self.execute(
+                    action,
+                    self.client
+                        .governance(address)?
+                        .initialize(&admin, &options.governance_voters),
+                )?,
+                Contract::CrossChainBridge => self.execute(
+                    action,
+                    self.client.cross_chain_bridge(address)?.initialize(
+                        &admin,
+                        &options.bridge_min_relayer_stake,
+                        &options.bridge_confirmation_threshold,
+                    ),
+                )?,
+                // Parties are set up per project, there is no admin
+                Contract::MultiPartyPayment => None,
+            };
+        }
+        Ok(deployed)
+    }
+
+    /// Connect a launched project to the rest of the deployment: open its
+    /// escrow with the project's creator and token, and register the token
+    /// for its profit distribution.
+    ///
+    /// The escrow is authorized by the project creator, so unless the
+    /// creator is the source account the transaction also needs the
+    /// creator's signature on its auth entry.
+    pub fn wire(&self, project_id: u64, validators: &[String]) -> Result<(), CliError> {
+        let project = self.get_project(project_id)?;
+        let validators = self.addresses(validators)?;
+        self.execute(
+            format!("initialize escrow for project {project_id}"),
+            self.client
+                .escrow(self.manifest.address(Contract::Escrow)?)?
+                .initialize(&project_id, &project.creator, &project.token, &validators),
+        )?;
+        self.execute(
+            format!("set profit token for project {project_id}"),
+            self.client
+                .profit_distribution(self.manifest.address(Contract::ProfitDistribution)?)?
+                .set_token(&project_id, &project.token),
+        )?;
+        Ok(())
+    }
+
+    /// Stop bridge deposits and withdrawals
+    pub fn pause(&self) -> Result<(), CliError> {
+        self.execute("pause bridge", self.bridge()?.pause_bridge())?;
+        Ok(())
+    }
+
+    pub fn unpause(&self) -> Result<(), CliError> {
+        self.execute("unpause bridge", self.bridge()?.unpause_bridge())?;
+        Ok(())
+    }
+
+    pub fn set_fees(&self, update: &FeeUpdate) -> Result<(), CliError> {
+        if update == &FeeUpdate::default() {
+            return Err(CliError::Usage("no fee to set".to_string()));
+        }
+        if update.validator_fee_share.is_some() || update.slash_rate.is_some() {
+            let escrow = self
+                .client
+                .escrow(self.manifest.address(Contract::Escrow)?)?;
+            let mut config = escrow.get_validator_registry().simulate(&self.source())?;
+            if let Some(fee_share) = update.validator_fee_share {
+                config.fee_share = fee_share;
+            }
+            if let Some(slash_rate) = update.slash_rate {
+                config.slash_rate = slash_rate;
+            }
+            self.execute(
+                "configure escrow validator registry",
+                escrow.configure_validator_registry(&config),
+            )?;
+        }
+        if let Some(max_gas_price) = update.max_gas_price {
+            self.execute(
+                "update bridge config",
+                self.bridge()?
+                    .update_config(&None, &None, &Some(max_gas_price)),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Replace a project's escrow validators
+    pub fn update_validators(
+        &self,
+        project_id: u64,
+        validators: &[String],
+    ) -> Result<(), CliError> {
+        let validators = self.addresses(validators)?;
+        self.execute(
+            format!("update validators for project {project_id}"),
+            self.client
+                .escrow(self.manifest.address(Contract::Escrow)?)?
+                .update_validators(&project_id, &validators),
+        )?;
+        Ok(())
+    }
+
+    /// Let the bridge accept transfers from and to `chain`
+    pub fn add_chain(
+        &self,
+        chain: ChainId,
+        name: &str,
+        bridge_contract: [u8; 32],
+        confirmations: u32,
+        gas_estimate: u64,
+    ) -> Result<(), CliError> {
+        let env = self.client.env();
+        self.execute(
+            format!("add chain {name}"),
+            self.bridge()?.add_supported_chain(
+                &chain,
+                &SorobanString::from_str(env, name),
+                &BytesN::from_array(env, &bridge_contract),
+                &confirmations,
+                &gas_estimate,
+            ),
+        )?;
+        Ok(())
+    }
+
+    pub fn get_project(&self, project_id: u64) -> Result<Project, CliError> {
+        Ok(self
+            .client
+            .project_launch(self.manifest.address(Contract::ProjectLaunch)?)?
+            .get_project(&project_id)
+            .simulate(&self.source())?)
+    }
+
+    pub fn get_escrow(&self, project_id: u64) -> Result<EscrowInfo, CliError> {
+        Ok(self
+            .client
+            .escrow(self.manifest.address(Contract::Escrow)?)?
+            .get_escrow(&project_id)
+            .simulate(&self.source())?)
+    }
+
+    /// Bridge configuration
+    pub fn get_config(&self) -> Result<BridgeConfig, CliError> {
+        Ok(self.bridge()?.get_config().simulate(&self.source())?)
+    }
+
+    fn is_dry_run(&self) -> bool {
+        matches!(self.mode, Mode::DryRun { .. })
+    }
+
+    fn bridge(&self) -> Result<novafund_client::CrossChainBridge<'_, B>, CliError> {
+        Ok(self
+            .client
+            .cross_chain_bridge(self.manifest.address(Contract::CrossChainBridge)?)?)
+    }
+
+    fn addresses(&self, strkeys: &[String]) -> Result<Vec<Address>, CliError> {
+        let mut addresses = Vec::new(self.client.env());
+        for strkey in strkeys {
+            addresses.push_back(self.client.address(strkey)?);
+        }
+        Ok(addresses)
+    }
+
+    /// Send `call`, or in a dry run build it and keep it for later. Dry
+    /// run transactions take consecutive sequence numbers.
+    fn execute<T: TryFromVal<Env, Val>>(
+        &self,
+        action: impl Into<String>,
+        call: Call<'_, B, T>,
+    ) -> Result<Option<T>, CliError> {
+        match &self.mode {
+            Mode::Send(signer) => Ok(Some(call.send(signer)?)),
+            Mode::DryRun { source } => {
+                let sequence = match self.next_sequence.get() {
+                    Some(sequence) => sequence,
+                    None => self.client.sequence(source)? + 1,
+                };
+                let tx = call.build_at(source, sequence)?;
+                self.next_sequence.set(Some(sequence + 1));
+                self.unsigned.borrow_mut().push(Unsigned {
+                    action: action.into(),
+                    xdr: tx.to_xdr_base64(Limits::none())?,
+                });
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Parse a chain by name (`ethereum`, `bsc`, ...) or EVM chain id
+pub fn parse_chain(chain: &str) -> Result<ChainId, CliError> {
+    match chain.to_lowercase().as_str() {
+        "ethereum" | "1" => Ok(ChainId::Ethereum),
+        "polygon" | "137" => Ok(ChainId::Polygon),
+        "bsc" | "binance-smart-chain" | "56" => Ok(ChainId::BinanceSmartChain),
+        "avalanche" | "43114" => Ok(ChainId::Avalanche),
+        "arbitrum" | "42161" => Ok(ChainId::Arbitrum),
+        "optimism" | "10" => Ok(ChainId::Optimism),
+        "base" | "8453" => Ok(ChainId::Base),
+        _ => Err(CliError::Usage(format!("unknown chain {chain}"))),
+    }
+}
+
+/// Parse 32 bytes of hex, with or without a `0x` prefix
+pub fn parse_bytes32(hex: &str) -> Result<[u8; 32], CliError> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    let invalid = || CliError::Usage(format!("expected 32 bytes of hex, got {hex}"));
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let mut bytes = [0; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests;
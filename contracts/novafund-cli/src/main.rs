@@ -0,0 +1,246 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use novafund_cli::{
+    parse_bytes32, parse_chain, CliError, DeployOptions, FeeUpdate, Manifest, Mode, Operator,
+};
+use novafund_client::{Network, NovaFundClient, RpcBackend, Signer};
+use shared::types::ChainId;
+
+/// Deploy and operate NovaFund contracts
+#[derive(Parser)]
+#[command(name = "novafund", version)]
+struct Cli {
+    /// Deployment manifest with the contract addresses
+    #[arg(long, global = true, default_value = "novafund.json")]
+    manifest: PathBuf,
+
+    #[arg(long, global = true, value_enum, default_value = "testnet")]
+    network: NetworkName,
+
+    /// RPC endpoint, overriding the network's default
+    #[arg(long, global = true)]
+    rpc_url: Option<String>,
+
+    /// Network passphrase, overriding the network's default
+    #[arg(long, global = true)]
+    network_passphrase: Option<String>,
+
+    /// `S...` secret key signing the transactions
+    #[arg(
+        long,
+        global = true,
+        env = "NOVAFUND_SECRET_KEY",
+        hide_env_values = true
+    )]
+    secret_key: Option<String>,
+
+    /// Print unsigned transactions instead of sending them
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Source account for dry runs and reads, instead of the secret key's
+    #[arg(long, global = true)]
+    source: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum NetworkName {
+    Testnet,
+    Futurenet,
+    Standalone,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Deploy and initialize every contract, writing their addresses to the manifest
+    Deploy {
+        /// Directory with the release wasm builds
+        #[arg(long, default_value = "target/wasm32-unknown-unknown/release")]
+        wasm_dir: PathBuf,
+        /// Salt for the contract addresses, to deploy more than once from one account
+        #[arg(long, default_value = "")]
+        salt: String,
+        #[arg(long, default_value_t = 1)]
+        governance_voters: u32,
+        #[arg(long, default_value_t = 0)]
+        bridge_min_relayer_stake: i128,
+        #[arg(long, default_value_t = 1)]
+        bridge_confirmation_threshold: u32,
+    },
+    /// Open a launched project's escrow and register its profit token
+    Wire {
+        project_id: u64,
+        /// Escrow validators, comma separated
+        #[arg(long, value_delimiter = ',', required = true)]
+        validators: Vec<String>,
+    },
+    /// Pause the bridge
+    Pause,
+    /// Unpause the bridge
+    Unpause,
+    /// Change escrow validator fees and slashing, or the bridge gas price cap
+    SetFees {
+        /// Validators' share of released milestones, in basis points
+        #[arg(long)]
+        validator_fee_share: Option<u32>,
+        /// Stake slashed for an upheld dispute, in basis points
+        #[arg(long)]
+        slash_rate: Option<u32>,
+        #[arg(long)]
+        max_gas_price: Option<u64>,
+    },
+    /// Replace a project's escrow validators
+    UpdateValidators {
+        project_id: u64,
+        #[arg(required = true)]
+        validators: Vec<String>,
+    },
+    /// Add a chain to the bridge
+    AddChain {
+        /// Chain name or EVM chain id
+        #[arg(value_parser = chain)]
+        chain: ChainId,
+        #[arg(long)]
+        name: String,
+        /// Bridge contract on the chain, 32 bytes of hex
+        #[arg(long, value_parser = bytes32)]
+        bridge_contract: [u8; 32],
+        #[arg(long)]
+        confirmations: u32,
+        #[arg(long)]
+        gas_estimate: u64,
+    },
+    /// Print a project from project-launch
+    GetProject { project_id: u64 },
+    /// Print a project's escrow
+    GetEscrow { project_id: u64 },
+    /// Print the bridge configuration
+    GetConfig,
+}
+
+fn chain(value: &str) -> Result<ChainId, String> {
+    parse_chain(value).map_err(|err| err.to_string())
+}
+
+fn bytes32(value: &str) -> Result<[u8; 32], String> {
+    parse_bytes32(value).map_err(|err| err.to_string())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), CliError> {
+    let mut network = match cli.network {
+        NetworkName::Testnet => Network::testnet(),
+        NetworkName::Futurenet => Network::futurenet(),
+        NetworkName::Standalone => Network::standalone(),
+    };
+    if let Some(rpc_url) = cli.rpc_url {
+        network.rpc_url = rpc_url;
+    }
+    if let Some(passphrase) = cli.network_passphrase {
+        network.passphrase = passphrase;
+    }
+
+    let signer = cli
+        .secret_key
+        .as_deref()
+        .map(Signer::from_secret)
+        .transpose()?;
+    let source = cli
+        .source
+        .or_else(|| signer.as_ref().map(Signer::account_id));
+    let mode = match (cli.dry_run, signer, source) {
+        (false, Some(signer), _) => Mode::Send(signer),
+        (_, _, Some(source)) => Mode::DryRun { source },
+        _ => {
+            return Err(CliError::Usage(
+                "set NOVAFUND_SECRET_KEY, or pass --source for dry runs and reads".to_string(),
+            ))
+        }
+    };
+    let reads_only = matches!(
+        cli.command,
+        Command::GetProject { .. } | Command::GetEscrow { .. } | Command::GetConfig
+    );
+    if !cli.dry_run && !reads_only && matches!(mode, Mode::DryRun { .. }) {
+        return Err(CliError::Usage(
+            "sending needs NOVAFUND_SECRET_KEY, or pass --dry-run".to_string(),
+        ));
+    }
+
+    let manifest = Manifest::load(&cli.manifest)?;
+    let client = NovaFundClient::new(RpcBackend::new(network));
+    let mut operator = Operator::new(client, mode, manifest);
+
+    match cli.command {
+        Command::Deploy {
+            wasm_dir,
+            salt,
+            governance_voters,
+            bridge_min_relayer_stake,
+            bridge_confirmation_threshold,
+        } => {
+            let options = DeployOptions {
+                salt,
+                governance_voters,
+                bridge_min_relayer_stake,
+                bridge_confirmation_threshold,
+            };
+            let result = operator.deploy(&wasm_dir, &options);
+            // Keep whatever was created, even if a later step failed
+            if !cli.dry_run {
+                operator.manifest().save(&cli.manifest)?;
+            }
+            for (contract, address) in result? {
+                println!("{:<20} {address}", contract.name());
+            }
+        }
+        Command::Wire {
+            project_id,
+            validators,
+        } => operator.wire(project_id, &validators)?,
+        Command::Pause => operator.pause()?,
+        Command::Unpause => operator.unpause()?,
+        Command::SetFees {
+            validator_fee_share,
+            slash_rate,
+            max_gas_price,
+        } => operator.set_fees(&FeeUpdate {
+            validator_fee_share,
+            slash_rate,
+            max_gas_price,
+        })?,
+        Command::UpdateValidators {
+            project_id,
+            validators,
+        } => operator.update_validators(project_id, &validators)?,
+        Command::AddChain {
+            chain,
+            name,
+            bridge_contract,
+            confirmations,
+            gas_estimate,
+        } => operator.add_chain(chain, &name, bridge_contract, confirmations, gas_estimate)?,
+        Command::GetProject { project_id } => println!("{:#?}", operator.get_project(project_id)?),
+        Command::GetEscrow { project_id } => println!("{:#?}", operator.get_escrow(project_id)?),
+        Command::GetConfig => println!("{:#?}", operator.get_config()?),
+    }
+
+    for unsigned in operator.unsigned() {
+        println!("# {}\n{}", unsigned.action, unsigned.xdr);
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! The deployment manifest: which contract lives where
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::CliError;
+
+/// A NovaFund contract the CLI deploys
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Contract {
+    ProjectLaunch,
+    Escrow,
+    ProfitDistribution,
+    SubscriptionPool,
+    MultiPartyPayment,
+    Reputation,
+    Governance,
+    CrossChainBridge,
+}
+
+impl Contract {
+    /// In deployment order
+    pub const ALL: [Contract; 8] = [
+        Contract::ProjectLaunch,
+        Contract::Escrow,
+        Contract::ProfitDistribution,
+        Contract::SubscriptionPool,
+        Contract::MultiPartyPayment,
+        Contract::Reputation,
+        Contract::Governance,
+        Contract::CrossChainBridge,
+    ];
+
+    /// Crate name, also the key in the manifest
+    pub fn name(self) -> &'static str {
+        match self {
+            Contract::ProjectLaunch => "project-launch",
+            Contract::Escrow => "escrow",
+            Contract::ProfitDistribution => "profit-distribution",
+            Contract::SubscriptionPool => "subscription-pool",
+            Contract::MultiPartyPayment => "multi-party-payment",
+            Contract::Reputation => "reputation",
+            Contract::Governance => "governance",
+            Contract::CrossChainBridge => "cross-chain-bridge",
+        }
+    }
+
+    /// File name of the release build
+    pub fn wasm_file(self) -> String {
+        format!("{}.wasm", self.name().replace('-', "_"))
+    }
+}
+
+/// Addresses of a deployment, kept as JSON next to the operator's config
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub network_passphrase: String,
+    /// Account the contracts were initialized with as admin
+    pub admin: String,
+    /// `C...` address by contract name
+    pub contracts: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Read `path`, or start an empty manifest if it doesn't exist
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CliError> {
+        match fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CliError> {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)? + "\n")?)
+    }
+
+    pub fn address(&self, contract: Contract) -> Result<&str, CliError> {
+        self.contracts
+            .get(contract.name())
+            .map(String::as_str)
+            .ok_or(CliError::NotDeployed(contract.name()))
+    }
+
+    pub fn set_address(&mut self, contract: Contract, address: String) {
+        self.contracts.insert(contract.name().to_string(), address);
+    }
+}
@@ -0,0 +1,362 @@
+use std::path::PathBuf;
+
+use cross_chain_bridge::CrossChainBridge;
+use escrow::EscrowContract;
+use governance::GovernanceContract;
+use multi_party_payment::MultiPartyPayment;
+use novafund_client::MockBackend;
+use profit_distribution::ProfitDistribution;
+use project_launch::ProjectLaunch;
+use reputation::ReputationContract;
+use shared::types::ValidatorRegistryConfig;
+use soroban_sdk::{
+    testutils::Ledger as _,
+    xdr::{ReadXdr, TransactionEnvelope},
+    Bytes,
+};
+use subscription_pool::SubscriptionPool;
+
+use super::*;
+
+const DAY: u64 = 86_400;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("novafund-cli-{}-{name}", std::process::id()))
+}
+
+fn wasm(contract: Contract) -> std::vec::Vec<u8> {
+    format!("wasm of {}", contract.name()).into_bytes()
+}
+
+/// A mock node that runs each contract natively, and a directory with
+/// stand-in wasm files for them
+fn mock(name: &str) -> (MockBackend, PathBuf) {
+    let mut backend = MockBackend::new();
+    let dir = temp_path(name);
+    fs::create_dir_all(&dir).unwrap();
+    for contract in Contract::ALL {
+        fs::write(dir.join(contract.wasm_file()), wasm(contract)).unwrap();
+        backend.register_native(&wasm(contract), move |env, address| {
+            match contract {
+                Contract::ProjectLaunch => env.register_contract(Some(address), ProjectLaunch),
+                Contract::Escrow => env.register_contract(Some(address), EscrowContract),
+                Contract::ProfitDistribution => {
+                    env.register_contract(Some(address), ProfitDistribution)
+                }
+                Contract::SubscriptionPool => {
+                    env.register_contract(Some(address), SubscriptionPool)
+                }
+                Contract::MultiPartyPayment => {
+                    env.register_contract(Some(address), MultiPartyPayment)
+                }
+                Contract::Reputation => env.register_contract(Some(address), ReputationContract),
+                Contract::Governance => env.register_contract(Some(address), GovernanceContract),
+                Contract::CrossChainBridge => {
+                    env.register_contract(Some(address), CrossChainBridge)
+                }
+            };
+        });
+    }
+    (backend, dir)
+}
+
+fn operator(backend: &MockBackend) -> Operator<&MockBackend> {
+    Operator::new(
+        NovaFundClient::new(backend),
+        Mode::Send(Signer::from_seed([1; 32])),
+        Manifest::default(),
+    )
+}
+
+fn account(seed: u8) -> String {
+    Signer::from_seed([seed; 32]).account_id()
+}
+
+/// Launch a project through project-launch, created by the operator
+fn launch_project<B: Backend>(operator: &Operator<B>) -> u64 {
+    let client = operator.client();
+    let source = operator.source();
+    let admin = client.address(&source).unwrap();
+    let launch = client
+        .project_launch(
+            operator
+                .manifest()
+                .address(Contract::ProjectLaunch)
+                .unwrap(),
+        )
+        .unwrap();
+    let token = client
+        .address(
+            operator
+                .manifest()
+                .address(Contract::ProfitDistribution)
+                .unwrap(),
+        )
+        .unwrap();
+    let signer = Signer::from_seed([1; 32]);
+    launch
+        .create_project(
+            &admin,
+            &10_000_000_000,
+            &(30 * DAY),
+            &token,
+            &Bytes::from_slice(client.env(), b"ipfs://project"),
+        )
+        .send(&signer)
+        .unwrap()
+}
+
+#[test]
+fn test_deploy_initializes_every_contract() {
+    let (backend, dir) = mock("deploy");
+    let mut operator = operator(&backend);
+    let deployed = operator.deploy(&dir, &DeployOptions::default()).unwrap();
+
+    let manifest = operator.manifest();
+    assert_eq!(deployed.len(), Contract::ALL.len());
+    for (contract, address) in &deployed {
+        assert_eq!(manifest.address(*contract).unwrap(), address);
+    }
+    assert_eq!(manifest.admin, account(1));
+    assert_eq!(manifest.network_passphrase, backend.network_passphrase());
+
+    let client = operator.client();
+    let source = operator.source();
+    assert!(client
+        .project_launch(manifest.address(Contract::ProjectLaunch).unwrap())
+        .unwrap()
+        .is_initialized()
+        .simulate(&source)
+        .unwrap());
+    let config = operator.get_config().unwrap();
+    assert_eq!(config.admin.to_string().to_string(), account(1));
+    assert_eq!(config.confirmation_threshold, 1);
+
+    // Deploying again from the same account needs another salt
+    assert!(operator.deploy(&dir, &DeployOptions::default()).is_err());
+    let options = DeployOptions {
+        salt: "second".to_string(),
+        ..Default::default()
+    };
+    let again = operator.deploy(&dir, &options).unwrap();
+    assert_ne!(again[0].1, deployed[0].1);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_wire_links_project_to_escrow_and_profits() {
+    let (backend, dir) = mock("wire");
+    backend.env().ledger().set_timestamp(DAY);
+    let mut operator = operator(&backend);
+    operator.deploy(&dir, &DeployOptions::default()).unwrap();
+    let project_id = launch_project(&operator);
+
+    let validators: std::vec::Vec<String> = (10..13).map(account).collect();
+    operator.wire(project_id, &validators).unwrap();
+
+    let project = operator.get_project(project_id).unwrap();
+    let escrow = operator.get_escrow(project_id).unwrap();
+    assert_eq!(escrow.creator, project.creator);
+    assert_eq!(escrow.token, project.token);
+    assert_eq!(escrow.validators.len(), 3);
+    assert_eq!(
+        backend.submitted().len(),
+        // Uploads, creations, initializations, linking the escrow to
+        // project-launch, the project and the wiring
+        8 + 8 + 7 + 1 + 1 + 2
+    );
+
+    // Wiring twice fails on the escrow
+    assert!(operator.wire(project_id, &validators).is_err());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_admin_actions() {
+    let (backend, dir) = mock("admin");
+    backend.env().ledger().set_timestamp(DAY);
+    let mut operator = operator(&backend);
+    operator.deploy(&dir, &DeployOptions::default()).unwrap();
+
+    operator.pause().unwrap();
+    assert!(operator.get_config().unwrap().paused);
+    operator.unpause().unwrap();
+    assert!(!operator.get_config().unwrap().paused);
+
+    operator
+        .set_fees(&FeeUpdate {
+            max_gas_price: Some(42),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(operator.get_config().unwrap().max_gas_price, 42);
+    assert!(matches!(
+        operator.set_fees(&FeeUpdate::default()),
+        Err(CliError::Usage(_))
+    ));
+
+    let project_id = launch_project(&operator);
+    let validators: std::vec::Vec<String> = (10..13).map(account).collect();
+    operator.wire(project_id, &validators).unwrap();
+    let replacements: std::vec::Vec<String> = (20..24).map(account).collect();
+    operator
+        .update_validators(project_id, &replacements)
+        .unwrap();
+    assert_eq!(operator.get_escrow(project_id).unwrap().validators.len(), 4);
+
+    // Validator fees need a registry to change
+    let fees = FeeUpdate {
+        validator_fee_share: Some(250),
+        ..Default::default()
+    };
+    assert!(operator.set_fees(&fees).is_err());
+    let client = operator.client();
+    let escrow = client
+        .escrow(operator.manifest().address(Contract::Escrow).unwrap())
+        .unwrap();
+    let treasury = client.address(&account(1)).unwrap();
+    escrow
+        .configure_validator_registry(&ValidatorRegistryConfig {
+            stake_token: treasury.clone(),
+            min_bond: 1_000,
+            unbonding_period: DAY,
+            fee_share: 100,
+            slash_rate: 1_000,
+            treasury,
+        })
+        .send(&Signer::from_seed([1; 32]))
+        .unwrap();
+    operator.set_fees(&fees).unwrap();
+    let registry = escrow
+        .get_validator_registry()
+        .simulate(&operator.source())
+        .unwrap();
+    assert_eq!(registry.fee_share, 250);
+    assert_eq!(registry.slash_rate, 1_000);
+
+    operator
+        .add_chain(ChainId::Base, "Base", [7; 32], 12, 50_000)
+        .unwrap();
+    let bridge = operator
+        .client()
+        .cross_chain_bridge(
+            operator
+                .manifest()
+                .address(Contract::CrossChainBridge)
+                .unwrap(),
+        )
+        .unwrap();
+    assert!(bridge
+        .is_chain_supported(&ChainId::Base)
+        .simulate(&operator.source())
+        .unwrap());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_dry_run_builds_unsigned_transactions() {
+    let (backend, dir) = mock("dry-run");
+    let mut deployer = operator(&backend);
+    deployer.deploy(&dir, &DeployOptions::default()).unwrap();
+    let sent = backend.submitted().len();
+
+    let source = account(1);
+    let dry = Operator::new(
+        NovaFundClient::new(&backend),
+        Mode::DryRun {
+            source: source.clone(),
+        },
+        deployer.manifest().clone(),
+    );
+    dry.pause().unwrap();
+    dry.set_fees(&FeeUpdate {
+        max_gas_price: Some(7),
+        ..Default::default()
+    })
+    .unwrap();
+
+    // Nothing sent, two transactions from consecutive sequence numbers
+    assert_eq!(backend.submitted().len(), sent);
+    assert!(!dry.get_config().unwrap().paused);
+    let unsigned = dry.unsigned();
+    assert_eq!(unsigned.len(), 2);
+    assert_eq!(unsigned[0].action, "pause bridge");
+    let sequence = backend.sequence(&source).unwrap();
+    for (i, tx) in unsigned.iter().enumerate() {
+        let TransactionEnvelope::Tx(envelope) =
+            TransactionEnvelope::from_xdr_base64(&tx.xdr, Limits::none()).unwrap()
+        else {
+            panic!("expected a v1 transaction");
+        };
+        assert!(envelope.signatures.is_empty());
+        assert_eq!(envelope.tx.seq_num.0, sequence + 1 + i as i64);
+    }
+
+    // Signed in order, they apply
+    let signer = Signer::from_seed([1; 32]);
+    for tx in &unsigned {
+        let mut tx = TransactionEnvelope::from_xdr_base64(&tx.xdr, Limits::none()).unwrap();
+        dry.client().sign(&mut tx, &signer).unwrap();
+        backend.submit(&tx).unwrap();
+    }
+    let config = dry.get_config().unwrap();
+    assert!(config.paused);
+    assert_eq!(config.max_gas_price, 7);
+
+    // A dry deploy only uploads, but reports where the contracts will be
+    let mut dry = Operator::new(
+        NovaFundClient::new(&backend),
+        Mode::DryRun { source },
+        Manifest::default(),
+    );
+    let options = DeployOptions {
+        salt: "next".to_string(),
+        ..Default::default()
+    };
+    let planned = dry.deploy(&dir, &options).unwrap();
+    assert_eq!(dry.unsigned().len(), Contract::ALL.len());
+    assert!(dry.manifest().contracts.is_empty());
+    let deployed = deployer.deploy(&dir, &options).unwrap();
+    assert_eq!(planned, deployed);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_manifest_round_trip() {
+    let path = temp_path("manifest.json");
+    assert_eq!(Manifest::load(&path).unwrap(), Manifest::default());
+
+    let mut manifest = Manifest {
+        network_passphrase: "Test SDF Network ; September 2015".to_string(),
+        admin: account(1),
+        ..Default::default()
+    };
+    manifest.set_address(Contract::Escrow, "CESCROW".to_string());
+    manifest.save(&path).unwrap();
+    let loaded = Manifest::load(&path).unwrap();
+    assert_eq!(loaded, manifest);
+    assert_eq!(loaded.address(Contract::Escrow).unwrap(), "CESCROW");
+    assert!(matches!(
+        loaded.address(Contract::Governance),
+        Err(CliError::NotDeployed("governance"))
+    ));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_parse_arguments() {
+    assert_eq!(parse_chain("ethereum").unwrap(), ChainId::Ethereum);
+    assert_eq!(parse_chain("8453").unwrap(), ChainId::Base);
+    assert_eq!(parse_chain("BSC").unwrap(), ChainId::BinanceSmartChain);
+    assert!(parse_chain("solana").is_err());
+
+    let hex = "ab".repeat(32);
+    assert_eq!(parse_bytes32(&hex).unwrap(), [0xab; 32]);
+    assert_eq!(parse_bytes32(&format!("0x{hex}")).unwrap(), [0xab; 32]);
+    assert!(parse_bytes32("abcd").is_err());
+    assert!(parse_bytes32(&"zz".repeat(32)).is_err());
+    assert_eq!(
+        Contract::ProfitDistribution.wasm_file(),
+        "profit_distribution.wasm"
+    );
+}
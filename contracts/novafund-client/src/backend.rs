@@ -0,0 +1,280 @@
+//! Where transactions are simulated and sent
+
+use std::{thread, time::Duration};
+
+use serde_json::{json, Value};
+use soroban_sdk::xdr::{
+    LedgerEntryData, LedgerKey, LedgerKeyAccount, Limits, PublicKey, ReadXdr, ScVal,
+    SorobanAuthorizationEntry, SorobanTransactionData, TransactionEnvelope, TransactionMeta,
+    Uint256, WriteXdr,
+};
+
+use crate::{tx::transaction_hash, ClientError};
+
+/// What simulating an invocation reports
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulation {
+    /// Return value of the contract function
+    pub result: ScVal,
+    /// Authorizations the invocation requires
+    pub auth: Vec<SorobanAuthorizationEntry>,
+    /// Footprint and resources, when the backend meters them
+    pub transaction_data: Option<SorobanTransactionData>,
+    pub min_resource_fee: u64,
+}
+
+/// A transaction included in a ledger
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    /// Hex transaction hash
+    pub hash: String,
+    pub result: ScVal,
+}
+
+pub trait Backend {
+    fn network_passphrase(&self) -> String;
+
+    /// Current sequence number of `account`
+    fn sequence(&self, account: &str) -> Result<i64, ClientError>;
+
+    fn simulate(&self, tx: &TransactionEnvelope) -> Result<Simulation, ClientError>;
+
+    /// Send a signed transaction and wait until it is in a ledger
+    fn submit(&self, tx: &TransactionEnvelope) -> Result<Submission, ClientError>;
+}
+
+impl<B: Backend + ?Sized> Backend for Box<B> {
+    fn network_passphrase(&self) -> String {
+        (**self).network_passphrase()
+    }
+
+    fn sequence(&self, account: &str) -> Result<i64, ClientError> {
+        (**self).sequence(account)
+    }
+
+    fn simulate(&self, tx: &TransactionEnvelope) -> Result<Simulation, ClientError> {
+        (**self).simulate(tx)
+    }
+
+    fn submit(&self, tx: &TransactionEnvelope) -> Result<Submission, ClientError> {
+        (**self).submit(tx)
+    }
+}
+
+impl<B: Backend + ?Sized> Backend for &B {
+    fn network_passphrase(&self) -> String {
+        (**self).network_passphrase()
+    }
+
+    fn sequence(&self, account: &str) -> Result<i64, ClientError> {
+        (**self).sequence(account)
+    }
+
+    fn simulate(&self, tx: &TransactionEnvelope) -> Result<Simulation, ClientError> {
+        (**self).simulate(tx)
+    }
+
+    fn submit(&self, tx: &TransactionEnvelope) -> Result<Submission, ClientError> {
+        (**self).submit(tx)
+    }
+}
+
+/// RPC endpoint and the passphrase of the network it serves
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Network {
+    pub rpc_url: String,
+    pub passphrase: String,
+}
+
+impl Network {
+    pub fn new(rpc_url: impl Into<String>, passphrase: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            passphrase: passphrase.into(),
+        }
+    }
+
+    pub fn testnet() -> Self {
+        Self::new(
+            "https://soroban-testnet.stellar.org",
+            "Test SDF Network ; September 2015",
+        )
+    }
+
+    pub fn futurenet() -> Self {
+        Self::new(
+            "https://rpc-futurenet.stellar.org",
+            "Test SDF Future Network ; October 2022",
+        )
+    }
+
+    /// A local quickstart container
+    pub fn standalone() -> Self {
+        Self::new(
+            "http://localhost:8000/soroban/rpc",
+            "Standalone Network ; February 2017",
+        )
+    }
+}
+
+/// A Soroban RPC node
+pub struct RpcBackend {
+    network: Network,
+    agent: ureq::Agent,
+    poll_interval: Duration,
+    poll_attempts: u32,
+}
+
+impl RpcBackend {
+    pub fn new(network: Network) -> Self {
+        Self {
+            network,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+            poll_interval: Duration::from_secs(1),
+            poll_attempts: 30,
+        }
+    }
+
+    /// How often, and how many times, to ask whether a sent transaction
+    /// made it into a ledger
+    pub fn with_polling(mut self, interval: Duration, attempts: u32) -> Self {
+        self.poll_interval = interval;
+        self.poll_attempts = attempts;
+        self
+    }
+
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+
+    fn request(&self, method: &str, params: Value) -> Result<Value, ClientError> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self
+            .agent
+            .post(&self.network.rpc_url)
+            .send_json(body)
+            .map_err(|err| ClientError::Http(err.to_string()))?
+            .into_json()
+            .map_err(|err| ClientError::Http(err.to_string()))?;
+        if let Some(error) = response.get("error") {
+            return Err(ClientError::Rpc {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+        Ok(response["result"].clone())
+    }
+}
+
+impl Backend for RpcBackend {
+    fn network_passphrase(&self) -> String {
+        self.network.passphrase.clone()
+    }
+
+    fn sequence(&self, account: &str) -> Result<i64, ClientError> {
+        let key = stellar_strkey::ed25519::PublicKey::from_string(account)
+            .map_err(|_| ClientError::InvalidKey(account.to_string()))?;
+        let key = LedgerKey::Account(LedgerKeyAccount {
+            account_id: PublicKey::PublicKeyTypeEd25519(Uint256(key.0)).into(),
+        });
+        let result = self.request(
+            "getLedgerEntries",
+            json!({ "keys": [key.to_xdr_base64(Limits::none())?] }),
+        )?;
+        let entry = result["entries"][0]["xdr"]
+            .as_str()
+            .ok_or_else(|| ClientError::InvalidKey(format!("{account} (account not found)")))?;
+        match LedgerEntryData::from_xdr_base64(entry, Limits::none())? {
+            LedgerEntryData::Account(account) => Ok(account.seq_num.0),
+            _ => Err(ClientError::Conversion("account ledger entry".to_string())),
+        }
+    }
+
+    fn simulate(&self, tx: &TransactionEnvelope) -> Result<Simulation, ClientError> {
+        let result = self.request(
+            "simulateTransaction",
+            json!({ "transaction": tx.to_xdr_base64(Limits::none())? }),
+        )?;
+        if let Some(error) = result["error"].as_str() {
+            return Err(ClientError::Simulation(error.to_string()));
+        }
+        let invocation = &result["results"][0];
+        Ok(Simulation {
+            result: ScVal::from_xdr_base64(
+                invocation["xdr"].as_str().unwrap_or_default(),
+                Limits::none(),
+            )?,
+            auth: invocation["auth"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(|entry| SorobanAuthorizationEntry::from_xdr_base64(entry, Limits::none()))
+                .collect::<Result<_, _>>()?,
+            transaction_data: result["transactionData"]
+                .as_str()
+                .map(|data| SorobanTransactionData::from_xdr_base64(data, Limits::none()))
+                .transpose()?,
+            min_resource_fee: result["minResourceFee"]
+                .as_str()
+                .and_then(|fee| fee.parse().ok())
+                .unwrap_or_default(),
+        })
+    }
+
+    fn submit(&self, tx: &TransactionEnvelope) -> Result<Submission, ClientError> {
+        let TransactionEnvelope::Tx(envelope) = tx else {
+            return Err(ClientError::Conversion(
+                "only v1 transactions can be sent".to_string(),
+            ));
+        };
+        let hash = hex(&transaction_hash(&envelope.tx, &self.network.passphrase)?);
+        let sent = self.request(
+            "sendTransaction",
+            json!({ "transaction": tx.to_xdr_base64(Limits::none())? }),
+        )?;
+        let status = sent["status"].as_str().unwrap_or_default();
+        if status != "PENDING" && status != "DUPLICATE" {
+            return Err(ClientError::Transaction {
+                hash,
+                status: status.to_string(),
+            });
+        }
+
+        for _ in 0..self.poll_attempts {
+            let result = self.request("getTransaction", json!({ "hash": hash }))?;
+            match result["status"].as_str().unwrap_or_default() {
+                "NOT_FOUND" => thread::sleep(self.poll_interval),
+                "SUCCESS" => {
+                    let meta = TransactionMeta::from_xdr_base64(
+                        result["resultMetaXdr"].as_str().unwrap_or_default(),
+                        Limits::none(),
+                    )?;
+                    let result = match meta {
+                        TransactionMeta::V3(meta) => {
+                            meta.soroban_meta.map(|meta| meta.return_value)
+                        }
+                        _ => None,
+                    };
+                    return Ok(Submission {
+                        hash,
+                        result: result.unwrap_or(ScVal::Void),
+                    });
+                }
+                status => {
+                    return Err(ClientError::Transaction {
+                        hash,
+                        status: status.to_string(),
+                    })
+                }
+            }
+        }
+        Err(ClientError::Timeout(hash))
+    }
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
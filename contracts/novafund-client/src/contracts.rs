@@ -0,0 +1,283 @@
+//! Typed wrappers, one per contract, mirroring the generated clients
+
+use multi_party_payment::Party;
+use profit_distribution::{
+    CompoundTarget, DistributionRound, InvestorShare, RewardPosition, UnallocatedPolicy,
+    VestingStream, Waterfall, WaterfallPosition, WaterfallProgress,
+};
+use reputation::{BadgeType, ReputationProfile};
+use shared::types::*;
+use soroban_sdk::{xdr::ScAddress, Address, Bytes, BytesN, Map, String, Vec};
+use subscription_pool::{Pool, Subscription, SubscriptionPeriod};
+
+use crate::{parse_address, Backend, Call, ClientError, NovaFundClient};
+
+macro_rules! contract_client {
+    (
+        $(#[$doc:meta])*
+        $name:ident {
+            $($func:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty;)*
+        }
+    ) => {
+        $(#[$doc])*
+        pub struct $name<'a, B> {
+            client: &'a NovaFundClient<B>,
+            contract: ScAddress,
+        }
+
+        impl<'a, B: Backend> $name<'a, B> {
+            /// Wrap the contract deployed at the `C...` strkey `contract`
+            pub fn new(client: &'a NovaFundClient<B>, contract: &str) -> Result<Self, ClientError> {
+                match parse_address(contract)? {
+                    contract @ ScAddress::Contract(_) => Ok(Self { client, contract }),
+                    _ => Err(ClientError::InvalidKey(contract.to_string())),
+                }
+            }
+
+            pub fn contract(&self) -> &ScAddress {
+                &self.contract
+            }
+
+            $(
+                pub fn $func(&self, $($arg: &$ty),*) -> Call<'a, B, $ret> {
+                    Call::invoke(
+                        self.client,
+                        &self.contract,
+                        stringify!($func),
+                        vec![$(self.client.encode($arg)),*],
+                    )
+                }
+            )*
+        }
+    };
+}
+
+contract_client! {
+    /// The ProjectLaunch contract
+    ProjectLaunch {
+        initialize(admin: Address) -> ();
+        create_project(creator: Address, funding_goal: i128, deadline: u64, token: Address, metadata_hash: Bytes) -> u64;
+        contribute(project_id: u64, contributor: Address, amount: i128) -> ();
+        contribute_for(project_id: u64, payer: Address, contributor: Address, amount: i128) -> ();
+        get_project(project_id: u64) -> Project;
+        get_user_contribution(project_id: u64, contributor: Address) -> i128;
+        get_next_project_id() -> u64;
+        is_initialized() -> bool;
+        get_admin() -> Option<Address>;
+        mark_project_failed(project_id: u64) -> ();
+        refund_contributor(project_id: u64, contributor: Address) -> i128;
+        is_refunded(project_id: u64, contributor: Address) -> bool;
+        is_failure_processed(project_id: u64) -> bool;
+    }
+}
+
+contract_client! {
+    /// The milestone escrow contract
+    Escrow {
+        initialize_admin(admin: Address) -> ();
+        initialize(project_id: u64, creator: Address, token: Address, validators: Vec<Address>) -> ();
+        initialize_backer_voting(project_id: u64, creator: Address, token: Address, config: BackerVotingConfig) -> ();
+        set_project_launch(project_launch: Address) -> ();
+        get_project_launch() -> Address;
+        deposit(project_id: u64, amount: Amount) -> ();
+        deposit_token(project_id: u64, token: Address, amount: Amount) -> ();
+        create_milestone(project_id: u64, description_hash: Hash, amount: Amount, dependencies: Vec<u64>) -> ();
+        create_basket_milestone(project_id: u64, description_hash: Hash, amounts: Vec<TokenAmount>, dependencies: Vec<u64>) -> ();
+        submit_milestone(project_id: u64, milestone_id: u64, proof_hash: Hash) -> ();
+        vote_milestone(project_id: u64, milestone_id: u64, voter: Address, approve: bool) -> ();
+        vote_milestone_partial(project_id: u64, milestone_id: u64, voter: Address, share: BasisPoints) -> ();
+        finalize_milestone(project_id: u64, milestone_id: u64) -> MilestoneStatus;
+        get_escrow(project_id: u64) -> EscrowInfo;
+        get_milestone(project_id: u64, milestone_id: u64) -> Milestone;
+        list_milestones(project_id: u64, cursor: u64, limit: u32, status: Option<MilestoneStatus>) -> MilestonePage;
+        get_votes(project_id: u64, milestone_id: u64) -> Vec<ValidatorVote>;
+        get_escrow_summary(project_id: u64) -> EscrowSummary;
+        get_roadmap(project_id: u64) -> Roadmap;
+        get_backer_voting_config(project_id: u64) -> BackerVotingConfig;
+        get_total_milestone_amount(project_id: u64) -> Amount;
+        get_available_balance(project_id: u64) -> Amount;
+        get_token_balances(project_id: u64) -> Vec<TokenBalance>;
+        update_validators(project_id: u64, new_validators: Vec<Address>) -> ();
+        propose_validator_change(project_id: u64, new_validators: Vec<Address>) -> ();
+        approve_validator_change(project_id: u64, approver: Address) -> ();
+        get_validator_change(project_id: u64) -> ValidatorChangeProposal;
+        get_milestone_validators(project_id: u64, milestone_id: u64) -> Vec<Address>;
+        configure_validator_registry(config: ValidatorRegistryConfig) -> ();
+        get_validator_registry() -> ValidatorRegistryConfig;
+        bond_validator(validator: Address, amount: Amount) -> ();
+        unbond_validator(validator: Address, amount: Amount) -> ();
+        withdraw_unbonded(validator: Address) -> Amount;
+        get_validator_bond(validator: Address) -> ValidatorBond;
+        claim_validator_fees(validator: Address, token: Address) -> Amount;
+        get_validator_fees(validator: Address, token: Address) -> Amount;
+        configure_disputes(config: DisputeConfig) -> ();
+        get_dispute_config() -> DisputeConfig;
+        set_milestone_tranches(project_id: u64, milestone_id: u64, tranches: Vec<Tranche>) -> ();
+        get_milestone_tranches(project_id: u64, milestone_id: u64) -> Vec<Tranche>;
+        release_milestone(project_id: u64, milestone_id: u64) -> Amount;
+        open_dispute(project_id: u64, milestone_id: u64, challenger: Address, evidence_hash: Hash) -> ();
+        vote_dispute(project_id: u64, milestone_id: u64, arbitrator: Address, uphold: bool) -> ();
+        resolve_dispute(project_id: u64, milestone_id: u64, upheld: bool) -> ();
+        get_dispute(project_id: u64, milestone_id: u64) -> Dispute;
+        set_vault_whitelisted(vault: Address, whitelisted: bool) -> ();
+        is_vault_whitelisted(vault: Address) -> bool;
+        configure_yield(project_id: u64, config: YieldConfig) -> ();
+        get_yield_config(project_id: u64) -> YieldConfig;
+        get_yield_position(project_id: u64) -> YieldPosition;
+        invest_idle(project_id: u64) -> Amount;
+        harvest_yield(project_id: u64) -> Amount;
+        divest(project_id: u64) -> Amount;
+    }
+}
+
+contract_client! {
+    /// The profit distribution contract
+    ProfitDistribution {
+        initialize(admin: Address) -> ();
+        set_token(project_id: u64, token: Address) -> ();
+        add_reward_token(project_id: u64, token: Address) -> ();
+        get_reward_tokens(project_id: u64) -> Vec<Address>;
+        register_investors(project_id: u64, investors: Map<Address, u32>) -> ();
+        remove_investors(project_id: u64, investors: Vec<Address>) -> ();
+        register_investors_from_launch(project_id: u64, project_launch: Address, backers: Vec<Address>) -> Map<Address, u32>;
+        deposit_profits(project_id: u64, depositor: Address, amount: i128) -> ();
+        deposit_token_profits(project_id: u64, token: Address, depositor: Address, amount: i128) -> ();
+        deposit_vested_profits(project_id: u64, token: Address, depositor: Address, amount: i128, duration: u64) -> ();
+        get_vesting_streams(project_id: u64, token: Address) -> Vec<VestingStream>;
+        claim_dividends(project_id: u64, investor: Address) -> i128;
+        claim_all(project_id: u64, investor: Address) -> Map<Address, i128>;
+        get_investor_share(project_id: u64, investor: Address) -> InvestorShare;
+        get_reward_position(project_id: u64, investor: Address, token: Address) -> RewardPosition;
+        get_pending_rewards(project_id: u64, investor: Address) -> Map<Address, i128>;
+        transfer_shares(project_id: u64, from: Address, to: Address, shares: u32) -> ();
+        approve_shares(project_id: u64, owner: Address, spender: Address, shares: u32, expiration_ledger: u32) -> ();
+        get_share_allowance(project_id: u64, owner: Address, spender: Address) -> u32;
+        transfer_shares_from(project_id: u64, spender: Address, from: Address, to: Address, shares: u32) -> ();
+        set_share_token(project_id: u64, token: Address) -> ();
+        get_share_token(project_id: u64) -> Option<Address>;
+        share_balance(project_id: u64, holder: Address) -> u32;
+        set_treasury(treasury: Address) -> ();
+        get_treasury() -> Option<Address>;
+        set_unallocated_policy(project_id: u64, policy: UnallocatedPolicy) -> ();
+        get_unallocated_policy(project_id: u64) -> UnallocatedPolicy;
+        get_dust(project_id: u64, token: Address) -> i128;
+        sweep_dust(project_id: u64, token: Address) -> i128;
+        set_keeper_fee(fee_bps: u32) -> ();
+        get_keeper_fee() -> u32;
+        set_compounding(project_id: u64, investor: Address, target: Option<CompoundTarget>) -> ();
+        get_compounding(project_id: u64, investor: Address) -> Option<CompoundTarget>;
+        compound(project_id: u64, investor: Address, keeper: Address) -> i128;
+        deposit_round(project_id: u64, token: Address, depositor: Address, amount: i128, claim_period: u64) -> u64;
+        claim_round(project_id: u64, holder: Address, round_id: u64) -> i128;
+        close_round(project_id: u64, round_id: u64) -> i128;
+        get_round(project_id: u64, round_id: u64) -> DistributionRound;
+        get_open_rounds(project_id: u64) -> Vec<u64>;
+        get_claimable_rounds(project_id: u64, holder: Address) -> Map<u64, i128>;
+        get_round_shares(project_id: u64, round_id: u64, holder: Address) -> u32;
+        set_waterfall(project_id: u64, waterfall: Waterfall) -> ();
+        get_waterfall(project_id: u64) -> Option<Waterfall>;
+        get_waterfall_progress(project_id: u64) -> WaterfallProgress;
+        get_waterfall_position(project_id: u64, investor: Address) -> WaterfallPosition;
+        get_admin() -> Option<Address>;
+    }
+}
+
+contract_client! {
+    /// A project's share token, a SEP-41 token over its profit shares
+    ShareToken {
+        initialize(ledger: Address, project_id: u64, name: String, symbol: String) -> ();
+        ledger() -> Address;
+        project_id() -> u64;
+        balance(id: Address) -> i128;
+        allowance(from: Address, spender: Address) -> i128;
+        approve(from: Address, spender: Address, amount: i128, expiration_ledger: u32) -> ();
+        transfer(from: Address, to: Address, amount: i128) -> ();
+        transfer_from(spender: Address, from: Address, to: Address, amount: i128) -> ();
+        burn(from: Address, amount: i128) -> ();
+        burn_from(spender: Address, from: Address, amount: i128) -> ();
+        decimals() -> u32;
+        name() -> String;
+        symbol() -> String;
+    }
+}
+
+contract_client! {
+    /// The subscription pool contract
+    SubscriptionPool {
+        initialize(admin: Address) -> ();
+        create_pool(name: String, token: Address) -> u64;
+        subscribe(pool_id: u64, subscriber: Address, amount: i128, period: SubscriptionPeriod) -> ();
+        process_deposits(pool_id: u64) -> ();
+        cancel_subscription(pool_id: u64, subscriber: Address) -> ();
+        modify_subscription(pool_id: u64, subscriber: Address, new_amount: i128, new_period: SubscriptionPeriod) -> ();
+        pause_subscription(pool_id: u64, subscriber: Address) -> ();
+        resume_subscription(pool_id: u64, subscriber: Address) -> ();
+        withdraw(pool_id: u64, subscriber: Address, amount: i128) -> ();
+        deposit_for(pool_id: u64, payer: Address, subscriber: Address, amount: i128) -> ();
+        get_pool(pool_id: u64) -> Pool;
+        get_pool_token(pool_id: u64) -> Address;
+        get_deposited(pool_id: u64, subscriber: Address) -> i128;
+        get_subscription(pool_id: u64, subscriber: Address) -> Subscription;
+    }
+}
+
+contract_client! {
+    /// The multi-party payment contract
+    MultiPartyPayment {
+        setup_parties(project_id: u64, parties: Vec<Party>) -> ();
+        receive_payment(project_id: u64, amount: i128) -> ();
+        withdraw_share(project_id: u64, party_index: u32, party: Address) -> i128;
+        get_party(project_id: u64, party_index: u32) -> Party;
+    }
+}
+
+contract_client! {
+    /// The reputation contract
+    Reputation {
+        initialize(admin: Address) -> ();
+        register_user(user: Address) -> ReputationProfile;
+        update_score(user: Address, delta: i128) -> i128;
+        award_badge(user: Address, badge: BadgeType) -> ();
+        get_profile(user: Address) -> ReputationProfile;
+        get_admin() -> Address;
+    }
+}
+
+contract_client! {
+    /// The governance contract
+    Governance {
+        initialize(admin: Address, total_voters: u32) -> ();
+        create_proposal(creator: Address, payload_ref: Bytes, start_time: u64, end_time: u64) -> u64;
+        vote(proposal_id: u64, voter: Address, support: bool) -> ();
+        finalize(proposal_id: u64) -> ();
+        get_proposal(proposal_id: u64) -> Proposal;
+        has_voted(proposal_id: u64, voter: Address) -> bool;
+        get_total_voters() -> u32;
+    }
+}
+
+contract_client! {
+    /// The cross-chain bridge contract
+    CrossChainBridge {
+        initialize(admin: Address, min_relayer_stake: Amount, confirmation_threshold: u32) -> ();
+        add_supported_chain(chain_id: ChainId, name: String, bridge_contract: BytesN<32>, confirmations_required: u32, gas_cost_estimate: u64) -> ();
+        remove_supported_chain(chain_id: ChainId) -> ();
+        register_wrapped_asset(asset_code: String, issuer: Address, original_chain: ChainId, original_contract: BytesN<32>, decimals: u32) -> Address;
+        deposit(source_chain: ChainId, source_tx_hash: BytesN<32>, sender: BytesN<32>, recipient: Address, asset: Address, amount: Amount) -> u64;
+        withdraw(sender: Address, destination_chain: ChainId, recipient: BytesN<32>, asset: Address, amount: Amount) -> u64;
+        confirm_withdrawal(relayer: Address, tx_id: u64, destination_tx_hash: BytesN<32>) -> ();
+        register_relayer(relayer: Address, stake: Amount) -> ();
+        unregister_relayer(relayer: Address) -> ();
+        pause_bridge() -> ();
+        unpause_bridge() -> ();
+        update_config(min_relayer_stake: Option<Amount>, confirmation_threshold: Option<u32>, max_gas_price: Option<u64>) -> ();
+        get_config() -> BridgeConfig;
+        get_chain_config(chain_id: ChainId) -> ChainConfig;
+        get_wrapped_asset(asset: Address) -> WrappedAssetInfo;
+        get_transaction(tx_id: u64) -> BridgeTransaction;
+        get_relayer(address: Address) -> RelayerInfo;
+        is_chain_supported(chain_id: ChainId) -> bool;
+        get_total_wrapped(asset: Address) -> Amount;
+        get_transaction_count() -> u64;
+    }
+}
@@ -0,0 +1,395 @@
+//! Off-chain client for the NovaFund contracts
+//!
+//! [`NovaFundClient`] hands out typed wrappers, one per contract, that
+//! mirror the contracts' generated clients (`ProjectLaunchClient`,
+//! `EscrowContractClient`, ...): the same methods with the same argument
+//! types. Instead of invoking an in-process host they produce a [`Call`],
+//! which can be simulated for its result, built into an unsigned
+//! transaction, or signed and sent.
+//!
+//! Transactions go through a [`Backend`]: [`RpcBackend`] talks JSON-RPC to a
+//! Soroban RPC node, [`MockBackend`] runs them against contracts registered
+//! in a local test `Env`, so everything works offline.
+use std::{fmt, marker::PhantomData};
+
+use soroban_sdk::{
+    xdr::{
+        ContractExecutable, ContractIdPreimage, ContractIdPreimageFromAddress, CreateContractArgs,
+        Hash, HostFunction, ScAddress, ScSymbol, ScVal, TransactionEnvelope, Uint256,
+    },
+    Address, BytesN, Env, IntoVal, TryFromVal, Val,
+};
+
+mod backend;
+mod contracts;
+mod mock;
+mod tx;
+
+pub use backend::{Backend, Network, RpcBackend, Simulation, Submission};
+pub use contracts::*;
+pub use mock::{MockBackend, MOCK_NETWORK_PASSPHRASE};
+pub use tx::{transaction_hash, Invocation, Signer};
+
+/// Inclusion fee offered per transaction, in stroops
+pub const DEFAULT_BASE_FEE: u32 = 100;
+
+/// Seconds a built transaction stays valid
+pub const DEFAULT_TIMEOUT: u64 = 300;
+
+#[derive(Debug)]
+pub enum ClientError {
+    Xdr(stellar_xdr::curr::Error),
+    /// A value that doesn't convert to or from the contract's types
+    Conversion(String),
+    /// A malformed account, contract or secret key
+    InvalidKey(String),
+    Http(String),
+    /// Error object returned by the RPC server
+    Rpc {
+        code: i64,
+        message: String,
+    },
+    /// The invocation failed in simulation
+    Simulation(String),
+    /// The transaction was rejected or failed on chain
+    Transaction {
+        hash: String,
+        status: String,
+    },
+    /// The transaction wasn't found in a ledger in time
+    Timeout(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Xdr(err) => write!(f, "xdr error: {err}"),
+            ClientError::Conversion(what) => write!(f, "cannot convert {what}"),
+            ClientError::InvalidKey(key) => write!(f, "invalid key {key}"),
+            ClientError::Http(err) => write!(f, "http error: {err}"),
+            ClientError::Rpc { code, message } => write!(f, "rpc error {code}: {message}"),
+            ClientError::Simulation(err) => write!(f, "simulation failed: {err}"),
+            ClientError::Transaction { hash, status } => {
+                write!(f, "transaction {hash} failed with status {status}")
+            }
+            ClientError::Timeout(hash) => write!(f, "transaction {hash} not confirmed in time"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<stellar_xdr::curr::Error> for ClientError {
+    fn from(err: stellar_xdr::curr::Error) -> Self {
+        ClientError::Xdr(err)
+    }
+}
+
+pub struct NovaFundClient<B> {
+    env: Env,
+    backend: B,
+    base_fee: u32,
+    timeout: u64,
+}
+
+impl<B: Backend> NovaFundClient<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            env: Env::default(),
+            backend,
+            base_fee: DEFAULT_BASE_FEE,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn with_base_fee(mut self, base_fee: u32) -> Self {
+        self.base_fee = base_fee;
+        self
+    }
+
+    /// Seconds from now until built transactions expire
+    pub fn with_timeout(mut self, timeout: u64) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Host environment the call arguments and results live in. Use it to
+    /// build `Bytes`, `Vec`, `Map` and other SDK values for calls.
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Parse a `G...` account or `C...` contract strkey into an argument
+    pub fn address(&self, strkey: &str) -> Result<Address, ClientError> {
+        parse_address(strkey)?;
+        Ok(Address::from_string(&soroban_sdk::String::from_str(
+            &self.env, strkey,
+        )))
+    }
+
+    pub fn project_launch(&self, contract: &str) -> Result<ProjectLaunch<'_, B>, ClientError> {
+        ProjectLaunch::new(self, contract)
+    }
+
+    pub fn escrow(&self, contract: &str) -> Result<Escrow<'_, B>, ClientError> {
+        Escrow::new(self, contract)
+    }
+
+    pub fn profit_distribution(
+        &self,
+        contract: &str,
+    ) -> Result<ProfitDistribution<'_, B>, ClientError> {
+        ProfitDistribution::new(self, contract)
+    }
+
+    pub fn share_token(&self, contract: &str) -> Result<ShareToken<'_, B>, ClientError> {
+        ShareToken::new(self, contract)
+    }
+
+    pub fn subscription_pool(
+        &self,
+        contract: &str,
+    ) -> Result<SubscriptionPool<'_, B>, ClientError> {
+        SubscriptionPool::new(self, contract)
+    }
+
+    pub fn multi_party_payment(
+        &self,
+        contract: &str,
+    ) -> Result<MultiPartyPayment<'_, B>, ClientError> {
+        MultiPartyPayment::new(self, contract)
+    }
+
+    pub fn reputation(&self, contract: &str) -> Result<Reputation<'_, B>, ClientError> {
+        Reputation::new(self, contract)
+    }
+
+    pub fn governance(&self, contract: &str) -> Result<Governance<'_, B>, ClientError> {
+        Governance::new(self, contract)
+    }
+
+    pub fn cross_chain_bridge(
+        &self,
+        contract: &str,
+    ) -> Result<CrossChainBridge<'_, B>, ClientError> {
+        CrossChainBridge::new(self, contract)
+    }
+
+    /// Upload contract code. The call returns the hash the code is
+    /// stored under, which is also `sha256(wasm)`.
+    pub fn upload_wasm(&self, wasm: &[u8]) -> Call<'_, B, BytesN<32>> {
+        Call::new(
+            self,
+            wasm.to_vec()
+                .try_into()
+                .map(HostFunction::UploadContractWasm)
+                .map_err(ClientError::from),
+        )
+    }
+
+    /// Create a contract running the uploaded `wasm_hash`, at the address
+    /// derived from `deployer` and `salt`. `deployer` must authorize it.
+    pub fn create_contract(
+        &self,
+        deployer: &str,
+        wasm_hash: [u8; 32],
+        salt: [u8; 32],
+    ) -> Call<'_, B, Address> {
+        Call::new(
+            self,
+            parse_address(deployer).map(|address| {
+                HostFunction::CreateContract(CreateContractArgs {
+                    contract_id_preimage: ContractIdPreimage::Address(
+                        ContractIdPreimageFromAddress {
+                            address,
+                            salt: Uint256(salt),
+                        },
+                    ),
+                    executable: ContractExecutable::Wasm(Hash(wasm_hash)),
+                })
+            }),
+        )
+    }
+
+    /// `C...` address [`NovaFundClient::create_contract`] gives a contract
+    /// created by `deployer` with `salt`
+    pub fn contract_address(&self, deployer: &str, salt: [u8; 32]) -> Result<String, ClientError> {
+        tx::contract_address(
+            &self.backend.network_passphrase(),
+            parse_address(deployer)?,
+            salt,
+        )
+    }
+
+    /// Simulate `function` with `source` as the transaction source
+    pub fn simulate(
+        &self,
+        source: &str,
+        function: &HostFunction,
+    ) -> Result<Simulation, ClientError> {
+        let tx = tx::build(
+            source,
+            self.sequence(source)? + 1,
+            self.base_fee,
+            self.timeout,
+            function,
+        )?;
+        self.backend.simulate(&tx)
+    }
+
+    /// Build the transaction for `function`, with the footprint, resource
+    /// fee and auth entries from simulation, ready to be signed by `source`.
+    ///
+    /// Auth entries recorded in simulation are attached as they are, which
+    /// covers addresses authorizing as the transaction source. Entries for
+    /// other addresses need their own signatures before the transaction is
+    /// sent.
+    pub fn build(
+        &self,
+        source: &str,
+        function: &HostFunction,
+    ) -> Result<TransactionEnvelope, ClientError> {
+        self.build_at(source, self.sequence(source)? + 1, function)
+    }
+
+    /// [`NovaFundClient::build`] with an explicit sequence number, for
+    /// several transactions from `source` that are sent later in order
+    pub fn build_at(
+        &self,
+        source: &str,
+        sequence: i64,
+        function: &HostFunction,
+    ) -> Result<TransactionEnvelope, ClientError> {
+        let mut tx = tx::build(source, sequence, self.base_fee, self.timeout, function)?;
+        let simulation = self.backend.simulate(&tx)?;
+        tx::assemble(&mut tx, &simulation)?;
+        Ok(tx)
+    }
+
+    /// Sign a built transaction for the backend's network
+    pub fn sign(&self, tx: &mut TransactionEnvelope, signer: &Signer) -> Result<(), ClientError> {
+        signer.sign(tx, &self.backend.network_passphrase())
+    }
+
+    /// Build, sign and send `function`, waiting for its result
+    pub fn send(
+        &self,
+        function: &HostFunction,
+        signer: &Signer,
+    ) -> Result<Submission, ClientError> {
+        let mut tx = self.build(&signer.account_id(), function)?;
+        self.sign(&mut tx, signer)?;
+        self.backend.submit(&tx)
+    }
+
+    /// Current sequence number of `account`
+    pub fn sequence(&self, account: &str) -> Result<i64, ClientError> {
+        self.backend.sequence(account)
+    }
+
+    /// Decode a contract return value into `T`
+    pub fn decode<T: TryFromVal<Env, Val>>(&self, value: &ScVal) -> Result<T, ClientError> {
+        let val = Val::try_from_val(&self.env, value)
+            .map_err(|_| ClientError::Conversion(format!("{value:?}")))?;
+        T::try_from_val(&self.env, &val).map_err(|_| {
+            ClientError::Conversion(format!("{value:?} to {}", std::any::type_name::<T>()))
+        })
+    }
+
+    pub(crate) fn encode<T: IntoVal<Env, Val> + Clone>(
+        &self,
+        arg: &T,
+    ) -> Result<ScVal, ClientError> {
+        let val: Val = arg.clone().into_val(&self.env);
+        ScVal::try_from_val(&self.env, &val)
+            .map_err(|_| ClientError::Conversion(format!("{val:?}")))
+    }
+}
+
+/// A pending call returning `T`
+pub struct Call<'a, B, T> {
+    client: &'a NovaFundClient<B>,
+    function: Result<HostFunction, String>,
+    _ret: PhantomData<fn() -> T>,
+}
+
+impl<'a, B: Backend, T: TryFromVal<Env, Val>> Call<'a, B, T> {
+    fn new(client: &'a NovaFundClient<B>, function: Result<HostFunction, ClientError>) -> Self {
+        Self {
+            client,
+            function: function.map_err(|err| err.to_string()),
+            _ret: PhantomData,
+        }
+    }
+
+    /// Call `function` on `contract`
+    fn invoke(
+        client: &'a NovaFundClient<B>,
+        contract: &ScAddress,
+        function: &str,
+        args: Vec<Result<ScVal, ClientError>>,
+    ) -> Self {
+        let function = args
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|args| {
+                Invocation {
+                    contract: contract.clone(),
+                    function: ScSymbol(
+                        function
+                            .try_into()
+                            .map_err(|_| ClientError::Conversion(function.to_string()))?,
+                    ),
+                    args,
+                }
+                .host_function()
+            });
+        Self::new(client, function)
+    }
+
+    pub fn host_function(&self) -> Result<&HostFunction, ClientError> {
+        self.function
+            .as_ref()
+            .map_err(|err| ClientError::Conversion(err.clone()))
+    }
+
+    /// Result of the call without sending it, for reads
+    pub fn simulate(&self, source: &str) -> Result<T, ClientError> {
+        let simulation = self.client.simulate(source, self.host_function()?)?;
+        self.client.decode(&simulation.result)
+    }
+
+    /// Unsigned transaction for the call, with `source` paying the fees
+    pub fn build(&self, source: &str) -> Result<TransactionEnvelope, ClientError> {
+        self.client.build(source, self.host_function()?)
+    }
+
+    /// [`Call::build`] with an explicit sequence number
+    pub fn build_at(
+        &self,
+        source: &str,
+        sequence: i64,
+    ) -> Result<TransactionEnvelope, ClientError> {
+        self.client
+            .build_at(source, sequence, self.host_function()?)
+    }
+
+    /// Send the call signed by `signer` and return its result
+    pub fn send(&self, signer: &Signer) -> Result<T, ClientError> {
+        let submission = self.client.send(self.host_function()?, signer)?;
+        self.client.decode(&submission.result)
+    }
+}
+
+pub(crate) fn parse_address(strkey: &str) -> Result<ScAddress, ClientError> {
+    strkey
+        .parse()
+        .map_err(|_| ClientError::InvalidKey(strkey.to_string()))
+}
+
+#[cfg(test)]
+mod tests;
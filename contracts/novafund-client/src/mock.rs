@@ -0,0 +1,242 @@
+//! Offline backend running transactions against a local test `Env`
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use soroban_sdk::{
+    testutils::Ledger as _,
+    xdr::{
+        ContractExecutable, ContractIdPreimage, CreateContractArgs, HostFunction,
+        InvokeContractArgs, MuxedAccount, ScAddress, ScBytes, ScVal, TransactionEnvelope,
+    },
+    Address, Bytes, BytesN, Env, Symbol, TryFromVal, Val, Vec,
+};
+
+use crate::{
+    backend::{hex, Backend, Simulation, Submission},
+    tx::{host_function, network_id, transaction_hash},
+    ClientError,
+};
+
+/// Passphrase transactions for a [`MockBackend`] are signed with
+pub const MOCK_NETWORK_PASSPHRASE: &str = "NovaFund Mock Network";
+
+type Registrar = Box<dyn Fn(&Env, &Address)>;
+
+/// Stand-in for an RPC node. Register contracts in [`MockBackend::env`]
+/// and point the client at their addresses, or deploy them through the
+/// client after mapping their code to native contracts with
+/// [`MockBackend::register_native`].
+///
+/// Auths are mocked, so simulation returns no auth entries and no resource
+/// data. Sent transactions must carry the source account's next sequence
+/// number and its signature. Accounts start at sequence 0.
+pub struct MockBackend {
+    env: Env,
+    sequences: RefCell<HashMap<String, i64>>,
+    submitted: RefCell<std::vec::Vec<TransactionEnvelope>>,
+    natives: HashMap<[u8; 32], Registrar>,
+    /// Native contracts created through transactions
+    created: RefCell<HashSet<String>>,
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger()
+            .with_mut(|ledger| ledger.network_id = network_id(MOCK_NETWORK_PASSPHRASE).0);
+        Self {
+            env,
+            sequences: RefCell::default(),
+            submitted: RefCell::default(),
+            natives: HashMap::new(),
+            created: RefCell::default(),
+        }
+    }
+
+    /// Make contracts created from `wasm` run natively: `register` is
+    /// called with the new contract's address and should register the
+    /// contract there, e.g. `env.register_contract(Some(address), Escrow)`
+    pub fn register_native(&mut self, wasm: &[u8], register: impl Fn(&Env, &Address) + 'static) {
+        self.natives
+            .insert(Sha256::digest(wasm).into(), Box::new(register));
+    }
+
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Transactions applied so far, oldest first
+    pub fn submitted(&self) -> std::vec::Vec<TransactionEnvelope> {
+        self.submitted.borrow().clone()
+    }
+
+    fn apply(&self, tx: &TransactionEnvelope) -> Result<ScVal, ClientError> {
+        match host_function(tx)? {
+            HostFunction::InvokeContract(args) => self.invoke(args),
+            HostFunction::UploadContractWasm(wasm) => {
+                let hash: [u8; 32] = Sha256::digest(&wasm).into();
+                if !self.natives.contains_key(&hash) {
+                    self.env
+                        .deployer()
+                        .upload_contract_wasm(Bytes::from_slice(&self.env, &wasm));
+                }
+                Ok(ScVal::Bytes(ScBytes(hash.to_vec().try_into()?)))
+            }
+            HostFunction::CreateContract(CreateContractArgs {
+                contract_id_preimage: ContractIdPreimage::Address(preimage),
+                executable: ContractExecutable::Wasm(hash),
+            }) => {
+                let deployer = self.address(preimage.address)?;
+                let deployer = self
+                    .env
+                    .deployer()
+                    .with_address(deployer, BytesN::from_array(&self.env, &preimage.salt.0));
+                let address = match self.natives.get(&hash.0) {
+                    Some(register) => {
+                        let address = deployer.deployed_address();
+                        if !self
+                            .created
+                            .borrow_mut()
+                            .insert(address.to_string().to_string())
+                        {
+                            return Err(ClientError::Simulation(
+                                "contract already exists".to_string(),
+                            ));
+                        }
+                        register(&self.env, &address);
+                        address
+                    }
+                    None => deployer.deploy(BytesN::from_array(&self.env, &hash.0)),
+                };
+                Ok(ScVal::Address((&address).into()))
+            }
+            _ => Err(ClientError::Simulation(
+                "unsupported host function".to_string(),
+            )),
+        }
+    }
+
+    fn address(&self, address: ScAddress) -> Result<Address, ClientError> {
+        Address::try_from_val(&self.env, &ScVal::Address(address))
+            .map_err(|_| ClientError::Conversion("address".to_string()))
+    }
+
+    fn invoke(&self, invocation: InvokeContractArgs) -> Result<ScVal, ClientError> {
+        let ScAddress::Contract(_) = &invocation.contract_address else {
+            return Err(ClientError::Simulation(
+                "not a contract address".to_string(),
+            ));
+        };
+        let contract = self.address(invocation.contract_address)?;
+        let function = Symbol::new(&self.env, &invocation.function_name.to_utf8_string_lossy());
+        let mut args = Vec::new(&self.env);
+        for arg in invocation.args.iter() {
+            args.push_back(
+                Val::try_from_val(&self.env, arg)
+                    .map_err(|_| ClientError::Conversion(format!("{arg:?}")))?,
+            );
+        }
+
+        let result = match self
+            .env
+            .try_invoke_contract::<Val, soroban_sdk::Error>(&contract, &function, args)
+        {
+            Ok(Ok(result)) => result,
+            Ok(Err(err)) => return Err(ClientError::Simulation(format!("{err:?}"))),
+            Err(Ok(err)) => return Err(ClientError::Simulation(format!("{err:?}"))),
+            Err(Err(err)) => return Err(ClientError::Simulation(format!("{err:?}"))),
+        };
+        ScVal::try_from_val(&self.env, &result)
+            .map_err(|_| ClientError::Conversion(format!("{result:?}")))
+    }
+}
+
+impl Backend for MockBackend {
+    fn network_passphrase(&self) -> String {
+        MOCK_NETWORK_PASSPHRASE.to_string()
+    }
+
+    fn sequence(&self, account: &str) -> Result<i64, ClientError> {
+        stellar_strkey::ed25519::PublicKey::from_string(account)
+            .map_err(|_| ClientError::InvalidKey(account.to_string()))?;
+        Ok(self.sequences.borrow().get(account).copied().unwrap_or(0))
+    }
+
+    /// Runs the invocation, then puts the ledger back as it was
+    fn simulate(&self, tx: &TransactionEnvelope) -> Result<Simulation, ClientError> {
+        let host = self.env.host();
+        let saved = host
+            .with_mut_storage(|storage| Ok(storage.map.clone()))
+            .map_err(|err| ClientError::Simulation(format!("{err:?}")))?;
+        let created = self.created.borrow().clone();
+        let result = self.apply(tx);
+        *self.created.borrow_mut() = created;
+        host.with_mut_storage(|storage| {
+            storage.map = saved;
+            Ok(())
+        })
+        .map_err(|err| ClientError::Simulation(format!("{err:?}")))?;
+        Ok(Simulation {
+            result: result?,
+            auth: std::vec::Vec::new(),
+            transaction_data: None,
+            min_resource_fee: 0,
+        })
+    }
+
+    fn submit(&self, tx: &TransactionEnvelope) -> Result<Submission, ClientError> {
+        let TransactionEnvelope::Tx(envelope) = tx else {
+            return Err(ClientError::Conversion(
+                "only v1 transactions can be sent".to_string(),
+            ));
+        };
+        let hash = transaction_hash(&envelope.tx, MOCK_NETWORK_PASSPHRASE)?;
+        let failed = |status: &str| ClientError::Transaction {
+            hash: hex(&hash),
+            status: status.to_string(),
+        };
+
+        let MuxedAccount::Ed25519(source) = &envelope.tx.source_account else {
+            return Err(failed("txBAD_SRC_ACCOUNT"));
+        };
+        let account = stellar_strkey::ed25519::PublicKey(source.0).to_string();
+        if envelope.tx.seq_num.0 != self.sequence(&account)? + 1 {
+            return Err(failed("txBAD_SEQ"));
+        }
+        let key = VerifyingKey::from_bytes(&source.0).map_err(|_| failed("txBAD_AUTH"))?;
+        let signed = envelope.signatures.iter().any(|signature| {
+            <[u8; 64]>::try_from(signature.signature.as_slice())
+                .map(|bytes| {
+                    key.verify_strict(&hash, &Signature::from_bytes(&bytes))
+                        .is_ok()
+                })
+                .unwrap_or(false)
+        });
+        if !signed {
+            return Err(failed("txBAD_AUTH"));
+        }
+
+        // The sequence number is consumed even if the invocation fails
+        self.sequences
+            .borrow_mut()
+            .insert(account, envelope.tx.seq_num.0);
+        let result = self.apply(tx).map_err(|_| failed("txFAILED"))?;
+        self.submitted.borrow_mut().push(tx.clone());
+        Ok(Submission {
+            hash: hex(&hash),
+            result,
+        })
+    }
+}
@@ -0,0 +1,431 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+use escrow::EscrowContract;
+use project_launch::ProjectLaunch;
+use serde_json::{json, Value};
+use shared::types::{EscrowInfo, ProjectStatus};
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    xdr::{
+        AccountEntry, AccountEntryExt, ExtensionPoint, LedgerEntryData, LedgerFootprint, Limits,
+        PublicKey, ScVal, SorobanResources, SorobanTransactionData, Thresholds,
+        TransactionEnvelope, TransactionExt, Uint256, WriteXdr,
+    },
+    Address, Bytes, Env, String as SorobanString, Vec,
+};
+
+use super::*;
+
+const DAY: u64 = 86_400;
+
+#[contracttype]
+struct Balance(Address);
+
+/// Just enough of a token for contributions. The Stellar asset contract
+/// would need trustlines for `G...` accounts.
+#[contract]
+struct TestToken;
+
+#[contractimpl]
+impl TestToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&Balance(to), &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&Balance(id)).unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        Self::mint(env.clone(), from, -amount);
+        Self::mint(env, to, amount);
+    }
+}
+
+fn signer(seed: u8) -> Signer {
+    Signer::from_seed([seed; 32])
+}
+
+/// Address of `account` in the mock's own environment
+fn mock_address(backend: &MockBackend, account: &str) -> Address {
+    Address::from_string(&SorobanString::from_str(backend.env(), account))
+}
+
+fn strkey(address: &Address) -> std::string::String {
+    address.to_string().to_string()
+}
+
+struct Setup {
+    client: NovaFundClient<MockBackend>,
+    admin: Signer,
+    backer: Signer,
+    launch: std::string::String,
+    token: std::string::String,
+}
+
+fn setup() -> Setup {
+    let backend = MockBackend::new();
+    let admin = signer(1);
+    let backer = signer(2);
+    let env = backend.env();
+
+    let launch = env.register_contract(None, ProjectLaunch);
+    let token = env.register_contract(None, TestToken);
+    TestTokenClient::new(env, &token).mint(
+        &mock_address(&backend, &backer.account_id()),
+        &50_000_000_000,
+    );
+
+    let launch = strkey(&launch);
+    let token = strkey(&token);
+    Setup {
+        client: NovaFundClient::new(backend),
+        admin,
+        backer,
+        launch,
+        token,
+    }
+}
+
+fn create_project(s: &Setup) -> u64 {
+    let client = &s.client;
+    let launch = client.project_launch(&s.launch).unwrap();
+    let admin = client.address(&s.admin.account_id()).unwrap();
+    launch.initialize(&admin).send(&s.admin).unwrap();
+    launch
+        .create_project(
+            &admin,
+            &10_000_000_000,
+            &(client.backend().env().ledger().timestamp() + 30 * DAY),
+            &client.address(&s.token).unwrap(),
+            &Bytes::from_slice(client.env(), b"ipfs://project"),
+        )
+        .send(&s.admin)
+        .unwrap()
+}
+
+#[test]
+fn test_project_lifecycle_through_mock_backend() {
+    let s = setup();
+    let project_id = create_project(&s);
+    let launch = s.client.project_launch(&s.launch).unwrap();
+    let backer = s.client.address(&s.backer.account_id()).unwrap();
+
+    launch
+        .contribute(&project_id, &backer, &20_000_000_000)
+        .send(&s.backer)
+        .unwrap();
+
+    let project = launch
+        .get_project(&project_id)
+        .simulate(&s.backer.account_id())
+        .unwrap();
+    assert_eq!(project.total_raised, 20_000_000_000);
+    assert_eq!(project.status, ProjectStatus::Active);
+    assert_eq!(
+        launch
+            .get_user_contribution(&project_id, &backer)
+            .simulate(&s.backer.account_id())
+            .unwrap(),
+        20_000_000_000
+    );
+
+    // One transaction per call, each consuming the sender's next sequence
+    assert_eq!(s.client.backend().submitted().len(), 3);
+    assert_eq!(
+        s.client.backend().sequence(&s.admin.account_id()).unwrap(),
+        2
+    );
+    assert_eq!(
+        s.client.backend().sequence(&s.backer.account_id()).unwrap(),
+        1
+    );
+}
+
+#[test]
+fn test_simulate_leaves_state_untouched() {
+    let s = setup();
+    let project_id = create_project(&s);
+    let launch = s.client.project_launch(&s.launch).unwrap();
+    let source = s.admin.account_id();
+
+    let next = launch
+        .create_project(
+            &s.client.address(&source).unwrap(),
+            &10_000_000_000,
+            &(s.client.backend().env().ledger().timestamp() + 30 * DAY),
+            &s.client.address(&s.token).unwrap(),
+            &Bytes::new(s.client.env()),
+        )
+        .simulate(&source)
+        .unwrap();
+    assert_eq!(next, project_id + 1);
+    assert_eq!(
+        launch.get_next_project_id().simulate(&source).unwrap(),
+        project_id + 1
+    );
+    assert_eq!(s.client.backend().sequence(&source).unwrap(), 2);
+}
+
+#[test]
+fn test_build_produces_unsigned_transaction() {
+    let s = setup();
+    let source = s.admin.account_id();
+    let launch = s.client.project_launch(&s.launch).unwrap();
+    let call = launch.initialize(&s.client.address(&source).unwrap());
+
+    let mut tx = call.build(&source).unwrap();
+    let TransactionEnvelope::Tx(envelope) = &tx else {
+        panic!("expected a v1 transaction");
+    };
+    assert!(envelope.signatures.is_empty());
+    assert_eq!(envelope.tx.seq_num.0, 1);
+    assert_eq!(envelope.tx.fee, DEFAULT_BASE_FEE);
+    assert_eq!(
+        &Invocation::from_transaction(&tx)
+            .unwrap()
+            .host_function()
+            .unwrap(),
+        call.host_function().unwrap()
+    );
+
+    // Building doesn't send anything
+    assert!(!launch.is_initialized().simulate(&source).unwrap());
+
+    s.client.sign(&mut tx, &s.admin).unwrap();
+    let submission = s.client.backend().submit(&tx).unwrap();
+    assert_eq!(submission.result, ScVal::Void);
+    assert!(launch.is_initialized().simulate(&source).unwrap());
+}
+
+#[test]
+fn test_mock_rejects_bad_transactions() {
+    let s = setup();
+    let source = s.admin.account_id();
+    let launch = s.client.project_launch(&s.launch).unwrap();
+    let admin = s.client.address(&source).unwrap();
+    let backend = s.client.backend();
+
+    // Unsigned, and signed by the wrong key
+    let mut tx = launch.initialize(&admin).build(&source).unwrap();
+    let err = backend.submit(&tx).unwrap_err();
+    assert!(matches!(err, ClientError::Transaction { ref status, .. } if status == "txBAD_AUTH"));
+    s.backer.sign(&mut tx, MOCK_NETWORK_PASSPHRASE).unwrap();
+    assert!(backend.submit(&tx).is_err());
+
+    // Replayed
+    let mut tx = launch.initialize(&admin).build(&source).unwrap();
+    s.client.sign(&mut tx, &s.admin).unwrap();
+    backend.submit(&tx).unwrap();
+    let err = backend.submit(&tx).unwrap_err();
+    assert!(matches!(err, ClientError::Transaction { ref status, .. } if status == "txBAD_SEQ"));
+
+    // Contract errors fail simulation
+    let err = launch.initialize(&admin).send(&s.admin).unwrap_err();
+    assert!(matches!(err, ClientError::Simulation(_)));
+    assert!(matches!(
+        launch.get_project(&7).simulate(&source),
+        Err(ClientError::Simulation(_))
+    ));
+}
+
+#[test]
+fn test_struct_arguments_and_results_round_trip() {
+    let s = setup();
+    let env = s.client.backend().env();
+    let escrow = strkey(&env.register_contract(None, EscrowContract));
+    let creator = s.client.address(&s.admin.account_id()).unwrap();
+    let mut validators = Vec::new(s.client.env());
+    for seed in 10..13 {
+        validators.push_back(s.client.address(&signer(seed).account_id()).unwrap());
+    }
+
+    let client = s.client.escrow(&escrow).unwrap();
+    client
+        .initialize(
+            &1,
+            &creator,
+            &s.client.address(&s.token).unwrap(),
+            &validators,
+        )
+        .send(&s.admin)
+        .unwrap();
+    let info: EscrowInfo = client
+        .get_escrow(&1)
+        .simulate(&s.admin.account_id())
+        .unwrap();
+    assert_eq!(info.creator, creator);
+    assert_eq!(info.validators, validators);
+}
+
+#[test]
+fn test_deploy_through_mock_backend() {
+    let mut backend = MockBackend::new();
+    backend.register_native(b"project-launch", |env, address| {
+        env.register_contract(Some(address), ProjectLaunch);
+    });
+    let client = NovaFundClient::new(backend);
+    let admin = signer(1);
+    let salt = [9; 32];
+
+    let hash = client.upload_wasm(b"project-launch").send(&admin).unwrap();
+    let expected = client.contract_address(&admin.account_id(), salt).unwrap();
+    let address = client
+        .create_contract(&admin.account_id(), hash.to_array(), salt)
+        .send(&admin)
+        .unwrap();
+    assert_eq!(strkey(&address), expected);
+
+    let launch = client.project_launch(&expected).unwrap();
+    launch
+        .initialize(&client.address(&admin.account_id()).unwrap())
+        .send(&admin)
+        .unwrap();
+    assert!(launch
+        .is_initialized()
+        .simulate(&admin.account_id())
+        .unwrap());
+}
+
+#[test]
+fn test_wrappers_reject_non_contract_addresses() {
+    let s = setup();
+    assert!(s.client.escrow(&s.admin.account_id()).is_err());
+    assert!(s.client.escrow("not a strkey").is_err());
+    assert!(s.client.address("nope").is_err());
+    assert!(Signer::from_secret("SNOTASECRET").is_err());
+}
+
+#[test]
+fn test_signer_from_secret() {
+    let seed = [7; 32];
+    let secret = stellar_strkey::ed25519::PrivateKey(seed).to_string();
+    assert_eq!(
+        Signer::from_secret(&secret).unwrap().account_id(),
+        Signer::from_seed(seed).account_id()
+    );
+}
+
+/// Serve canned JSON-RPC results on a local port
+fn stub_rpc(respond: fn(&str) -> Value) -> std::string::String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            'requests: loop {
+                let mut length = 0;
+                loop {
+                    let mut line = std::string::String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 {
+                        break 'requests;
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": respond(request["method"].as_str().unwrap()),
+                })
+                .to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        }
+    });
+    url
+}
+
+fn rpc_responses(method: &str) -> Value {
+    let account = AccountEntry {
+        account_id: PublicKey::PublicKeyTypeEd25519(Uint256([3; 32])).into(),
+        balance: 1_000_000_000,
+        seq_num: 41.into(),
+        num_sub_entries: 0,
+        inflation_dest: None,
+        flags: 0,
+        home_domain: Default::default(),
+        thresholds: Thresholds([1, 0, 0, 0]),
+        signers: Default::default(),
+        ext: AccountEntryExt::V0,
+    };
+    let data = SorobanTransactionData {
+        ext: ExtensionPoint::V0,
+        resources: SorobanResources {
+            footprint: LedgerFootprint {
+                read_only: Default::default(),
+                read_write: Default::default(),
+            },
+            instructions: 1_000,
+            read_bytes: 100,
+            write_bytes: 100,
+        },
+        resource_fee: 5_000,
+    };
+    match method {
+        "getLedgerEntries" => json!({ "entries": [{
+            "xdr": LedgerEntryData::Account(account).to_xdr_base64(Limits::none()).unwrap(),
+        }] }),
+        "simulateTransaction" => json!({
+            "transactionData": data.to_xdr_base64(Limits::none()).unwrap(),
+            "minResourceFee": "5000",
+            "results": [{ "auth": [], "xdr": ScVal::U64(9).to_xdr_base64(Limits::none()).unwrap() }],
+        }),
+        "sendTransaction" => json!({ "status": "ERROR", "hash": "00" }),
+        _ => Value::Null,
+    }
+}
+
+#[test]
+fn test_rpc_backend_builds_from_simulation() {
+    let url = stub_rpc(rpc_responses);
+    let client = NovaFundClient::new(RpcBackend::new(Network::new(url, "Stub Network")));
+    let source = signer(3);
+    let contract = stellar_strkey::Contract([4; 32]).to_string();
+    let launch = client.project_launch(&contract).unwrap();
+
+    assert_eq!(client.backend().sequence(&source.account_id()).unwrap(), 41);
+    assert_eq!(
+        launch
+            .get_next_project_id()
+            .simulate(&source.account_id())
+            .unwrap(),
+        9
+    );
+
+    let tx = launch
+        .get_next_project_id()
+        .build(&source.account_id())
+        .unwrap();
+    let TransactionEnvelope::Tx(envelope) = &tx else {
+        panic!("expected a v1 transaction");
+    };
+    assert_eq!(envelope.tx.seq_num.0, 42);
+    assert_eq!(envelope.tx.fee, DEFAULT_BASE_FEE + 5_000);
+    assert!(matches!(&envelope.tx.ext, TransactionExt::V1(data) if data.resource_fee == 5_000));
+
+    let err = launch.get_next_project_id().send(&source).unwrap_err();
+    assert!(matches!(err, ClientError::Transaction { ref status, .. } if status == "ERROR"));
+}
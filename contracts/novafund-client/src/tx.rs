@@ -0,0 +1,210 @@
+//! Transaction building and signing
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::{Signer as _, SigningKey};
+use sha2::{Digest, Sha256};
+use soroban_sdk::xdr::{
+    ContractIdPreimage, ContractIdPreimageFromAddress, DecoratedSignature, Hash, HashIdPreimage,
+    HashIdPreimageContractId, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
+    MuxedAccount, Operation, OperationBody, Preconditions, ScAddress, ScSymbol, ScVal,
+    SequenceNumber, Signature, SignatureHint, TimeBounds, TimePoint, Transaction,
+    TransactionEnvelope, TransactionExt, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, TransactionV1Envelope, Uint256, WriteXdr,
+};
+
+use crate::{backend::Simulation, ClientError};
+
+/// A contract function call, as carried by an `InvokeHostFunction` operation
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invocation {
+    pub contract: ScAddress,
+    pub function: ScSymbol,
+    pub args: Vec<ScVal>,
+}
+
+impl Invocation {
+    pub fn host_function(&self) -> Result<HostFunction, ClientError> {
+        Ok(HostFunction::InvokeContract(InvokeContractArgs {
+            contract_address: self.contract.clone(),
+            function_name: self.function.clone(),
+            args: self.args.clone().try_into()?,
+        }))
+    }
+
+    /// The invocation carried by a transaction built by this crate
+    pub fn from_transaction(tx: &TransactionEnvelope) -> Result<Self, ClientError> {
+        match host_function(tx)? {
+            HostFunction::InvokeContract(args) => Ok(Self {
+                contract: args.contract_address,
+                function: args.function_name,
+                args: args.args.to_vec(),
+            }),
+            _ => Err(ClientError::Conversion(
+                "transaction without a contract invocation".to_string(),
+            )),
+        }
+    }
+}
+
+/// The host function of a single-operation transaction
+pub(crate) fn host_function(tx: &TransactionEnvelope) -> Result<HostFunction, ClientError> {
+    let op = match tx {
+        TransactionEnvelope::Tx(envelope) => envelope.tx.operations.first(),
+        _ => None,
+    };
+    match op.map(|op| &op.body) {
+        Some(OperationBody::InvokeHostFunction(op)) => Ok(op.host_function.clone()),
+        _ => Err(ClientError::Conversion(
+            "transaction without a host function".to_string(),
+        )),
+    }
+}
+
+/// An ed25519 account key that signs transactions
+pub struct Signer {
+    key: SigningKey,
+}
+
+impl Signer {
+    /// From an `S...` secret seed
+    pub fn from_secret(secret: &str) -> Result<Self, ClientError> {
+        let seed = stellar_strkey::ed25519::PrivateKey::from_string(secret)
+            .map_err(|_| ClientError::InvalidKey("secret seed".to_string()))?;
+        Ok(Self::from_seed(seed.0))
+    }
+
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self {
+            key: SigningKey::from_bytes(&seed),
+        }
+    }
+
+    /// The `G...` account this key controls
+    pub fn account_id(&self) -> String {
+        stellar_strkey::ed25519::PublicKey(self.key.verifying_key().to_bytes()).to_string()
+    }
+
+    /// Add this key's signature to a transaction for `network_passphrase`
+    pub fn sign(
+        &self,
+        tx: &mut TransactionEnvelope,
+        network_passphrase: &str,
+    ) -> Result<(), ClientError> {
+        let TransactionEnvelope::Tx(envelope) = tx else {
+            return Err(ClientError::Conversion(
+                "only v1 transactions can be signed".to_string(),
+            ));
+        };
+        let hash = transaction_hash(&envelope.tx, network_passphrase)?;
+        let public_key = self.key.verifying_key().to_bytes();
+        let signature = DecoratedSignature {
+            hint: SignatureHint(public_key[28..].try_into().unwrap()),
+            signature: Signature(self.key.sign(&hash).to_bytes().try_into()?),
+        };
+        let mut signatures = envelope.signatures.to_vec();
+        signatures.push(signature);
+        envelope.signatures = signatures.try_into()?;
+        Ok(())
+    }
+}
+
+/// Hash a transaction is signed and identified by on `network_passphrase`
+pub fn transaction_hash(
+    tx: &Transaction,
+    network_passphrase: &str,
+) -> Result<[u8; 32], ClientError> {
+    let payload = TransactionSignaturePayload {
+        network_id: network_id(network_passphrase),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+    };
+    Ok(Sha256::digest(payload.to_xdr(Limits::none())?).into())
+}
+
+/// Address of the contract `deployer` creates with `salt`
+pub(crate) fn contract_address(
+    network_passphrase: &str,
+    deployer: ScAddress,
+    salt: [u8; 32],
+) -> Result<String, ClientError> {
+    let preimage = HashIdPreimage::ContractId(HashIdPreimageContractId {
+        network_id: network_id(network_passphrase),
+        contract_id_preimage: ContractIdPreimage::Address(ContractIdPreimageFromAddress {
+            address: deployer,
+            salt: Uint256(salt),
+        }),
+    });
+    let id = Sha256::digest(preimage.to_xdr(Limits::none())?);
+    Ok(ScAddress::Contract(Hash(id.into())).to_string())
+}
+
+pub(crate) fn network_id(network_passphrase: &str) -> Hash {
+    Hash(Sha256::digest(network_passphrase.as_bytes()).into())
+}
+
+/// Unsigned, unsimulated transaction carrying a single host function
+pub(crate) fn build(
+    source: &str,
+    sequence: i64,
+    base_fee: u32,
+    timeout: u64,
+    function: &HostFunction,
+) -> Result<TransactionEnvelope, ClientError> {
+    let source = stellar_strkey::ed25519::PublicKey::from_string(source)
+        .map_err(|_| ClientError::InvalidKey(source.to_string()))?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: function.clone(),
+            auth: Default::default(),
+        }),
+    };
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256(source.0)),
+            fee: base_fee,
+            seq_num: SequenceNumber(sequence),
+            cond: Preconditions::Time(TimeBounds {
+                min_time: TimePoint(0),
+                max_time: TimePoint(now + timeout),
+            }),
+            memo: Memo::None,
+            operations: vec![operation].try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: Default::default(),
+    }))
+}
+
+/// Apply simulation results: auth entries, footprint and resource fee
+pub(crate) fn assemble(
+    tx: &mut TransactionEnvelope,
+    simulation: &Simulation,
+) -> Result<(), ClientError> {
+    let TransactionEnvelope::Tx(envelope) = tx else {
+        return Err(ClientError::Conversion(
+            "only v1 transactions can be assembled".to_string(),
+        ));
+    };
+    let mut operations = envelope.tx.operations.to_vec();
+    if let Some(Operation {
+        body: OperationBody::InvokeHostFunction(op),
+        ..
+    }) = operations.first_mut()
+    {
+        op.auth = simulation.auth.clone().try_into()?;
+    }
+    envelope.tx.operations = operations.try_into()?;
+    if let Some(data) = &simulation.transaction_data {
+        envelope.tx.ext = TransactionExt::V1(data.clone());
+    }
+    envelope.tx.fee = envelope
+        .tx
+        .fee
+        .saturating_add(u32::try_from(simulation.min_resource_fee).unwrap_or(u32::MAX));
+    Ok(())
+}
@@ -0,0 +1,270 @@
+//! Off-chain decoder for NovaFund contract events
+//!
+//! Contracts publish the `#[contracttype]` structs in [`shared::events`] with
+//! the topics `(name, schema version, entity id)`. [`EventDecoder`] turns the
+//! raw XDR of such an event, as returned by RPC `getEvents` or captured in
+//! tests, back into the typed struct wrapped in an [`Event`].
+use std::fmt;
+
+use shared::events::{self as ev, NovaFundEvent, EVENT_SCHEMA_VERSION};
+use soroban_sdk::{
+    testutils::Events as _,
+    xdr::{
+        ContractEvent, ContractEventBody, ContractEventType, ContractEventV0, ExtensionPoint,
+        Limits, ReadXdr, ScAddress, ScVal,
+    },
+    Env, TryFromVal, Val,
+};
+
+macro_rules! events {
+    ($($event:ident),* $(,)?) => {
+        /// A decoded NovaFund event
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub enum Event {
+            $($event(ev::$event),)*
+        }
+
+        impl Event {
+            /// Event name as published in the first topic
+            pub fn name(&self) -> String {
+                match self {
+                    $(Event::$event(_) => <ev::$event as NovaFundEvent>::NAME.to_string(),)*
+                }
+            }
+        }
+
+        fn decode_payload(env: &Env, name: &str, data: &ScVal) -> Result<Event, DecodeError> {
+            $(
+                if name == <ev::$event as NovaFundEvent>::NAME.to_string() {
+                    return ev::$event::try_from_val(env, data)
+                        .map(Event::$event)
+                        .map_err(|_| DecodeError::InvalidPayload(name.to_string()));
+                }
+            )*
+            Err(DecodeError::UnknownEvent(name.to_string()))
+        }
+    };
+}
+
+events!(
+    ProjectCreated,
+    ProjectFailed,
+    ContributionMade,
+    RefundIssued,
+    EscrowInitialized,
+    FundsLocked,
+    FundsReleased,
+    TokenFundsLocked,
+    TokenFundsReleased,
+    MilestoneCreated,
+    MilestoneSubmitted,
+    MilestoneApproved,
+    MilestoneRejected,
+    ValidatorsUpdated,
+    ValidatorChangeProposed,
+    ValidatorChangeApproved,
+    BackerVoteCast,
+    ValidatorBonded,
+    ValidatorUnbonded,
+    ValidatorWithdrawn,
+    ValidatorSlashed,
+    ValidatorFeesClaimed,
+    ValidatorFeesForfeited,
+    DisputeOpened,
+    DisputeVoteCast,
+    DisputeResolved,
+    YieldInvested,
+    YieldDivested,
+    YieldHarvested,
+    ProfitDistributed,
+    DividendClaimed,
+    SharesTransferred,
+    SharesApproved,
+    SharesBurned,
+    DustSwept,
+    DividendsCompounded,
+    RoundOpened,
+    RoundClaimed,
+    RoundClosed,
+    WaterfallPaid,
+    ProposalCreated,
+    VoteCast,
+    ProposalExecuted,
+    UserRegistered,
+    ReputationUpdated,
+    BadgeEarned,
+    PaymentSetup,
+    PaymentReceived,
+    PaymentWithdrawn,
+    PoolCreated,
+    SubscriptionCreated,
+    SubscriptionCancelled,
+    SubscriptionModified,
+    SubscriptionPaused,
+    SubscriptionResumed,
+    PaymentFailed,
+    SubscriptionPayment,
+    PoolWithdrawn,
+    BridgeInitialized,
+    SupportedChainAdded,
+    SupportedChainRemoved,
+    AssetWrapped,
+    BridgeDeposit,
+    BridgeWithdraw,
+    BridgeTxConfirmed,
+    BridgePaused,
+    BridgeUnpaused,
+    RelayerAdded,
+    RelayerRemoved,
+);
+
+/// A NovaFund event together with where it came from
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodedEvent {
+    /// Strkey (`C...`) of the emitting contract, when known
+    pub contract_id: Option<String>,
+    pub version: u32,
+    /// Third topic: the project, pool, proposal, ... the event is about
+    pub entity: ScVal,
+    pub event: Event,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// Not a contract event, or its topics don't follow the NovaFund layout
+    NotNovaFundEvent,
+    UnsupportedVersion(u32),
+    UnknownEvent(String),
+    /// The payload doesn't match the struct registered for the event name
+    InvalidPayload(String),
+    Xdr(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::NotNovaFundEvent => write!(f, "not a NovaFund event"),
+            DecodeError::UnsupportedVersion(v) => {
+                write!(f, "unsupported event schema version {v}")
+            }
+            DecodeError::UnknownEvent(name) => write!(f, "unknown event `{name}`"),
+            DecodeError::InvalidPayload(name) => write!(f, "invalid payload for `{name}`"),
+            DecodeError::Xdr(err) => write!(f, "invalid XDR: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<stellar_xdr::curr::Error> for DecodeError {
+    fn from(err: stellar_xdr::curr::Error) -> Self {
+        DecodeError::Xdr(err.to_string())
+    }
+}
+
+/// Decodes NovaFund events from their XDR representation
+pub struct EventDecoder {
+    env: Env,
+}
+
+impl Default for EventDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventDecoder {
+    pub fn new() -> Self {
+        Self {
+            env: Env::default(),
+        }
+    }
+
+    /// Decode an event from its topics and data
+    pub fn decode(&self, topics: &[ScVal], data: &ScVal) -> Result<DecodedEvent, DecodeError> {
+        let [ScVal::Symbol(name), ScVal::U32(version), entity] = topics else {
+            return Err(DecodeError::NotNovaFundEvent);
+        };
+        if *version != EVENT_SCHEMA_VERSION {
+            return Err(DecodeError::UnsupportedVersion(*version));
+        }
+        let name = name.to_utf8_string_lossy();
+        Ok(DecodedEvent {
+            contract_id: None,
+            version: *version,
+            entity: entity.clone(),
+            event: decode_payload(&self.env, &name, data)?,
+        })
+    }
+
+    /// Decode a contract event, e.g. one read from transaction meta
+    pub fn decode_event(&self, event: &ContractEvent) -> Result<DecodedEvent, DecodeError> {
+        if event.type_ != ContractEventType::Contract {
+            return Err(DecodeError::NotNovaFundEvent);
+        }
+        let ContractEventBody::V0(body) = &event.body;
+        let mut decoded = self.decode(&body.topics, &body.data)?;
+        decoded.contract_id = event
+            .contract_id
+            .as_ref()
+            .map(|id| ScAddress::Contract(id.clone()).to_string());
+        Ok(decoded)
+    }
+
+    /// Decode a base64 `ContractEvent` XDR
+    pub fn decode_xdr(&self, xdr: &str) -> Result<DecodedEvent, DecodeError> {
+        self.decode_event(&ContractEvent::from_xdr_base64(xdr, Limits::none())?)
+    }
+
+    /// Decode an event in the shape RPC `getEvents` returns it: base64
+    /// `ScVal` topics and value
+    pub fn decode_rpc<S: AsRef<str>>(
+        &self,
+        contract_id: Option<&str>,
+        topics: &[S],
+        value: &str,
+    ) -> Result<DecodedEvent, DecodeError> {
+        let topics = topics
+            .iter()
+            .map(|topic| ScVal::from_xdr_base64(topic.as_ref(), Limits::none()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let data = ScVal::from_xdr_base64(value, Limits::none())?;
+        let mut decoded = self.decode(&topics, &data)?;
+        decoded.contract_id = contract_id.map(str::to_string);
+        Ok(decoded)
+    }
+}
+
+/// Contract events published so far in a test `Env`, as XDR
+pub fn captured_events(env: &Env) -> Vec<ContractEvent> {
+    env.events()
+        .all()
+        .iter()
+        .map(|(contract, topics, data)| {
+            let ScAddress::Contract(contract_id) = ScAddress::from(&contract) else {
+                unreachable!("events are always published by contracts")
+            };
+            ContractEvent {
+                ext: ExtensionPoint::V0,
+                contract_id: Some(contract_id),
+                type_: ContractEventType::Contract,
+                body: ContractEventBody::V0(ContractEventV0 {
+                    topics: topics
+                        .iter()
+                        .map(|topic| to_sc_val(env, topic))
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap(),
+                    data: to_sc_val(env, data),
+                }),
+            }
+        })
+        .collect()
+}
+
+fn to_sc_val(env: &Env, val: Val) -> ScVal {
+    ScVal::try_from_val(env, &val).unwrap()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,133 @@
+use super::*;
+use shared::events::{
+    publish, ContributionMade, ALL_EVENTS, CONTRIBUTION_MADE, PROFIT_DISTRIBUTED,
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, xdr::WriteXdr, Address,
+};
+
+#[contract]
+struct Emitter;
+
+#[contractimpl]
+impl Emitter {
+    pub fn contribute(env: Env, project_id: u64, contributor: Address, amount: i128) {
+        publish(
+            &env,
+            ContributionMade {
+                project_id,
+                contributor,
+                amount,
+                total_raised: amount,
+            },
+        );
+    }
+
+    pub fn raw(env: Env, name: soroban_sdk::Symbol, version: u32, data: i128) {
+        env.events().publish((name, version, 1u64), data);
+    }
+}
+
+fn setup() -> (Env, Address, EmitterClient<'static>) {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Emitter);
+    let client = EmitterClient::new(&env, &contract_id);
+    (env, contract_id, client)
+}
+
+#[test]
+fn test_decode_captured_event() {
+    let (env, contract_id, client) = setup();
+    let contributor = Address::generate(&env);
+    client.contribute(&7, &contributor, &500);
+
+    let events = captured_events(&env);
+    assert_eq!(events.len(), 1);
+
+    let decoded = EventDecoder::new().decode_event(&events[0]).unwrap();
+    assert_eq!(
+        decoded.contract_id,
+        Some(contract_id.to_string().to_string())
+    );
+    assert_eq!(decoded.version, EVENT_SCHEMA_VERSION);
+    assert_eq!(decoded.entity, ScVal::U64(7));
+    assert_eq!(decoded.event.name(), "contrib");
+    let Event::ContributionMade(event) = decoded.event else {
+        panic!("unexpected event {:?}", decoded.event);
+    };
+    assert_eq!(event.project_id, 7);
+    assert_eq!(event.amount, 500);
+    assert_eq!(event.total_raised, 500);
+    assert_eq!(
+        ScAddress::from(&event.contributor),
+        ScAddress::from(&contributor)
+    );
+}
+
+#[test]
+fn test_decode_base64() {
+    let (env, _, client) = setup();
+    client.contribute(&7, &Address::generate(&env), &500);
+    let event = captured_events(&env).remove(0);
+    let decoder = EventDecoder::new();
+
+    let from_xdr = decoder
+        .decode_xdr(&event.to_xdr_base64(Limits::none()).unwrap())
+        .unwrap();
+    assert_eq!(from_xdr, decoder.decode_event(&event).unwrap());
+
+    let ContractEventBody::V0(body) = &event.body;
+    let topics: Vec<String> = body
+        .topics
+        .iter()
+        .map(|topic| topic.to_xdr_base64(Limits::none()).unwrap())
+        .collect();
+    let value = body.data.to_xdr_base64(Limits::none()).unwrap();
+    let from_rpc = decoder.decode_rpc(None, &topics, &value).unwrap();
+    assert_eq!(from_rpc.event, from_xdr.event);
+    assert_eq!(from_rpc.contract_id, None);
+
+    assert!(matches!(
+        decoder.decode_xdr("not xdr"),
+        Err(DecodeError::Xdr(_))
+    ));
+}
+
+#[test]
+fn test_decode_rejects_foreign_events() {
+    let (env, _, client) = setup();
+    let decoder = EventDecoder::new();
+
+    client.raw(&CONTRIBUTION_MADE, &(EVENT_SCHEMA_VERSION + 1), &5);
+    assert_eq!(
+        decoder.decode_event(captured_events(&env).last().unwrap()),
+        Err(DecodeError::UnsupportedVersion(EVENT_SCHEMA_VERSION + 1))
+    );
+
+    client.raw(&symbol_short!("unknown"), &EVENT_SCHEMA_VERSION, &5);
+    assert_eq!(
+        decoder.decode_event(captured_events(&env).last().unwrap()),
+        Err(DecodeError::UnknownEvent("unknown".to_string()))
+    );
+
+    // Right name, wrong payload shape
+    client.raw(&PROFIT_DISTRIBUTED, &EVENT_SCHEMA_VERSION, &5);
+    assert_eq!(
+        decoder.decode_event(captured_events(&env).last().unwrap()),
+        Err(DecodeError::InvalidPayload("profit".to_string()))
+    );
+
+    assert_eq!(
+        decoder.decode(&[ScVal::U32(EVENT_SCHEMA_VERSION)], &ScVal::Void),
+        Err(DecodeError::NotNovaFundEvent)
+    );
+}
+
+#[test]
+fn test_every_event_name_is_decodable() {
+    let env = Env::default();
+    for name in ALL_EVENTS {
+        let result = decode_payload(&env, &name.to_string(), &ScVal::Void);
+        assert_eq!(result, Err(DecodeError::InvalidPayload(name.to_string())));
+    }
+}
@@ -0,0 +1,194 @@
+//! Off-chain indexer for NovaFund contract events
+//!
+//! Builds the lists and aggregates the frontend needs (projects with their
+//! contributions and milestones, dividends, subscriptions, bridge
+//! transactions) in a SQLite database by replaying the events the contracts
+//! publish. Events can come from a JSON file in the RPC `getEvents` shape,
+//! a file of `ContractEvent` XDR, or anything implementing [`EventSource`].
+//!
+//! Every event is applied at most once, keyed by its RPC id, so overlapping
+//! pages or re-read files are harmless. The indexer assumes one NovaFund
+//! deployment: project, pool and bridge tx ids are not scoped by contract.
+use std::{fmt, path::Path};
+
+use novafund_events::{DecodeError, EventDecoder};
+use rusqlite::{params, Connection, OptionalExtension};
+
+mod queries;
+mod schema;
+mod source;
+mod store;
+
+pub use queries::*;
+pub use source::{read_json_file, read_xdr_file, EventSource, MockRpc, RpcEvent};
+
+/// Events requested per page by [`Indexer::sync`]
+pub const PAGE_SIZE: usize = 100;
+
+#[derive(Debug)]
+pub enum IndexError {
+    Decode(DecodeError),
+    Sql(rusqlite::Error),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// An amount or id too large for an SQLite integer
+    Overflow(i128),
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexError::Decode(err) => write!(f, "decode error: {err}"),
+            IndexError::Sql(err) => write!(f, "database error: {err}"),
+            IndexError::Io(err) => write!(f, "io error: {err}"),
+            IndexError::Json(err) => write!(f, "invalid JSON: {err}"),
+            IndexError::Overflow(value) => write!(f, "{value} does not fit in the database"),
+        }
+    }
+}
+
+impl std::error::Error for IndexError {}
+
+impl From<DecodeError> for IndexError {
+    fn from(err: DecodeError) -> Self {
+        IndexError::Decode(err)
+    }
+}
+
+impl From<rusqlite::Error> for IndexError {
+    fn from(err: rusqlite::Error) -> Self {
+        IndexError::Sql(err)
+    }
+}
+
+impl From<std::io::Error> for IndexError {
+    fn from(err: std::io::Error) -> Self {
+        IndexError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for IndexError {
+    fn from(err: serde_json::Error) -> Self {
+        IndexError::Json(err)
+    }
+}
+
+pub struct Indexer {
+    conn: Connection,
+    decoder: EventDecoder,
+}
+
+impl Indexer {
+    /// Open (or create) the database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, IndexError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Self, IndexError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, IndexError> {
+        conn.execute_batch(schema::SCHEMA)?;
+        Ok(Self {
+            conn,
+            decoder: EventDecoder::new(),
+        })
+    }
+
+    /// Apply one event. Returns false if it was already indexed or isn't a
+    /// NovaFund event (token transfers, other contracts, ...).
+    pub fn index(&mut self, event: &RpcEvent) -> Result<bool, IndexError> {
+        let seen = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM events WHERE id = ?1",
+                params![event.id],
+                |_| Ok(()),
+            )
+            .optional()?;
+        if seen.is_some() {
+            return Ok(false);
+        }
+
+        let decoded = match event.decode(&self.decoder) {
+            Ok(decoded) => decoded,
+            Err(DecodeError::NotNovaFundEvent | DecodeError::UnknownEvent(_)) => return Ok(false),
+            Err(err) => return Err(err.into()),
+        };
+
+        let tx = self.conn.transaction()?;
+        store::apply(&tx, &decoded.event, event.ledger)?;
+        tx.execute(
+            "INSERT INTO events (id, ledger, contract_id, name) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                event.id,
+                event.ledger,
+                event.contract_id,
+                decoded.event.name()
+            ],
+        )?;
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Apply events in order, returning how many were applied
+    pub fn index_all<'a>(
+        &mut self,
+        events: impl IntoIterator<Item = &'a RpcEvent>,
+    ) -> Result<usize, IndexError> {
+        let mut applied = 0;
+        for event in events {
+            if self.index(event)? {
+                applied += 1;
+            }
+        }
+        Ok(applied)
+    }
+
+    pub fn index_json_file(&mut self, path: impl AsRef<Path>) -> Result<usize, IndexError> {
+        self.index_all(&read_json_file(path)?)
+    }
+
+    pub fn index_xdr_file(&mut self, path: impl AsRef<Path>) -> Result<usize, IndexError> {
+        self.index_all(&read_xdr_file(path)?)
+    }
+
+    /// Page through `source` from the stored cursor until it has nothing
+    /// new, returning how many events were applied
+    pub fn sync(&mut self, source: &mut impl EventSource) -> Result<usize, IndexError> {
+        let mut applied = 0;
+        loop {
+            let cursor = self.cursor()?;
+            let page = source.events(cursor.as_deref(), PAGE_SIZE)?;
+            let Some(last) = page.last() else {
+                return Ok(applied);
+            };
+            applied += self.index_all(&page)?;
+            self.conn.execute(
+                "INSERT OR REPLACE INTO meta (key, value) VALUES ('cursor', ?1)",
+                params![last.id],
+            )?;
+        }
+    }
+
+    /// Id of the last event read by [`Indexer::sync`]
+    pub fn cursor(&self) -> Result<Option<String>, IndexError> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM meta WHERE key = 'cursor'", [], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    /// Number of NovaFund events applied so far
+    pub fn event_count(&self) -> Result<u64, IndexError> {
+        Ok(self
+            .conn
+            .query_row("SELECT COUNT(*) FROM events", [], |row| row.get(0))?)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,402 @@
+//! Read side: lists and aggregates the contracts don't store
+
+use rusqlite::{params, OptionalExtension, Row};
+use serde::Serialize;
+
+use crate::{IndexError, Indexer};
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Project {
+    pub project_id: u64,
+    pub creator: String,
+    pub funding_goal: i64,
+    pub deadline: u64,
+    pub token: String,
+    pub total_raised: i64,
+    pub total_refunded: i64,
+    /// `active` or `failed`
+    pub status: String,
+    pub created_ledger: u32,
+    pub contributor_count: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Contribution {
+    pub project_id: u64,
+    pub contributor: String,
+    pub amount: i64,
+    pub refunded: bool,
+    pub ledger: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Milestone {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub amount: i64,
+    pub description_hash: String,
+    pub proof_hash: Option<String>,
+    /// `pending`, `submitted`, `approved`, `rejected` or `disputed`
+    pub status: String,
+    pub approval_count: u32,
+    pub rejection_count: u32,
+    pub released_amount: i64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Dividend {
+    pub project_id: u64,
+    pub investor: String,
+    pub token: String,
+    pub amount: i64,
+    pub ledger: u32,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ProfitSummary {
+    pub deposited: i64,
+    pub claimed: i64,
+    pub claimants: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Pool {
+    pub pool_id: u64,
+    pub name: String,
+    pub total_paid: i64,
+    pub total_withdrawn: i64,
+    pub active_subscriptions: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Subscription {
+    pub pool_id: u64,
+    pub subscriber: String,
+    /// `active`, `paused` or `cancelled`
+    pub status: String,
+    /// Unknown until the first payment or modification
+    pub amount: Option<i64>,
+    pub period: Option<u32>,
+    pub total_paid: i64,
+    pub failure_count: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct BridgeTx {
+    pub tx_id: u64,
+    /// `deposit` or `withdraw`
+    pub direction: String,
+    pub chain_id: Option<u32>,
+    pub account: String,
+    pub asset: String,
+    pub amount: i64,
+    /// `pending` or `confirmed`
+    pub status: String,
+    pub source_tx_hash: Option<String>,
+    pub external_tx_hash: Option<String>,
+    pub ledger: u32,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct PlatformStats {
+    pub projects: u32,
+    pub failed_projects: u32,
+    pub total_raised: i64,
+    pub contributors: u32,
+    pub milestones_approved: u32,
+    pub total_released: i64,
+    pub dividends_paid: i64,
+    pub active_subscriptions: u32,
+    pub bridge_txs: u32,
+}
+
+const PROJECT: &str = "SELECT p.project_id, p.creator, p.funding_goal, p.deadline, p.token,
+        p.total_raised, p.total_refunded, p.status, p.created_ledger,
+        (SELECT COUNT(DISTINCT c.contributor) FROM contributions c
+         WHERE c.project_id = p.project_id)
+    FROM projects p";
+
+const CONTRIBUTION: &str =
+    "SELECT project_id, contributor, amount, refunded, ledger FROM contributions";
+
+const MILESTONE: &str = "SELECT project_id, milestone_id, amount, description_hash, proof_hash,
+        status, approval_count, rejection_count, released_amount
+    FROM milestones";
+
+const POOL: &str = "SELECT p.pool_id, p.name, p.total_paid, p.total_withdrawn,
+        (SELECT COUNT(*) FROM subscriptions s
+         WHERE s.pool_id = p.pool_id AND s.status = 'active')
+    FROM pools p";
+
+const SUBSCRIPTION: &str = "SELECT pool_id, subscriber, status, amount, period, total_paid,
+        failure_count
+    FROM subscriptions";
+
+const BRIDGE_TX: &str = "SELECT tx_id, direction, chain_id, account, asset, amount, status,
+        source_tx_hash, external_tx_hash, ledger
+    FROM bridge_txs";
+
+impl Indexer {
+    pub fn project(&self, project_id: u64) -> Result<Option<Project>, IndexError> {
+        Ok(self
+            .conn
+            .query_row(
+                &format!("{PROJECT} WHERE p.project_id = ?1"),
+                params![project_id as i64],
+                project,
+            )
+            .optional()?)
+    }
+
+    /// Projects, optionally only those with the given status, by id
+    pub fn projects(&self, status: Option<&str>) -> Result<Vec<Project>, IndexError> {
+        self.query(
+            &format!("{PROJECT} WHERE ?1 IS NULL OR p.status = ?1 ORDER BY p.project_id"),
+            params![status],
+            project,
+        )
+    }
+
+    pub fn projects_by_creator(&self, creator: &str) -> Result<Vec<Project>, IndexError> {
+        self.query(
+            &format!("{PROJECT} WHERE p.creator = ?1 ORDER BY p.project_id"),
+            params![creator],
+            project,
+        )
+    }
+
+    /// Projects that raised the most
+    pub fn top_projects(&self, limit: u32) -> Result<Vec<Project>, IndexError> {
+        self.query(
+            &format!("{PROJECT} ORDER BY p.total_raised DESC, p.project_id LIMIT ?1"),
+            params![limit],
+            project,
+        )
+    }
+
+    pub fn contributions(&self, project_id: u64) -> Result<Vec<Contribution>, IndexError> {
+        self.query(
+            &format!("{CONTRIBUTION} WHERE project_id = ?1 ORDER BY id"),
+            params![project_id as i64],
+            contribution,
+        )
+    }
+
+    pub fn contributions_by(&self, contributor: &str) -> Result<Vec<Contribution>, IndexError> {
+        self.query(
+            &format!("{CONTRIBUTION} WHERE contributor = ?1 ORDER BY id"),
+            params![contributor],
+            contribution,
+        )
+    }
+
+    pub fn milestones(&self, project_id: u64) -> Result<Vec<Milestone>, IndexError> {
+        self.query(
+            &format!("{MILESTONE} WHERE project_id = ?1 ORDER BY milestone_id"),
+            params![project_id as i64],
+            milestone,
+        )
+    }
+
+    /// Profits deposited and claimed in one of a project's reward tokens
+    pub fn profit_summary(
+        &self,
+        project_id: u64,
+        token: &str,
+    ) -> Result<ProfitSummary, IndexError> {
+        Ok(self.conn.query_row(
+            "SELECT
+                (SELECT COALESCE(SUM(amount), 0) FROM profit_deposits
+                 WHERE project_id = ?1 AND token = ?2),
+                (SELECT COALESCE(SUM(amount), 0) FROM dividends
+                 WHERE project_id = ?1 AND token = ?2),
+                (SELECT COUNT(DISTINCT investor) FROM dividends
+                 WHERE project_id = ?1 AND token = ?2)",
+            params![project_id as i64, token],
+            |row| {
+                Ok(ProfitSummary {
+                    deposited: row.get(0)?,
+                    claimed: row.get(1)?,
+                    claimants: row.get(2)?,
+                })
+            },
+        )?)
+    }
+
+    pub fn dividends_by(&self, investor: &str) -> Result<Vec<Dividend>, IndexError> {
+        self.query(
+            "SELECT project_id, investor, token, amount, ledger FROM dividends
+             WHERE investor = ?1 ORDER BY id",
+            params![investor],
+            |row| {
+                Ok(Dividend {
+                    project_id: row.get::<_, i64>(0)? as u64,
+                    investor: row.get(1)?,
+                    token: row.get(2)?,
+                    amount: row.get(3)?,
+                    ledger: row.get(4)?,
+                })
+            },
+        )
+    }
+
+    pub fn pool(&self, pool_id: u64) -> Result<Option<Pool>, IndexError> {
+        Ok(self
+            .conn
+            .query_row(
+                &format!("{POOL} WHERE p.pool_id = ?1"),
+                params![pool_id as i64],
+                |row| {
+                    Ok(Pool {
+                        pool_id: row.get::<_, i64>(0)? as u64,
+                        name: row.get(1)?,
+                        total_paid: row.get(2)?,
+                        total_withdrawn: row.get(3)?,
+                        active_subscriptions: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?)
+    }
+
+    pub fn subscriptions(&self, pool_id: u64) -> Result<Vec<Subscription>, IndexError> {
+        self.query(
+            &format!("{SUBSCRIPTION} WHERE pool_id = ?1 ORDER BY subscriber"),
+            params![pool_id as i64],
+            subscription,
+        )
+    }
+
+    pub fn subscriptions_by(&self, subscriber: &str) -> Result<Vec<Subscription>, IndexError> {
+        self.query(
+            &format!("{SUBSCRIPTION} WHERE subscriber = ?1 ORDER BY pool_id"),
+            params![subscriber],
+            subscription,
+        )
+    }
+
+    pub fn bridge_tx(&self, tx_id: u64) -> Result<Option<BridgeTx>, IndexError> {
+        Ok(self
+            .conn
+            .query_row(
+                &format!("{BRIDGE_TX} WHERE tx_id = ?1"),
+                params![tx_id as i64],
+                bridge_tx,
+            )
+            .optional()?)
+    }
+
+    pub fn bridge_txs_by(&self, account: &str) -> Result<Vec<BridgeTx>, IndexError> {
+        self.query(
+            &format!("{BRIDGE_TX} WHERE account = ?1 ORDER BY tx_id"),
+            params![account],
+            bridge_tx,
+        )
+    }
+
+    /// Platform-wide totals for dashboards
+    pub fn stats(&self) -> Result<PlatformStats, IndexError> {
+        Ok(self.conn.query_row(
+            "SELECT
+                (SELECT COUNT(*) FROM projects),
+                (SELECT COUNT(*) FROM projects WHERE status = 'failed'),
+                (SELECT COALESCE(SUM(total_raised), 0) FROM projects),
+                (SELECT COUNT(DISTINCT contributor) FROM contributions),
+                (SELECT COUNT(*) FROM milestones WHERE status = 'approved'),
+                (SELECT COALESCE(SUM(released_amount), 0) FROM milestones),
+                (SELECT COALESCE(SUM(amount), 0) FROM dividends),
+                (SELECT COUNT(*) FROM subscriptions WHERE status = 'active'),
+                (SELECT COUNT(*) FROM bridge_txs)",
+            [],
+            |row| {
+                Ok(PlatformStats {
+                    projects: row.get(0)?,
+                    failed_projects: row.get(1)?,
+                    total_raised: row.get(2)?,
+                    contributors: row.get(3)?,
+                    milestones_approved: row.get(4)?,
+                    total_released: row.get(5)?,
+                    dividends_paid: row.get(6)?,
+                    active_subscriptions: row.get(7)?,
+                    bridge_txs: row.get(8)?,
+                })
+            },
+        )?)
+    }
+
+    fn query<T, P, F>(&self, sql: &str, params: P, f: F) -> Result<Vec<T>, IndexError>
+    where
+        P: rusqlite::Params,
+        F: FnMut(&Row) -> rusqlite::Result<T>,
+    {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params, f)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+}
+
+fn project(row: &Row) -> rusqlite::Result<Project> {
+    Ok(Project {
+        project_id: row.get::<_, i64>(0)? as u64,
+        creator: row.get(1)?,
+        funding_goal: row.get(2)?,
+        deadline: row.get::<_, i64>(3)? as u64,
+        token: row.get(4)?,
+        total_raised: row.get(5)?,
+        total_refunded: row.get(6)?,
+        status: row.get(7)?,
+        created_ledger: row.get(8)?,
+        contributor_count: row.get(9)?,
+    })
+}
+
+fn contribution(row: &Row) -> rusqlite::Result<Contribution> {
+    Ok(Contribution {
+        project_id: row.get::<_, i64>(0)? as u64,
+        contributor: row.get(1)?,
+        amount: row.get(2)?,
+        refunded: row.get(3)?,
+        ledger: row.get(4)?,
+    })
+}
+
+fn milestone(row: &Row) -> rusqlite::Result<Milestone> {
+    Ok(Milestone {
+        project_id: row.get::<_, i64>(0)? as u64,
+        milestone_id: row.get::<_, i64>(1)? as u64,
+        amount: row.get(2)?,
+        description_hash: row.get(3)?,
+        proof_hash: row.get(4)?,
+        status: row.get(5)?,
+        approval_count: row.get(6)?,
+        rejection_count: row.get(7)?,
+        released_amount: row.get(8)?,
+    })
+}
+
+fn subscription(row: &Row) -> rusqlite::Result<Subscription> {
+    Ok(Subscription {
+        pool_id: row.get::<_, i64>(0)? as u64,
+        subscriber: row.get(1)?,
+        status: row.get(2)?,
+        amount: row.get(3)?,
+        period: row.get(4)?,
+        total_paid: row.get(5)?,
+        failure_count: row.get(6)?,
+    })
+}
+
+fn bridge_tx(row: &Row) -> rusqlite::Result<BridgeTx> {
+    Ok(BridgeTx {
+        tx_id: row.get::<_, i64>(0)? as u64,
+        direction: row.get(1)?,
+        chain_id: row.get(2)?,
+        account: row.get(3)?,
+        asset: row.get(4)?,
+        amount: row.get(5)?,
+        status: row.get(6)?,
+        source_tx_hash: row.get(7)?,
+        external_tx_hash: row.get(8)?,
+        ledger: row.get(9)?,
+    })
+}
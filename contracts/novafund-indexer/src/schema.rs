@@ -0,0 +1,109 @@
+//! SQLite schema maintained by the indexer
+//!
+//! Amounts are stored as INTEGER (i64); an event carrying an amount that
+//! doesn't fit is rejected rather than truncated. Addresses are strkeys and
+//! hashes are lowercase hex.
+
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS events (
+    id TEXT PRIMARY KEY,
+    ledger INTEGER NOT NULL,
+    contract_id TEXT,
+    name TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS projects (
+    project_id INTEGER PRIMARY KEY,
+    creator TEXT NOT NULL,
+    funding_goal INTEGER NOT NULL,
+    deadline INTEGER NOT NULL,
+    token TEXT NOT NULL,
+    total_raised INTEGER NOT NULL DEFAULT 0,
+    total_refunded INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'active',
+    created_ledger INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS contributions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    contributor TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    refunded INTEGER NOT NULL DEFAULT 0,
+    ledger INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS contributions_project ON contributions (project_id);
+CREATE INDEX IF NOT EXISTS contributions_contributor ON contributions (contributor);
+
+CREATE TABLE IF NOT EXISTS milestones (
+    project_id INTEGER NOT NULL,
+    milestone_id INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    description_hash TEXT NOT NULL,
+    proof_hash TEXT,
+    status TEXT NOT NULL,
+    -- Status a pending dispute would restore if dismissed
+    disputed_status TEXT,
+    approval_count INTEGER NOT NULL DEFAULT 0,
+    rejection_count INTEGER NOT NULL DEFAULT 0,
+    released_amount INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (project_id, milestone_id)
+);
+
+CREATE TABLE IF NOT EXISTS profit_deposits (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    token TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    ledger INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS dividends (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    investor TEXT NOT NULL,
+    token TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    ledger INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS dividends_investor ON dividends (investor);
+
+CREATE TABLE IF NOT EXISTS pools (
+    pool_id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    total_paid INTEGER NOT NULL DEFAULT 0,
+    total_withdrawn INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS subscriptions (
+    pool_id INTEGER NOT NULL,
+    subscriber TEXT NOT NULL,
+    status TEXT NOT NULL,
+    amount INTEGER,
+    period INTEGER,
+    total_paid INTEGER NOT NULL DEFAULT 0,
+    failure_count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (pool_id, subscriber)
+);
+CREATE INDEX IF NOT EXISTS subscriptions_subscriber ON subscriptions (subscriber);
+
+-- `account` is the Stellar recipient of a deposit, or the destination-chain
+-- recipient (hex) of a withdrawal
+CREATE TABLE IF NOT EXISTS bridge_txs (
+    tx_id INTEGER PRIMARY KEY,
+    direction TEXT NOT NULL,
+    chain_id INTEGER,
+    account TEXT NOT NULL,
+    asset TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    source_tx_hash TEXT,
+    external_tx_hash TEXT,
+    ledger INTEGER NOT NULL
+);
+";
@@ -0,0 +1,110 @@
+//! Where events come from: RPC-shaped JSON, XDR files or an RPC stand-in
+
+use std::{fs, path::Path};
+
+use novafund_events::{DecodeError, DecodedEvent, EventDecoder};
+use serde::{Deserialize, Serialize};
+use soroban_sdk::xdr::{ContractEvent, ContractEventBody, Limits, ReadXdr, ScAddress, WriteXdr};
+
+use crate::IndexError;
+
+/// An event in the shape RPC `getEvents` returns it
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEvent {
+    /// Paging token, ordered by ledger and position within the ledger
+    pub id: String,
+    pub ledger: u32,
+    pub contract_id: Option<String>,
+    /// Base64 `ScVal` topics
+    pub topic: Vec<String>,
+    /// Base64 `ScVal` data
+    pub value: String,
+}
+
+impl RpcEvent {
+    pub fn from_contract_event(
+        id: String,
+        ledger: u32,
+        event: &ContractEvent,
+    ) -> Result<Self, IndexError> {
+        let ContractEventBody::V0(body) = &event.body;
+        Ok(Self {
+            id,
+            ledger,
+            contract_id: event
+                .contract_id
+                .as_ref()
+                .map(|id| ScAddress::Contract(id.clone()).to_string()),
+            topic: body
+                .topics
+                .iter()
+                .map(|topic| topic.to_xdr_base64(Limits::none()))
+                .collect::<Result<_, _>>()
+                .map_err(DecodeError::from)?,
+            value: body
+                .data
+                .to_xdr_base64(Limits::none())
+                .map_err(DecodeError::from)?,
+        })
+    }
+
+    pub fn decode(&self, decoder: &EventDecoder) -> Result<DecodedEvent, DecodeError> {
+        decoder.decode_rpc(self.contract_id.as_deref(), &self.topic, &self.value)
+    }
+}
+
+/// Something that can be paged for events, like RPC `getEvents`
+pub trait EventSource {
+    /// Up to `limit` events after the `cursor` event id, oldest first
+    fn events(&mut self, cursor: Option<&str>, limit: usize) -> Result<Vec<RpcEvent>, IndexError>;
+}
+
+/// In-memory stand-in for an RPC node
+#[derive(Clone, Debug, Default)]
+pub struct MockRpc {
+    events: Vec<RpcEvent>,
+}
+
+impl MockRpc {
+    pub fn new(events: Vec<RpcEvent>) -> Self {
+        Self { events }
+    }
+
+    /// Make more events available, as new ledgers close
+    pub fn push(&mut self, event: RpcEvent) {
+        self.events.push(event);
+    }
+}
+
+impl EventSource for MockRpc {
+    fn events(&mut self, cursor: Option<&str>, limit: usize) -> Result<Vec<RpcEvent>, IndexError> {
+        Ok(self
+            .events
+            .iter()
+            .filter(|event| !matches!(cursor, Some(cursor) if event.id.as_str() <= cursor))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Read a JSON array of [`RpcEvent`]s
+pub fn read_json_file(path: impl AsRef<Path>) -> Result<Vec<RpcEvent>, IndexError> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Read a file with one base64 `ContractEvent` XDR per line. Events get their
+/// line number as id, and ledger 0.
+pub fn read_xdr_file(path: impl AsRef<Path>) -> Result<Vec<RpcEvent>, IndexError> {
+    fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let event = ContractEvent::from_xdr_base64(line.trim(), Limits::none())
+                .map_err(DecodeError::from)?;
+            RpcEvent::from_contract_event(format!("{:020}", i + 1), 0, &event)
+        })
+        .collect()
+}
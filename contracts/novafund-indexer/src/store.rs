@@ -0,0 +1,283 @@
+//! Applies decoded events to the database
+
+use novafund_events::Event;
+use rusqlite::{params, Transaction};
+use soroban_sdk::{Address, BytesN};
+
+use crate::IndexError;
+
+/// Fold one event into the platform tables
+pub(crate) fn apply(tx: &Transaction, event: &Event, ledger: u32) -> Result<(), IndexError> {
+    match event {
+        // Projects and contributions
+        Event::ProjectCreated(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO projects
+                    (project_id, creator, funding_goal, deadline, token, created_ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    id(e.project_id)?,
+                    addr(&e.creator),
+                    amount(e.funding_goal)?,
+                    id(e.deadline)?,
+                    addr(&e.token),
+                    ledger
+                ],
+            )?;
+        }
+        Event::ContributionMade(e) => {
+            tx.execute(
+                "INSERT INTO contributions (project_id, contributor, amount, ledger)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    id(e.project_id)?,
+                    addr(&e.contributor),
+                    amount(e.amount)?,
+                    ledger
+                ],
+            )?;
+            tx.execute(
+                "UPDATE projects SET total_raised = ?2 WHERE project_id = ?1",
+                params![id(e.project_id)?, amount(e.total_raised)?],
+            )?;
+        }
+        Event::ProjectFailed(e) => {
+            tx.execute(
+                "UPDATE projects SET status = 'failed' WHERE project_id = ?1",
+                params![id(e.project_id)?],
+            )?;
+        }
+        Event::RefundIssued(e) => {
+            tx.execute(
+                "UPDATE contributions SET refunded = 1
+                 WHERE project_id = ?1 AND contributor = ?2",
+                params![id(e.project_id)?, addr(&e.contributor)],
+            )?;
+            tx.execute(
+                "UPDATE projects SET total_refunded = total_refunded + ?2 WHERE project_id = ?1",
+                params![id(e.project_id)?, amount(e.amount)?],
+            )?;
+        }
+
+        // Milestones
+        Event::MilestoneCreated(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO milestones
+                    (project_id, milestone_id, amount, description_hash, status)
+                 VALUES (?1, ?2, ?3, ?4, 'pending')",
+                params![
+                    id(e.project_id)?,
+                    id(e.milestone_id)?,
+                    amount(e.amount)?,
+                    hex(&e.description_hash)
+                ],
+            )?;
+        }
+        Event::MilestoneSubmitted(e) => {
+            tx.execute(
+                "UPDATE milestones SET status = 'submitted', proof_hash = ?3
+                 WHERE project_id = ?1 AND milestone_id = ?2",
+                params![id(e.project_id)?, id(e.milestone_id)?, hex(&e.proof_hash)],
+            )?;
+        }
+        Event::MilestoneApproved(e) => {
+            tx.execute(
+                "UPDATE milestones SET status = 'approved', approval_count = ?3
+                 WHERE project_id = ?1 AND milestone_id = ?2",
+                params![id(e.project_id)?, id(e.milestone_id)?, e.approval_count],
+            )?;
+        }
+        Event::MilestoneRejected(e) => {
+            tx.execute(
+                "UPDATE milestones SET status = 'rejected', rejection_count = ?3
+                 WHERE project_id = ?1 AND milestone_id = ?2",
+                params![id(e.project_id)?, id(e.milestone_id)?, e.rejection_count],
+            )?;
+        }
+        Event::FundsReleased(e) => {
+            tx.execute(
+                "UPDATE milestones SET released_amount = released_amount + ?3
+                 WHERE project_id = ?1 AND milestone_id = ?2",
+                params![id(e.project_id)?, id(e.milestone_id)?, amount(e.amount)?],
+            )?;
+        }
+        Event::DisputeOpened(e) => {
+            tx.execute(
+                "UPDATE milestones SET disputed_status = status, status = 'disputed'
+                 WHERE project_id = ?1 AND milestone_id = ?2",
+                params![id(e.project_id)?, id(e.milestone_id)?],
+            )?;
+        }
+        Event::DisputeResolved(e) => {
+            // An upheld dispute reverses the disputed decision, a dismissed
+            // one restores it
+            tx.execute(
+                "UPDATE milestones SET
+                    status = CASE
+                        WHEN ?3 = 0 THEN disputed_status
+                        WHEN disputed_status = 'approved' THEN 'rejected'
+                        ELSE 'approved'
+                    END,
+                    disputed_status = NULL
+                 WHERE project_id = ?1 AND milestone_id = ?2",
+                params![id(e.project_id)?, id(e.milestone_id)?, e.upheld],
+            )?;
+        }
+
+        // Dividends
+        Event::ProfitDistributed(e) => {
+            tx.execute(
+                "INSERT INTO profit_deposits (project_id, token, amount, ledger)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![id(e.project_id)?, addr(&e.token), amount(e.amount)?, ledger],
+            )?;
+        }
+        Event::DividendClaimed(e) => {
+            tx.execute(
+                "INSERT INTO dividends (project_id, investor, token, amount, ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    id(e.project_id)?,
+                    addr(&e.investor),
+                    addr(&e.token),
+                    amount(e.amount)?,
+                    ledger
+                ],
+            )?;
+        }
+
+        // Subscriptions
+        Event::PoolCreated(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO pools (pool_id, name) VALUES (?1, ?2)",
+                params![id(e.pool_id)?, e.name.to_string()],
+            )?;
+        }
+        Event::SubscriptionCreated(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO subscriptions (pool_id, subscriber, status)
+                 VALUES (?1, ?2, 'active')",
+                params![id(e.pool_id)?, addr(&e.subscriber)],
+            )?;
+        }
+        Event::SubscriptionCancelled(e) => {
+            set_subscription_status(tx, e.pool_id, &e.subscriber, "cancelled")?;
+        }
+        Event::SubscriptionPaused(e) => {
+            set_subscription_status(tx, e.pool_id, &e.subscriber, "paused")?;
+        }
+        Event::SubscriptionResumed(e) => {
+            set_subscription_status(tx, e.pool_id, &e.subscriber, "active")?;
+        }
+        Event::SubscriptionModified(e) => {
+            tx.execute(
+                "UPDATE subscriptions SET amount = ?3, period = ?4
+                 WHERE pool_id = ?1 AND subscriber = ?2",
+                params![
+                    id(e.pool_id)?,
+                    addr(&e.subscriber),
+                    amount(e.amount)?,
+                    e.period
+                ],
+            )?;
+        }
+        Event::SubscriptionPayment(e) => {
+            tx.execute(
+                "UPDATE subscriptions
+                 SET amount = ?3, total_paid = total_paid + ?3, failure_count = 0
+                 WHERE pool_id = ?1 AND subscriber = ?2",
+                params![id(e.pool_id)?, addr(&e.subscriber), amount(e.amount)?],
+            )?;
+            tx.execute(
+                "UPDATE pools SET total_paid = total_paid + ?2 WHERE pool_id = ?1",
+                params![id(e.pool_id)?, amount(e.amount)?],
+            )?;
+        }
+        Event::PaymentFailed(e) => {
+            tx.execute(
+                "UPDATE subscriptions SET failure_count = ?3
+                 WHERE pool_id = ?1 AND subscriber = ?2",
+                params![id(e.pool_id)?, addr(&e.subscriber), e.failure_count],
+            )?;
+        }
+        Event::PoolWithdrawn(e) => {
+            tx.execute(
+                "UPDATE pools SET total_withdrawn = total_withdrawn + ?2 WHERE pool_id = ?1",
+                params![id(e.pool_id)?, amount(e.amount)?],
+            )?;
+        }
+
+        // Bridge transactions
+        Event::BridgeDeposit(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO bridge_txs
+                    (tx_id, direction, account, asset, amount, status, source_tx_hash, ledger)
+                 VALUES (?1, 'deposit', ?2, ?3, ?4, 'pending', ?5, ?6)",
+                params![
+                    id(e.tx_id)?,
+                    addr(&e.recipient),
+                    addr(&e.asset),
+                    amount(e.amount)?,
+                    hex(&e.source_tx_hash),
+                    ledger
+                ],
+            )?;
+        }
+        Event::BridgeWithdraw(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO bridge_txs
+                    (tx_id, direction, chain_id, account, asset, amount, status, ledger)
+                 VALUES (?1, 'withdraw', ?2, ?3, ?4, ?5, 'pending', ?6)",
+                params![
+                    id(e.tx_id)?,
+                    e.destination_chain as u32,
+                    hex(&e.recipient),
+                    addr(&e.asset),
+                    amount(e.amount)?,
+                    ledger
+                ],
+            )?;
+        }
+        Event::BridgeTxConfirmed(e) => {
+            tx.execute(
+                "UPDATE bridge_txs SET status = 'confirmed', external_tx_hash = ?2
+                 WHERE tx_id = ?1",
+                params![id(e.tx_id)?, hex(&e.external_tx_hash)],
+            )?;
+        }
+
+        // Validator, governance, reputation, payment and bridge admin events
+        // don't feed any of the tables
+        _ => {}
+    }
+    Ok(())
+}
+
+fn set_subscription_status(
+    tx: &Transaction,
+    pool_id: u64,
+    subscriber: &Address,
+    status: &str,
+) -> Result<(), IndexError> {
+    tx.execute(
+        "UPDATE subscriptions SET status = ?3 WHERE pool_id = ?1 AND subscriber = ?2",
+        params![id(pool_id)?, addr(subscriber), status],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn addr(address: &Address) -> String {
+    address.to_string().to_string()
+}
+
+fn hex(hash: &BytesN<32>) -> String {
+    hash.to_array().iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn amount(value: i128) -> Result<i64, IndexError> {
+    i64::try_from(value).map_err(|_| IndexError::Overflow(value))
+}
+
+fn id(value: u64) -> Result<i64, IndexError> {
+    amount(value as i128)
+}
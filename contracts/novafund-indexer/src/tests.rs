@@ -0,0 +1,393 @@
+use super::*;
+use cross_chain_bridge::{CrossChainBridge, CrossChainBridgeClient};
+use escrow::{EscrowContract, EscrowContractClient};
+use novafund_events::captured_events;
+use profit_distribution::{ProfitDistribution, ProfitDistributionClient};
+use project_launch::{ProjectLaunch, ProjectLaunchClient};
+use shared::{
+    constants::{MIN_FUNDING_GOAL, MIN_PROJECT_DURATION},
+    types::ChainId,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token,
+    xdr::{ContractEvent, Limits, WriteXdr},
+    Address, Bytes, BytesN, Env, Map, String, Vec,
+};
+use subscription_pool::{SubscriptionPeriod, SubscriptionPool, SubscriptionPoolClient};
+
+/// Collects events as RPC would serve them, one ledger per recorded step
+#[derive(Default)]
+struct Recorder {
+    raw: std::vec::Vec<ContractEvent>,
+    events: std::vec::Vec<RpcEvent>,
+}
+
+impl Recorder {
+    fn record(&mut self, env: &Env) {
+        let ledger = env.ledger().sequence();
+        let captured = captured_events(env);
+        for (i, event) in captured.iter().enumerate().skip(self.raw.len()) {
+            let id = format!("{ledger:010}-{i:010}");
+            self.events
+                .push(RpcEvent::from_contract_event(id, ledger, event).unwrap());
+            self.raw.push(event.clone());
+        }
+        env.ledger().set_sequence_number(ledger + 1);
+    }
+}
+
+struct Replay {
+    recorder: Recorder,
+    creator: std::string::String,
+    alice: std::string::String,
+    bob: std::string::String,
+    carol: std::string::String,
+    recipient: std::string::String,
+    token: std::string::String,
+    pool_id: u64,
+}
+
+/// Run every indexed contract through a typical lifecycle and record the
+/// events they publish
+fn replay() -> Replay {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(1000);
+    let mut recorder = Recorder::default();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_admin = token::StellarAssetClient::new(&env, &token_id);
+    for user in [&creator, &alice, &bob, &carol] {
+        token_admin.mint(user, &100_000_000_000);
+    }
+
+    // Project 0 is funded, project 1 fails and refunds
+    let launch = ProjectLaunchClient::new(&env, &env.register_contract(None, ProjectLaunch));
+    launch.initialize(&admin);
+    let deadline = 1000 + MIN_PROJECT_DURATION * 2;
+    let metadata = Bytes::from_slice(&env, b"ipfs://project");
+    launch.create_project(&creator, &MIN_FUNDING_GOAL, &deadline, &token_id, &metadata);
+    launch.create_project(&creator, &MIN_FUNDING_GOAL, &deadline, &token_id, &metadata);
+    launch.contribute(&0, &alice, &600_0000000);
+    launch.contribute(&0, &bob, &500_0000000);
+    launch.contribute(&1, &carol, &50_0000000);
+    recorder.record(&env);
+
+    env.ledger().set_timestamp(deadline + 1);
+    launch.mark_project_failed(&1);
+    launch.refund_contributor(&1, &carol);
+    recorder.record(&env);
+
+    // Escrow for project 0: first milestone approved, second rejected
+    let escrow = EscrowContractClient::new(&env, &env.register_contract(None, EscrowContract));
+    let mut validators = Vec::new(&env);
+    for _ in 0..3 {
+        validators.push_back(Address::generate(&env));
+    }
+    escrow.initialize(&0, &creator, &token_id, &validators);
+    escrow.deposit(&0, &1000_0000000);
+    token_admin.mint(&escrow.address, &1000_0000000);
+    escrow.create_milestone(
+        &0,
+        &BytesN::from_array(&env, &[1; 32]),
+        &400_0000000,
+        &Vec::new(&env),
+    );
+    escrow.create_milestone(
+        &0,
+        &BytesN::from_array(&env, &[2; 32]),
+        &300_0000000,
+        &Vec::new(&env),
+    );
+    escrow.submit_milestone(&0, &0, &BytesN::from_array(&env, &[3; 32]));
+    escrow.vote_milestone(&0, &0, &validators.get(0).unwrap(), &true);
+    escrow.submit_milestone(&0, &1, &BytesN::from_array(&env, &[4; 32]));
+    for validator in validators.iter() {
+        escrow.vote_milestone(&0, &1, &validator, &false);
+    }
+    recorder.record(&env);
+
+    // Profits for project 0, claimed by alice only
+    let profit =
+        ProfitDistributionClient::new(&env, &env.register_contract(None, ProfitDistribution));
+    profit.initialize(&admin);
+    profit.set_token(&0, &token_id);
+    let mut investors = Map::new(&env);
+    investors.set(alice.clone(), 6000);
+    investors.set(bob.clone(), 4000);
+    profit.register_investors(&0, &investors);
+    profit.deposit_profits(&0, &creator, &1000);
+    profit.claim_dividends(&0, &alice);
+    recorder.record(&env);
+
+    // Alice pays into a pool, bob subscribes and cancels
+    let pool = SubscriptionPoolClient::new(&env, &env.register_contract(None, SubscriptionPool));
+    pool.initialize(&admin);
+    let pool_id = pool.create_pool(&String::from_str(&env, "Alpha"), &token_id);
+    pool.subscribe(&pool_id, &alice, &1000, &SubscriptionPeriod::Weekly);
+    pool.subscribe(&pool_id, &bob, &1000, &SubscriptionPeriod::Monthly);
+    pool.cancel_subscription(&pool_id, &bob);
+    pool.process_deposits(&pool_id);
+    recorder.record(&env);
+
+    // A deposit into Stellar and a confirmed withdrawal out of it
+    let bridge = CrossChainBridgeClient::new(&env, &env.register_contract(None, CrossChainBridge));
+    let relayer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    bridge.initialize(&admin, &1000, &1);
+    bridge.add_supported_chain(
+        &ChainId::Ethereum,
+        &String::from_str(&env, "Ethereum"),
+        &BytesN::from_array(&env, &[5; 32]),
+        &12,
+        &5_000_000_000,
+    );
+    bridge.register_wrapped_asset(
+        &String::from_str(&env, "ETH"),
+        &token_id,
+        &ChainId::Ethereum,
+        &BytesN::from_array(&env, &[6; 32]),
+        &18,
+    );
+    bridge.register_relayer(&relayer, &1000);
+    bridge.deposit(
+        &ChainId::Ethereum,
+        &BytesN::from_array(&env, &[7; 32]),
+        &BytesN::from_array(&env, &[8; 32]),
+        &recipient,
+        &token_id,
+        &5000,
+    );
+    let withdrawal = bridge.withdraw(
+        &alice,
+        &ChainId::Ethereum,
+        &BytesN::from_array(&env, &[9; 32]),
+        &token_id,
+        &2000,
+    );
+    bridge.confirm_withdrawal(&relayer, &withdrawal, &BytesN::from_array(&env, &[10; 32]));
+    recorder.record(&env);
+
+    let strkey = |address: &Address| address.to_string().to_string();
+    Replay {
+        recorder,
+        creator: strkey(&creator),
+        alice: strkey(&alice),
+        bob: strkey(&bob),
+        carol: strkey(&carol),
+        recipient: strkey(&recipient),
+        token: strkey(&token_id),
+        pool_id,
+    }
+}
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("novafund-indexer-{}-{name}", std::process::id()))
+}
+
+#[test]
+fn test_replay_builds_platform_state() {
+    let replay = replay();
+    let mut indexer = Indexer::in_memory().unwrap();
+    let applied = indexer.index_all(&replay.recorder.events).unwrap();
+    assert_eq!(applied as u64, indexer.event_count().unwrap());
+    // Token transfers and mints are skipped
+    assert!(applied < replay.recorder.events.len());
+
+    let funded = indexer.project(0).unwrap().unwrap();
+    assert_eq!(funded.creator, replay.creator);
+    assert_eq!(funded.total_raised, 1100_0000000);
+    assert_eq!(funded.contributor_count, 2);
+    assert_eq!(funded.status, "active");
+
+    let failed = indexer.project(1).unwrap().unwrap();
+    assert_eq!(failed.status, "failed");
+    assert_eq!(failed.total_refunded, 50_0000000);
+    assert_eq!(indexer.projects(Some("failed")).unwrap(), vec![failed]);
+    assert_eq!(
+        indexer.projects_by_creator(&replay.creator).unwrap().len(),
+        2
+    );
+    assert_eq!(indexer.top_projects(1).unwrap()[0].project_id, 0);
+
+    let contributions = indexer.contributions(0).unwrap();
+    assert_eq!(contributions.len(), 2);
+    assert_eq!(contributions[0].contributor, replay.alice);
+    assert_eq!(contributions[0].amount, 600_0000000);
+    let refunded = indexer.contributions_by(&replay.carol).unwrap();
+    assert_eq!(refunded.len(), 1);
+    assert!(refunded[0].refunded);
+
+    let milestones = indexer.milestones(0).unwrap();
+    assert_eq!(milestones.len(), 2);
+    assert_eq!(milestones[0].status, "approved");
+    assert_eq!(milestones[0].approval_count, 1);
+    assert_eq!(milestones[0].released_amount, 400_0000000);
+    assert_eq!(milestones[0].proof_hash, Some("03".repeat(32)));
+    assert_eq!(milestones[1].status, "rejected");
+    assert_eq!(milestones[1].rejection_count, 3);
+    assert_eq!(milestones[1].released_amount, 0);
+
+    let profits = indexer.profit_summary(0, &replay.token).unwrap();
+    assert_eq!(profits.deposited, 1000);
+    assert_eq!(profits.claimed, 600);
+    assert_eq!(profits.claimants, 1);
+    let dividends = indexer.dividends_by(&replay.alice).unwrap();
+    assert_eq!(dividends[0].amount, 600);
+    assert_eq!(dividends[0].token, replay.token);
+    assert!(indexer.dividends_by(&replay.bob).unwrap().is_empty());
+
+    let pool = indexer.pool(replay.pool_id).unwrap().unwrap();
+    assert_eq!(pool.name, "Alpha");
+    assert_eq!(pool.total_paid, 1000);
+    assert_eq!(pool.active_subscriptions, 1);
+    let alice_subs = indexer.subscriptions_by(&replay.alice).unwrap();
+    assert_eq!(alice_subs[0].status, "active");
+    assert_eq!(alice_subs[0].amount, Some(1000));
+    assert_eq!(alice_subs[0].total_paid, 1000);
+    assert_eq!(
+        indexer.subscriptions_by(&replay.bob).unwrap()[0].status,
+        "cancelled"
+    );
+
+    let deposit = &indexer.bridge_txs_by(&replay.recipient).unwrap()[0];
+    assert_eq!(deposit.direction, "deposit");
+    assert_eq!(deposit.amount, 5000);
+    assert_eq!(deposit.status, "confirmed");
+    assert_eq!(deposit.external_tx_hash, Some("07".repeat(32)));
+    let withdrawal = indexer.bridge_tx(1).unwrap().unwrap();
+    assert_eq!(withdrawal.direction, "withdraw");
+    assert_eq!(withdrawal.chain_id, Some(ChainId::Ethereum as u32));
+    assert_eq!(withdrawal.account, "09".repeat(32));
+    assert_eq!(withdrawal.status, "confirmed");
+    assert_eq!(withdrawal.external_tx_hash, Some("0a".repeat(32)));
+
+    let stats = indexer.stats().unwrap();
+    assert_eq!(
+        stats,
+        PlatformStats {
+            projects: 2,
+            failed_projects: 1,
+            total_raised: 1150_0000000,
+            contributors: 3,
+            milestones_approved: 1,
+            total_released: 400_0000000,
+            dividends_paid: 600,
+            active_subscriptions: 1,
+            bridge_txs: 2,
+        }
+    );
+}
+
+#[test]
+fn test_reindexing_is_idempotent() {
+    let replay = replay();
+    let mut indexer = Indexer::in_memory().unwrap();
+    indexer.index_all(&replay.recorder.events).unwrap();
+    let stats = indexer.stats().unwrap();
+
+    assert_eq!(indexer.index_all(&replay.recorder.events).unwrap(), 0);
+    assert_eq!(indexer.stats().unwrap(), stats);
+    assert_eq!(indexer.contributions(0).unwrap().len(), 2);
+}
+
+#[test]
+fn test_index_json_and_xdr_files() {
+    let replay = replay();
+    let mut expected = Indexer::in_memory().unwrap();
+    expected.index_all(&replay.recorder.events).unwrap();
+    let expected = expected.stats().unwrap();
+
+    let json = temp_path("events.json");
+    std::fs::write(
+        &json,
+        serde_json::to_string(&replay.recorder.events).unwrap(),
+    )
+    .unwrap();
+    let mut from_json = Indexer::in_memory().unwrap();
+    from_json.index_json_file(&json).unwrap();
+    assert_eq!(from_json.stats().unwrap(), expected);
+
+    let xdr = temp_path("events.xdr");
+    let lines: std::vec::Vec<_> = replay
+        .recorder
+        .raw
+        .iter()
+        .map(|event| event.to_xdr_base64(Limits::none()).unwrap())
+        .collect();
+    std::fs::write(&xdr, lines.join("\n")).unwrap();
+    let mut from_xdr = Indexer::in_memory().unwrap();
+    from_xdr.index_xdr_file(&xdr).unwrap();
+    assert_eq!(from_xdr.stats().unwrap(), expected);
+
+    std::fs::remove_file(json).unwrap();
+    std::fs::remove_file(xdr).unwrap();
+}
+
+#[test]
+fn test_sync_resumes_from_cursor() {
+    let replay = replay();
+    let events = replay.recorder.events;
+    let half = events.len() / 2;
+
+    let db = temp_path("sync.db");
+    let mut rpc = MockRpc::new(events[..half].to_vec());
+    let mut indexer = Indexer::open(&db).unwrap();
+    indexer.sync(&mut rpc).unwrap();
+    assert_eq!(indexer.cursor().unwrap(), Some(events[half - 1].id.clone()));
+    drop(indexer);
+
+    // A reopened database picks up where it left off
+    for event in &events[half..] {
+        rpc.push(event.clone());
+    }
+    let mut indexer = Indexer::open(&db).unwrap();
+    indexer.sync(&mut rpc).unwrap();
+    assert_eq!(
+        indexer.cursor().unwrap(),
+        Some(events.last().unwrap().id.clone())
+    );
+    assert_eq!(indexer.sync(&mut rpc).unwrap(), 0);
+
+    let mut expected = Indexer::in_memory().unwrap();
+    expected.index_all(&events).unwrap();
+    assert_eq!(indexer.stats().unwrap(), expected.stats().unwrap());
+    assert_eq!(
+        indexer.event_count().unwrap(),
+        expected.event_count().unwrap()
+    );
+
+    drop(indexer);
+    std::fs::remove_file(db).unwrap();
+}
+
+#[test]
+fn test_rejects_future_schema() {
+    let replay = replay();
+    let decoder = EventDecoder::new();
+    let mut event = replay
+        .recorder
+        .events
+        .into_iter()
+        .find(|event| event.decode(&decoder).is_ok())
+        .unwrap();
+    assert_eq!(event.decode(&decoder).unwrap().event.name(), "proj_new");
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    event.topic[1] = soroban_sdk::xdr::ScVal::U32(shared::events::EVENT_SCHEMA_VERSION + 1)
+        .to_xdr_base64(Limits::none())
+        .unwrap();
+    assert!(matches!(
+        indexer.index(&event),
+        Err(IndexError::Decode(DecodeError::UnsupportedVersion(_)))
+    ));
+    assert_eq!(indexer.event_count().unwrap(), 0);
+}
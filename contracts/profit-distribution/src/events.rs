@@ -1,23 +1,191 @@
-use soroban_sdk::{contracttype, Address, Env};
+use shared::events::{
+    publish, DividendClaimed, DividendsCompounded, DustSwept, ProfitDistributed, RoundClaimed,
+    RoundClosed, RoundOpened, SharesApproved, SharesBurned, SharesTransferred, WaterfallPaid,
+};
+use soroban_sdk::{Address, Env};
 
-#[contracttype]
-pub enum EventType {
-    ProfitDeposited(u64, i128),          // project_id, amount
-    DividendClaimed(u64, Address, i128), // project_id, investor, amount
+use crate::types::{CompoundTarget, ShareAllowance, WaterfallProgress};
+
+pub fn emit_deposit_event(env: &Env, project_id: u64, token: &Address, amount: i128) {
+    publish(
+        env,
+        ProfitDistributed {
+            project_id,
+            token: token.clone(),
+            amount,
+        },
+    );
+}
+
+pub fn emit_claim_event(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    publish(
+        env,
+        DividendClaimed {
+            project_id,
+            investor: investor.clone(),
+            token: token.clone(),
+            amount,
+        },
+    );
+}
+
+pub fn emit_transfer_event(env: &Env, project_id: u64, from: &Address, to: &Address, shares: u32) {
+    publish(
+        env,
+        SharesTransferred {
+            project_id,
+            from: from.clone(),
+            to: to.clone(),
+            shares,
+        },
+    );
+}
+
+pub fn emit_approve_event(
+    env: &Env,
+    project_id: u64,
+    owner: &Address,
+    spender: &Address,
+    allowance: &ShareAllowance,
+) {
+    publish(
+        env,
+        SharesApproved {
+            project_id,
+            owner: owner.clone(),
+            spender: spender.clone(),
+            shares: allowance.shares,
+            expiration_ledger: allowance.expiration_ledger,
+        },
+    );
+}
+
+pub fn emit_burn_event(env: &Env, project_id: u64, holder: &Address, shares: u32) {
+    publish(
+        env,
+        SharesBurned {
+            project_id,
+            holder: holder.clone(),
+            shares,
+        },
+    );
 }
 
-pub fn emit_deposit_event(env: &Env, project_id: u64, amount: i128) {
-    env.events()
-        .publish((EventType::ProfitDeposited(project_id, amount),), ());
+pub fn emit_dust_swept_event(
+    env: &Env,
+    project_id: u64,
+    token: &Address,
+    treasury: &Address,
+    amount: i128,
+) {
+    publish(
+        env,
+        DustSwept {
+            project_id,
+            token: token.clone(),
+            treasury: treasury.clone(),
+            amount,
+        },
+    );
 }
 
-pub fn emit_claim_event(env: &Env, project_id: u64, investor: &Address, amount: i128) {
-    env.events().publish(
-        (EventType::DividendClaimed(
+pub fn emit_compound_event(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+    token: &Address,
+    target: &CompoundTarget,
+    amount: i128,
+    keeper_fee: i128,
+) {
+    let (target, target_id) = match target {
+        CompoundTarget::Project(launch, id) => (launch.clone(), *id),
+        CompoundTarget::Pool(pools, id) => (pools.clone(), *id),
+    };
+    publish(
+        env,
+        DividendsCompounded {
             project_id,
-            investor.clone(),
+            investor: investor.clone(),
+            token: token.clone(),
+            target,
+            target_id,
             amount,
-        ),),
-        (),
+            keeper_fee,
+        },
+    );
+}
+
+pub fn emit_round_opened_event(
+    env: &Env,
+    project_id: u64,
+    round_id: u64,
+    token: &Address,
+    amount: i128,
+    expires_at: u64,
+) {
+    publish(
+        env,
+        RoundOpened {
+            project_id,
+            round_id,
+            token: token.clone(),
+            amount,
+            expires_at,
+        },
+    );
+}
+
+pub fn emit_round_claimed_event(
+    env: &Env,
+    project_id: u64,
+    round_id: u64,
+    holder: &Address,
+    amount: i128,
+) {
+    publish(
+        env,
+        RoundClaimed {
+            project_id,
+            round_id,
+            holder: holder.clone(),
+            amount,
+        },
+    );
+}
+
+pub fn emit_round_closed_event(env: &Env, project_id: u64, round_id: u64, unclaimed: i128) {
+    publish(
+        env,
+        RoundClosed {
+            project_id,
+            round_id,
+            unclaimed,
+        },
+    );
+}
+
+pub fn emit_waterfall_event(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+    tiers: &WaterfallProgress,
+) {
+    publish(
+        env,
+        WaterfallPaid {
+            project_id,
+            investor: investor.clone(),
+            capital_returned: tiers.capital_returned,
+            preferred_return: tiers.preferred_return,
+            profit: tiers.profit,
+            carry: tiers.carry,
+        },
     );
 }
@@ -6,25 +6,61 @@
 // - Dividend claiming mechanism
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contractmeta, token::TokenClient, Address, Env, Map};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractimpl, contractmeta,
+    token::TokenClient,
+    vec, Address, Env, IntoVal, Map, Symbol, Vec,
+};
 
-mod errors;
 mod events;
 mod storage;
 mod types;
 
+pub use types::{
+    CompoundTarget, DistributionRound, InvestorShare, RewardPosition, ShareCheckpoint,
+    UnallocatedPolicy, VestingStream, Waterfall, WaterfallPosition, WaterfallProgress,
+};
+
 #[cfg(test)]
 mod tests;
 
+use shared::{
+    errors::DistributionError,
+    interfaces::{PoolFundingClient, ProjectFundingClient, ProjectLaunchClient, ShareTokenClient},
+    types::ProjectStatus,
+    utils::{calculate_fee, calculate_percentage, validate_basis_points},
+};
+
 use crate::{
-    errors::ContractError,
-    events::{emit_claim_event, emit_deposit_event},
+    events::{
+        emit_approve_event, emit_burn_event, emit_claim_event, emit_compound_event,
+        emit_deposit_event, emit_dust_swept_event, emit_round_claimed_event,
+        emit_round_closed_event, emit_round_opened_event, emit_transfer_event,
+        emit_waterfall_event,
+    },
     storage::*,
-    types::InvestorShare,
+    types::ShareAllowance,
 };
 
 const PRECISION: i128 = 1_000_000_000_000;
 
+/// Shares of a fully registered project, in basis points
+const TOTAL_SHARES: u32 = 10_000;
+
+/// Reward tokens a project can have, bounding the work of settling a holder
+const MAX_REWARD_TOKENS: u32 = 10;
+
+/// Vesting deposits a reward token can be releasing at once
+const MAX_VESTING_STREAMS: u32 = 10;
+
+/// Highest keeper fee on compounded dividends, in basis points
+const MAX_KEEPER_FEE: u32 = 500;
+
+/// Distribution rounds a project can have open at once, bounding the work of
+/// listing a holder's claimable rounds
+const MAX_OPEN_ROUNDS: u32 = 20;
+
 contractmeta!(key = "name", val = "Profit Distribution Contract");
 
 #[contract]
@@ -33,9 +69,9 @@ pub struct ProfitDistribution;
 #[contractimpl]
 impl ProfitDistribution {
     /// Initialize a new profit distribution for a project
-    pub fn initialize(env: Env, admin: Address) -> Result<(), ContractError> {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), DistributionError> {
         if get_admin(&env).is_some() {
-            return Err(ContractError::AlreadyInitialized);
+            return Err(DistributionError::AlreadyInitialized);
         }
         admin.require_auth();
         set_admin(&env, &admin);
@@ -43,85 +79,375 @@ impl ProfitDistribution {
     }
 
     /// Register the token used for project profits
-    pub fn set_token(env: Env, project_id: u64, token: Address) -> Result<(), ContractError> {
-        let admin = get_admin(&env).ok_or(ContractError::NotInitialized)?;
+    ///
+    /// This is the token `deposit_profits` and `claim_dividends` use. It is
+    /// also added to the project's reward tokens, where it stays if another
+    /// token replaces it later. Under a waterfall, the new token's profits go
+    /// through it from then on.
+    pub fn set_token(env: Env, project_id: u64, token: Address) -> Result<(), DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
         admin.require_auth();
+        add_reward_token(&env, project_id, &token)?;
         set_project_token(&env, project_id, &token);
+        if get_waterfall(&env, project_id).is_some() {
+            start_waterfall(&env, project_id, &token);
+        }
         Ok(())
     }
 
+    /// Accept profits in another token, accounted for separately from the
+    /// project's other reward tokens
+    pub fn add_reward_token(
+        env: Env,
+        project_id: u64,
+        token: Address,
+    ) -> Result<(), DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
+        admin.require_auth();
+        add_reward_token(&env, project_id, &token)
+    }
+
+    /// Every token a project pays profits in
+    pub fn get_reward_tokens(env: Env, project_id: u64) -> Vec<Address> {
+        get_reward_tokens(&env, project_id)
+    }
+
     /// Register investors with their share percentages
+    ///
+    /// Adds investors, or changes the shares of ones already registered, and
+    /// can be called as often as needed: what registered investors earned
+    /// before the change stays claimable. Fails without changing anything if
+    /// the project's shares would add up to more than 100%.
     pub fn register_investors(
         env: Env,
         project_id: u64,
         investors: Map<Address, u32>,
-    ) -> Result<(), ContractError> {
-        let admin = get_admin(&env).ok_or(ContractError::NotInitialized)?;
+    ) -> Result<(), DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
         admin.require_auth();
 
-        let mut total_shares: u32 = 0;
-        let current_acc = get_acc_profit_per_share(&env, project_id);
+        if investors.values().contains(0) {
+            return Err(DistributionError::InvalidSharePercentage);
+        }
+        store_investors(&env, project_id, &investors)
+    }
+
+    /// Take all shares away from registered investors
+    ///
+    /// Their dividends so far stay claimable, and the freed shares can be
+    /// registered to others.
+    pub fn remove_investors(
+        env: Env,
+        project_id: u64,
+        investors: Vec<Address>,
+    ) -> Result<(), DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
+        admin.require_auth();
 
-        for (investor, share_percentage) in investors.iter() {
-            if share_percentage == 0 {
-                return Err(ContractError::InvalidSharePercentage);
+        let mut removed = Map::new(&env);
+        for investor in investors.iter() {
+            if get_shares(&env, project_id, &investor).is_none() {
+                return Err(DistributionError::Unauthorized);
             }
-            total_shares += share_percentage;
-
-            let share = InvestorShare {
-                investor: investor.clone(),
-                share_percentage,
-                accumulated_at_last_update: current_acc,
-                claimable_amount: 0,
-                total_claimed: 0,
-            };
-            set_investor_share(&env, project_id, &investor, &share);
+            removed.set(investor, 0);
         }
+        store_investors(&env, project_id, &removed)
+    }
+
+    /// Register a completed project's backers with shares in proportion to
+    /// their contributions in `project_launch`
+    ///
+    /// `backers` must list every contributor once, so that their
+    /// contributions add up to the project's total raised. Shares are
+    /// rounded down to basis points and the basis points left over go to the
+    /// backers with the largest remainders, so they always add up to 100%.
+    /// A backer whose contribution rounds to no share at all is left out.
+    /// A project `project_launch` cannot return counts as not completed.
+    /// Returns the registered shares.
+    pub fn register_investors_from_launch(
+        env: Env,
+        project_id: u64,
+        project_launch: Address,
+        backers: Vec<Address>,
+    ) -> Result<Map<Address, u32>, DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
+        admin.require_auth();
 
-        if total_shares > 10000 {
-            return Err(ContractError::TotalSharesNot100);
+        let launch = ProjectLaunchClient::new(&env, &project_launch);
+        let project = match launch.try_get_project(&project_id) {
+            Ok(Ok(project)) => project,
+            _ => return Err(DistributionError::ProjectNotCompleted),
+        };
+        if project.status != ProjectStatus::Completed {
+            return Err(DistributionError::ProjectNotCompleted);
         }
 
-        set_total_shares(&env, project_id, total_shares);
-        Ok(())
+        let mut contributions: Map<Address, i128> = Map::new(&env);
+        let mut counted: i128 = 0;
+        for backer in backers.iter() {
+            let contribution = launch.get_user_contribution(&project_id, &backer);
+            if contribution <= 0 || contributions.contains_key(backer.clone()) {
+                return Err(DistributionError::ContributionMismatch);
+            }
+            counted += contribution;
+            contributions.set(backer, contribution);
+        }
+        if counted != project.total_raised {
+            return Err(DistributionError::ContributionMismatch);
+        }
+
+        let investors = proportional_shares(&env, &backers, &contributions, counted);
+        store_investors(&env, project_id, &investors)?;
+        Ok(investors)
     }
 
-    /// Deposit profits to be distributed among investors
     pub fn deposit_profits(
         env: Env,
         project_id: u64,
         depositor: Address,
         amount: i128,
-    ) -> Result<(), ContractError> {
-        if amount <= 0 {
-            return Err(ContractError::InvalidAmount);
+    ) -> Result<(), DistributionError> {
+        let token = get_project_token(&env, project_id).ok_or(DistributionError::NotInitialized)?;
+        deposit(&env, project_id, &token, &depositor, amount, 0)
+    }
+
+    /// Deposit profits in one of the project's reward tokens
+    pub fn deposit_token_profits(
+        env: Env,
+        project_id: u64,
+        token: Address,
+        depositor: Address,
+        amount: i128,
+    ) -> Result<(), DistributionError> {
+        if !get_reward_tokens(&env, project_id).contains(&token) {
+            return Err(DistributionError::UnknownRewardToken);
         }
+        deposit(&env, project_id, &token, &depositor, amount, 0)
+    }
 
-        depositor.require_auth();
+    /// Deposit profits that are released to investors linearly over
+    /// `duration` seconds instead of all at once
+    ///
+    /// What has been released is claimable, and goes to whoever held the
+    /// shares while it was released, so selling shares early forfeits the
+    /// rest to the buyer. Under the treasury policy the part of each release
+    /// for shares no investor holds at the time is paid to the treasury;
+    /// otherwise released profits with no shares registered to receive them
+    /// become dust.
+    pub fn deposit_vested_profits(
+        env: Env,
+        project_id: u64,
+        token: Address,
+        depositor: Address,
+        amount: i128,
+        duration: u64,
+    ) -> Result<(), DistributionError> {
+        if duration == 0 {
+            return Err(DistributionError::InvalidAmount);
+        }
+        if !get_reward_tokens(&env, project_id).contains(&token) {
+            return Err(DistributionError::UnknownRewardToken);
+        }
+        deposit(&env, project_id, &token, &depositor, amount, duration)
+    }
 
-        let token_address =
-            get_project_token(&env, project_id).ok_or(ContractError::NotInitialized)?;
-        let total_shares =
-            get_total_shares(&env, project_id).ok_or(ContractError::NotInitialized)?;
+    /// Open a distribution round paying `amount` of a reward token to the
+    /// holders of record now, returning the round's id
+    ///
+    /// Holders claim their part with `claim_round` at the shares they held
+    /// when the round opened, whatever they hold later. Rounds expire after
+    /// `claim_period` seconds, and what is still unclaimed then is added to
+    /// the running dividends when the round is closed. Rounds cannot be
+    /// opened while the project has a waterfall.
+    pub fn deposit_round(
+        env: Env,
+        project_id: u64,
+        token: Address,
+        depositor: Address,
+        amount: i128,
+        claim_period: u64,
+    ) -> Result<u64, DistributionError> {
+        if amount <= 0 || claim_period == 0 {
+            return Err(DistributionError::InvalidAmount);
+        }
+        if !get_reward_tokens(&env, project_id).contains(&token) {
+            return Err(DistributionError::UnknownRewardToken);
+        }
+        // Rounds would pay holders around their waterfall tiers
+        if get_waterfall(&env, project_id).is_some() {
+            return Err(DistributionError::WaterfallActive);
+        }
+        depositor.require_auth();
 
-        if total_shares == 0 {
-            return Err(ContractError::InvalidAmount);
+        let mut open_rounds = get_open_rounds(&env, project_id);
+        if open_rounds.len() >= MAX_OPEN_ROUNDS {
+            return Err(DistributionError::TooManyOpenRounds);
+        }
+        let created_at = env.ledger().timestamp();
+        let expires_at = created_at
+            .checked_add(claim_period)
+            .ok_or(DistributionError::InvalidAmount)?;
+        let allocation = allocate(&env, project_id, amount)?;
+        if allocation.shares == 0 {
+            return Err(DistributionError::InvalidAmount);
         }
 
-        // Transfer tokens to contract
-        let token_client = TokenClient::new(&env, &token_address);
+        let token_client = TokenClient::new(&env, &token);
         token_client.transfer(&depositor, &env.current_contract_address(), &amount);
+        if let Some(treasury) = &allocation.treasury {
+            token_client.transfer(
+                &env.current_contract_address(),
+                treasury,
+                &allocation.unallocated,
+            );
+        }
 
-        // Update global accumulated profit
-        let current_acc = get_acc_profit_per_share(&env, project_id);
-        let delta = (amount
-            .checked_mul(PRECISION)
-            .ok_or(ContractError::InvalidAmount)?)
-            / (total_shares as i128);
-        set_acc_profit_per_share(&env, project_id, current_acc + delta);
+        let round_id = get_round_count(&env, project_id);
+        let round = DistributionRound {
+            token: token.clone(),
+            depositor,
+            amount: amount - allocation.unallocated,
+            total_shares: allocation.shares,
+            claimed: 0,
+            created_at,
+            expires_at,
+            closed: false,
+        };
+        set_round(&env, project_id, round_id, &round);
+        set_round_count(&env, project_id, round_id + 1);
+        open_rounds.push_back(round_id);
+        set_open_rounds(&env, project_id, &open_rounds);
 
-        emit_deposit_event(&env, project_id, amount);
-        Ok(())
+        emit_round_opened_event(
+            &env,
+            project_id,
+            round_id,
+            &token,
+            round.amount,
+            round.expires_at,
+        );
+        Ok(round_id)
+    }
+
+    /// Claim a holder's part of a round, returning the amount paid
+    pub fn claim_round(
+        env: Env,
+        project_id: u64,
+        holder: Address,
+        round_id: u64,
+    ) -> Result<i128, DistributionError> {
+        holder.require_auth();
+
+        let mut round =
+            get_round(&env, project_id, round_id).ok_or(DistributionError::RoundNotFound)?;
+        if round.closed || env.ledger().timestamp() >= round.expires_at {
+            return Err(DistributionError::RoundExpired);
+        }
+        if is_round_claimed(&env, project_id, round_id, &holder) {
+            return Err(DistributionError::AlreadyClaimed);
+        }
+        let amount = round_entitlement(&env, project_id, round_id, &round, &holder);
+        if amount <= 0 {
+            return Err(DistributionError::NothingToClaim);
+        }
+
+        set_round_claimed(&env, project_id, round_id, &holder);
+        round.claimed += amount;
+        set_round(&env, project_id, round_id, &round);
+        TokenClient::new(&env, &round.token).transfer(
+            &env.current_contract_address(),
+            &holder,
+            &amount,
+        );
+
+        emit_round_claimed_event(&env, project_id, round_id, &holder, amount);
+        Ok(amount)
+    }
+
+    /// Close an expired round and add what was never claimed to the running
+    /// dividends of the current shareholders, returning that amount
+    ///
+    /// Anyone may close a round once it has expired.
+    pub fn close_round(
+        env: Env,
+        project_id: u64,
+        round_id: u64,
+    ) -> Result<i128, DistributionError> {
+        let mut round =
+            get_round(&env, project_id, round_id).ok_or(DistributionError::RoundNotFound)?;
+        if round.closed {
+            return Err(DistributionError::RoundExpired);
+        }
+        if env.ledger().timestamp() < round.expires_at {
+            return Err(DistributionError::RoundNotExpired);
+        }
+
+        let unclaimed = round.amount - round.claimed;
+        round.closed = true;
+        set_round(&env, project_id, round_id, &round);
+        let mut open_rounds = get_open_rounds(&env, project_id);
+        if let Some(index) = open_rounds.first_index_of(round_id) {
+            open_rounds.remove(index);
+        }
+        set_open_rounds(&env, project_id, &open_rounds);
+
+        if unclaimed > 0 {
+            let total_shares = get_total_shares(&env, project_id).unwrap_or(0);
+            let (delta, dust) = spread(unclaimed, total_shares);
+            let current_acc = get_acc_profit_per_share(&env, project_id, &round.token);
+            set_acc_profit_per_share(&env, project_id, &round.token, current_acc + delta);
+            set_dust(
+                &env,
+                project_id,
+                &round.token,
+                get_dust(&env, project_id, &round.token) + dust,
+            );
+        }
+
+        emit_round_closed_event(&env, project_id, round_id, unclaimed);
+        Ok(unclaimed)
+    }
+
+    pub fn get_round(
+        env: Env,
+        project_id: u64,
+        round_id: u64,
+    ) -> Result<DistributionRound, DistributionError> {
+        get_round(&env, project_id, round_id).ok_or(DistributionError::RoundNotFound)
+    }
+
+    /// Ids of the rounds not closed yet, including expired ones
+    pub fn get_open_rounds(env: Env, project_id: u64) -> Vec<u64> {
+        get_open_rounds(&env, project_id)
+    }
+
+    /// What a holder can still claim from each unexpired round, by round id
+    pub fn get_claimable_rounds(env: Env, project_id: u64, holder: Address) -> Map<u64, i128> {
+        let now = env.ledger().timestamp();
+        let mut claimable = Map::new(&env);
+        for round_id in get_open_rounds(&env, project_id).iter() {
+            let Some(round) = get_round(&env, project_id, round_id) else {
+                continue;
+            };
+            if now >= round.expires_at || is_round_claimed(&env, project_id, round_id, &holder) {
+                continue;
+            }
+            let amount = round_entitlement(&env, project_id, round_id, &round, &holder);
+            if amount > 0 {
+                claimable.set(round_id, amount);
+            }
+        }
+        claimable
+    }
+
+    /// Shares a holder held when a round opened
+    pub fn get_round_shares(env: Env, project_id: u64, round_id: u64, holder: Address) -> u32 {
+        shares_at(&env, project_id, &holder, round_id)
+    }
+
+    /// Vesting deposits of a reward token that are still being released
+    pub fn get_vesting_streams(env: Env, project_id: u64, token: Address) -> Vec<VestingStream> {
+        get_vesting_streams(&env, project_id, &token)
     }
 
     /// Allow an investor to claim their dividends
@@ -129,38 +455,43 @@ impl ProfitDistribution {
         env: Env,
         project_id: u64,
         investor: Address,
-    ) -> Result<i128, ContractError> {
+    ) -> Result<i128, DistributionError> {
         investor.require_auth();
 
-        let token_address =
-            get_project_token(&env, project_id).ok_or(ContractError::NotInitialized)?;
-        let mut share =
-            get_investor_share(&env, project_id, &investor).ok_or(ContractError::Unauthorized)?;
+        let token = get_project_token(&env, project_id).ok_or(DistributionError::NotInitialized)?;
+        let shares =
+            get_shares(&env, project_id, &investor).ok_or(DistributionError::Unauthorized)?;
 
-        let current_acc = get_acc_profit_per_share(&env, project_id);
-
-        // Calculate pending amount
-        let pending = (share.share_percentage as i128
-            * (current_acc - share.accumulated_at_last_update))
-            / PRECISION;
-        let total_claimable = share.claimable_amount + pending;
-
-        if total_claimable <= 0 {
-            return Err(ContractError::NothingToClaim);
+        let claimed = claim(&env, project_id, &investor, shares, &token);
+        if claimed <= 0 {
+            return Err(DistributionError::NothingToClaim);
         }
+        Ok(claimed)
+    }
 
-        // Update user state
-        share.claimable_amount = 0;
-        share.accumulated_at_last_update = current_acc;
-        share.total_claimed += total_claimable;
-        set_investor_share(&env, project_id, &investor, &share);
+    /// Claim an investor's dividends in every reward token, returning the
+    /// amount claimed per token
+    pub fn claim_all(
+        env: Env,
+        project_id: u64,
+        investor: Address,
+    ) -> Result<Map<Address, i128>, DistributionError> {
+        investor.require_auth();
 
-        // Transfer funds
-        let token_client = TokenClient::new(&env, &token_address);
-        token_client.transfer(&env.current_contract_address(), &investor, &total_claimable);
+        let shares =
+            get_shares(&env, project_id, &investor).ok_or(DistributionError::Unauthorized)?;
 
-        emit_claim_event(&env, project_id, &investor, total_claimable);
-        Ok(total_claimable)
+        let mut claimed = Map::new(&env);
+        for token in get_reward_tokens(&env, project_id).iter() {
+            let amount = claim(&env, project_id, &investor, shares, &token);
+            if amount > 0 {
+                claimed.set(token, amount);
+            }
+        }
+        if claimed.is_empty() {
+            return Err(DistributionError::NothingToClaim);
+        }
+        Ok(claimed)
     }
 
     /// Get investor share information
@@ -168,17 +499,417 @@ impl ProfitDistribution {
         env: Env,
         project_id: u64,
         investor: Address,
-    ) -> Result<InvestorShare, ContractError> {
-        let mut share =
-            get_investor_share(&env, project_id, &investor).ok_or(ContractError::Unauthorized)?;
+    ) -> Result<InvestorShare, DistributionError> {
+        let shares =
+            get_shares(&env, project_id, &investor).ok_or(DistributionError::Unauthorized)?;
+        let position = match get_project_token(&env, project_id) {
+            Some(token) => settled_position(&env, project_id, &investor, shares, &token).0,
+            None => RewardPosition::default(),
+        };
+
+        Ok(InvestorShare {
+            investor,
+            share_percentage: shares,
+            accumulated_at_last_update: position.accumulated_at_last_update,
+            claimable_amount: position.claimable_amount,
+            total_claimed: position.total_claimed,
+        })
+    }
+
+    /// An investor's profits in one reward token, with what is claimable now
+    pub fn get_reward_position(
+        env: Env,
+        project_id: u64,
+        investor: Address,
+        token: Address,
+    ) -> RewardPosition {
+        let shares = get_shares(&env, project_id, &investor).unwrap_or(0);
+        settled_position(&env, project_id, &investor, shares, &token).0
+    }
+
+    /// What an investor can claim now, per reward token
+    pub fn get_pending_rewards(env: Env, project_id: u64, investor: Address) -> Map<Address, i128> {
+        let shares = get_shares(&env, project_id, &investor).unwrap_or(0);
+        let mut pending = Map::new(&env);
+        for token in get_reward_tokens(&env, project_id).iter() {
+            let (position, _) = settled_position(&env, project_id, &investor, shares, &token);
+            pending.set(token, position.claimable_amount);
+        }
+        pending
+    }
+
+    /// Move `shares` basis points of profit rights from `from` to `to`
+    ///
+    /// Dividends accrued so far stay with whoever held the shares: both sides
+    /// are settled before the shares move, and `from` keeps its record, and
+    /// its claimable dividends, even when it transfers everything. Under a
+    /// waterfall, the shares carry their part of `from`'s capital and of
+    /// what has been returned on it.
+    pub fn transfer_shares(
+        env: Env,
+        project_id: u64,
+        from: Address,
+        to: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError> {
+        from.require_auth();
+        move_shares(&env, project_id, &from, &to, shares)
+    }
+
+    /// Allow `spender` to move up to `shares` basis points of `owner`'s
+    /// shares until `expiration_ledger`, replacing any earlier allowance
+    pub fn approve_shares(
+        env: Env,
+        project_id: u64,
+        owner: Address,
+        spender: Address,
+        shares: u32,
+        expiration_ledger: u32,
+    ) -> Result<(), DistributionError> {
+        owner.require_auth();
+        approve(
+            &env,
+            project_id,
+            &owner,
+            &spender,
+            shares,
+            expiration_ledger,
+        )
+    }
+
+    /// Shares `spender` may still move on `owner`'s behalf
+    pub fn get_share_allowance(env: Env, project_id: u64, owner: Address, spender: Address) -> u32 {
+        live_allowance(&env, project_id, &owner, &spender).shares
+    }
+
+    /// Move `shares` basis points from `from` to `to` on `from`'s behalf,
+    /// spending `spender`'s allowance
+    pub fn transfer_shares_from(
+        env: Env,
+        project_id: u64,
+        spender: Address,
+        from: Address,
+        to: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError> {
+        spender.require_auth();
+        spend_allowance(&env, project_id, &from, &spender, shares)?;
+        move_shares(&env, project_id, &from, &to, shares)
+    }
+
+    /// Register the token standing for a project's shares
+    ///
+    /// The token must have been initialized for this contract and project.
+    /// Its transfers, approvals and burns then go through the `token_*`
+    /// hooks below, so shares held through the token settle dividends like
+    /// any other transfer.
+    pub fn set_share_token(
+        env: Env,
+        project_id: u64,
+        token: Address,
+    ) -> Result<(), DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
+        admin.require_auth();
+
+        let share_token = ShareTokenClient::new(&env, &token);
+        if share_token.ledger() != env.current_contract_address()
+            || share_token.project_id() != project_id
+        {
+            return Err(DistributionError::ShareTokenMismatch);
+        }
+        set_share_token(&env, project_id, &token);
+        Ok(())
+    }
+
+    /// Token standing for a project's shares, if one is registered
+    pub fn get_share_token(env: Env, project_id: u64) -> Option<Address> {
+        get_share_token(&env, project_id)
+    }
+
+    /// Shares `holder` owns in a project, in basis points
+    pub fn share_balance(env: Env, project_id: u64, holder: Address) -> u32 {
+        get_shares(&env, project_id, &holder).unwrap_or(0)
+    }
+
+    /// Share token hook for `transfer`
+    pub fn token_transfer(
+        env: Env,
+        project_id: u64,
+        from: Address,
+        to: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError> {
+        require_share_token(&env, project_id)?;
+        move_shares(&env, project_id, &from, &to, shares)
+    }
+
+    /// Share token hook for `transfer_from`
+    pub fn token_transfer_from(
+        env: Env,
+        project_id: u64,
+        spender: Address,
+        from: Address,
+        to: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError> {
+        require_share_token(&env, project_id)?;
+        spend_allowance(&env, project_id, &from, &spender, shares)?;
+        move_shares(&env, project_id, &from, &to, shares)
+    }
+
+    /// Share token hook for `approve`
+    pub fn token_approve(
+        env: Env,
+        project_id: u64,
+        owner: Address,
+        spender: Address,
+        shares: u32,
+        expiration_ledger: u32,
+    ) -> Result<(), DistributionError> {
+        require_share_token(&env, project_id)?;
+        approve(
+            &env,
+            project_id,
+            &owner,
+            &spender,
+            shares,
+            expiration_ledger,
+        )
+    }
+
+    /// Share token hook for `burn`
+    ///
+    /// Burned shares leave the project's total, so later profits go to the
+    /// remaining holders. Dividends accrued before the burn stay claimable.
+    pub fn token_burn(
+        env: Env,
+        project_id: u64,
+        from: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError> {
+        require_share_token(&env, project_id)?;
+        burn_shares(&env, project_id, &from, shares)
+    }
+
+    /// Share token hook for `burn_from`
+    pub fn token_burn_from(
+        env: Env,
+        project_id: u64,
+        spender: Address,
+        from: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError> {
+        require_share_token(&env, project_id)?;
+        spend_allowance(&env, project_id, &from, &spender, shares)?;
+        burn_shares(&env, project_id, &from, shares)
+    }
+
+    /// Set the treasury receiving unallocated profits and swept dust
+    pub fn set_treasury(env: Env, treasury: Address) -> Result<(), DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
+        admin.require_auth();
+        set_treasury(&env, &treasury);
+        Ok(())
+    }
+
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        get_treasury(&env)
+    }
+
+    /// Choose where a project's profits go for shares no investor holds
+    ///
+    /// Projects split everything between their investors by default. Paying
+    /// the treasury instead needs one to be set. Vesting streams release
+    /// under the new policy from now on.
+    pub fn set_unallocated_policy(
+        env: Env,
+        project_id: u64,
+        policy: UnallocatedPolicy,
+    ) -> Result<(), DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
+        admin.require_auth();
+        if policy == UnallocatedPolicy::Treasury && get_treasury(&env).is_none() {
+            return Err(DistributionError::NotInitialized);
+        }
+        for token in get_reward_tokens(&env, project_id).iter() {
+            accrue(&env, project_id, &token);
+        }
+        set_unallocated_policy(&env, project_id, policy);
+        Ok(())
+    }
+
+    pub fn get_unallocated_policy(env: Env, project_id: u64) -> UnallocatedPolicy {
+        get_unallocated_policy(&env, project_id)
+    }
+
+    /// Profits in `token` rounded off when deposits were split into
+    /// per-share amounts, which no investor can claim
+    pub fn get_dust(env: Env, project_id: u64, token: Address) -> i128 {
+        get_dust(&env, project_id, &token)
+    }
+
+    /// Pay a project's dust in `token` to the treasury, returning the amount
+    /// swept
+    pub fn sweep_dust(
+        env: Env,
+        project_id: u64,
+        token: Address,
+    ) -> Result<i128, DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
+        admin.require_auth();
+
+        let treasury = get_treasury(&env).ok_or(DistributionError::NotInitialized)?;
+        let dust = get_dust(&env, project_id, &token);
+        if dust <= 0 {
+            return Err(DistributionError::NothingToClaim);
+        }
 
-        let current_acc = get_acc_profit_per_share(&env, project_id);
-        let pending = (share.share_percentage as i128
-            * (current_acc - share.accumulated_at_last_update))
-            / PRECISION;
-        share.claimable_amount += pending;
+        set_dust(&env, project_id, &token, 0);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &treasury, &dust);
 
-        Ok(share)
+        emit_dust_swept_event(&env, project_id, &token, &treasury, dust);
+        Ok(dust)
+    }
+
+    /// Route a project's profits through a waterfall
+    ///
+    /// Each investor's profits in the project's token, vested or not, first
+    /// return their capital, their contribution recorded in `project_launch`
+    /// moved along with any shares they sell or buy, then pay them
+    /// `preferred_return_bps` of it, and only then give the creator
+    /// `carry_bps` of the rest. Profits go through an investor's tiers when
+    /// their position is settled, on claims and share changes, and the carry
+    /// is paid to the creator then. Profits deposited in a token before the
+    /// waterfall was set, or before the token became the project's token,
+    /// bypass it, and while it is set other reward tokens and
+    /// distribution rounds, which would bypass it, are rejected. Progress
+    /// through the tiers is kept when the waterfall is changed.
+    pub fn set_waterfall(
+        env: Env,
+        project_id: u64,
+        waterfall: Waterfall,
+    ) -> Result<(), DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
+        admin.require_auth();
+        if !validate_basis_points(waterfall.carry_bps) {
+            return Err(DistributionError::InvalidSharePercentage);
+        }
+        if let Some(token) = get_project_token(&env, project_id) {
+            start_waterfall(&env, project_id, &token);
+        }
+        set_waterfall(&env, project_id, &waterfall);
+        Ok(())
+    }
+
+    pub fn get_waterfall(env: Env, project_id: u64) -> Option<Waterfall> {
+        get_waterfall(&env, project_id)
+    }
+
+    /// Profits a project's waterfall has paid through each tier so far, as
+    /// investors' positions were settled
+    pub fn get_waterfall_progress(env: Env, project_id: u64) -> WaterfallProgress {
+        get_waterfall_progress(&env, project_id)
+    }
+
+    /// An investor's part of what each investor tier of the waterfall has
+    /// paid
+    pub fn get_waterfall_position(
+        env: Env,
+        project_id: u64,
+        investor: Address,
+    ) -> Result<WaterfallPosition, DistributionError> {
+        let shares =
+            get_shares(&env, project_id, &investor).ok_or(DistributionError::Unauthorized)?;
+        let payment = get_project_token(&env, project_id)
+            .and_then(|token| settled_position(&env, project_id, &investor, shares, &token).1);
+        Ok(match (payment, get_waterfall(&env, project_id)) {
+            (Some(payment), _) => payment.position,
+            (None, Some(waterfall)) => waterfall_position(&env, project_id, &waterfall, &investor),
+            (None, None) => get_waterfall_position(&env, project_id, &investor).unwrap_or_default(),
+        })
+    }
+
+    /// Set the cut of compounded dividends paid to whoever calls `compound`,
+    /// in basis points
+    pub fn set_keeper_fee(env: Env, fee_bps: u32) -> Result<(), DistributionError> {
+        let admin = get_admin(&env).ok_or(DistributionError::NotInitialized)?;
+        admin.require_auth();
+        if fee_bps > MAX_KEEPER_FEE {
+            return Err(DistributionError::InvalidAmount);
+        }
+        set_keeper_fee(&env, fee_bps);
+        Ok(())
+    }
+
+    pub fn get_keeper_fee(env: Env) -> u32 {
+        get_keeper_fee(&env)
+    }
+
+    /// Opt in to having dividends in the project's token reinvested in
+    /// `target`, or opt out with `None`
+    pub fn set_compounding(
+        env: Env,
+        project_id: u64,
+        investor: Address,
+        target: Option<CompoundTarget>,
+    ) -> Result<(), DistributionError> {
+        investor.require_auth();
+        match target {
+            Some(target) => {
+                get_shares(&env, project_id, &investor).ok_or(DistributionError::Unauthorized)?;
+                set_compounding(&env, project_id, &investor, &target);
+            }
+            None => remove_compounding(&env, project_id, &investor),
+        }
+        Ok(())
+    }
+
+    pub fn get_compounding(env: Env, project_id: u64, investor: Address) -> Option<CompoundTarget> {
+        get_compounding(&env, project_id, &investor)
+    }
+
+    /// Reinvest an investor's claimable dividends in their compounding
+    /// target, returning the amount reinvested
+    ///
+    /// Anyone can call this for an investor who opted in; `keeper` is paid
+    /// the keeper fee out of the dividends. The target must take the
+    /// project's token, and its own checks, such as a minimum contribution,
+    /// still apply.
+    pub fn compound(
+        env: Env,
+        project_id: u64,
+        investor: Address,
+        keeper: Address,
+    ) -> Result<i128, DistributionError> {
+        let token = get_project_token(&env, project_id).ok_or(DistributionError::NotInitialized)?;
+        let shares =
+            get_shares(&env, project_id, &investor).ok_or(DistributionError::Unauthorized)?;
+        let target = get_compounding(&env, project_id, &investor)
+            .ok_or(DistributionError::CompoundingNotSet)?;
+        if target_token(&env, &target) != Some(token.clone()) {
+            return Err(DistributionError::CompoundTargetMismatch);
+        }
+
+        let claimable = take_claimable(&env, project_id, &investor, shares, &token);
+        if claimable <= 0 {
+            return Err(DistributionError::NothingToClaim);
+        }
+
+        let keeper_fee = calculate_fee(claimable, get_keeper_fee(&env));
+        if keeper_fee > 0 {
+            TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &keeper,
+                &keeper_fee,
+            );
+        }
+        let amount = claimable - keeper_fee;
+        reinvest(&env, &target, &investor, &token, amount);
+
+        emit_compound_event(
+            &env, project_id, &investor, &token, &target, amount, keeper_fee,
+        );
+        Ok(amount)
     }
 
     /// Get contract admin
@@ -186,3 +917,733 @@ impl ProfitDistribution {
         get_admin(&env)
     }
 }
+
+/// Set investors' shares and update the project's total
+///
+/// Registered investors are settled at their old shares first; new ones
+/// start their profit accounting from now. The new total is checked before
+/// anything is written.
+fn store_investors(
+    env: &Env,
+    project_id: u64,
+    investors: &Map<Address, u32>,
+) -> Result<(), DistributionError> {
+    let mut total_shares = get_total_shares(env, project_id).unwrap_or(0);
+    for (investor, share_percentage) in investors.iter() {
+        let held = get_shares(env, project_id, &investor).unwrap_or(0);
+        total_shares = total_shares
+            .saturating_sub(held)
+            .checked_add(share_percentage)
+            .ok_or(DistributionError::TotalSharesNot100)?;
+    }
+    if total_shares > TOTAL_SHARES {
+        return Err(DistributionError::TotalSharesNot100);
+    }
+
+    for (investor, share_percentage) in investors.iter() {
+        let held = get_shares(env, project_id, &investor).unwrap_or(0);
+        settle_holder(env, project_id, &investor, held);
+        update_shares(env, project_id, &investor, share_percentage);
+    }
+
+    set_total_shares(env, project_id, total_shares);
+    Ok(())
+}
+
+/// Split [`TOTAL_SHARES`] between `backers` in proportion to their
+/// contributions by the largest remainder method, leaving out backers whose
+/// share rounds to zero
+fn proportional_shares(
+    env: &Env,
+    backers: &Vec<Address>,
+    contributions: &Map<Address, i128>,
+    total: i128,
+) -> Map<Address, u32> {
+    let mut shares: Vec<u32> = Vec::new(env);
+    let mut remainders: Vec<i128> = Vec::new(env);
+    let mut allocated: u32 = 0;
+    for backer in backers.iter() {
+        let scaled = contributions.get(backer).unwrap_or(0) * TOTAL_SHARES as i128;
+        let share = (scaled / total) as u32;
+        shares.push_back(share);
+        remainders.push_back(scaled % total);
+        allocated += share;
+    }
+
+    // Hand out what rounding down left over, largest remainder first and
+    // earlier backers first on ties
+    for _ in allocated..TOTAL_SHARES {
+        let mut largest = 0;
+        for index in 1..remainders.len() {
+            if remainders.get_unchecked(index) > remainders.get_unchecked(largest) {
+                largest = index;
+            }
+        }
+        shares.set(largest, shares.get_unchecked(largest) + 1);
+        remainders.set(largest, -1);
+    }
+
+    let mut investors = Map::new(env);
+    for (index, backer) in backers.iter().enumerate() {
+        let share = shares.get_unchecked(index as u32);
+        if share > 0 {
+            investors.set(backer, share);
+        }
+    }
+    investors
+}
+
+/// Add `token` to a project's reward tokens, unless it is there already
+fn add_reward_token(env: &Env, project_id: u64, token: &Address) -> Result<(), DistributionError> {
+    let mut tokens = get_reward_tokens(env, project_id);
+    if tokens.contains(token) {
+        return Ok(());
+    }
+    if tokens.len() >= MAX_REWARD_TOKENS {
+        return Err(DistributionError::TooManyRewardTokens);
+    }
+    tokens.push_back(token.clone());
+    set_reward_tokens(env, project_id, &tokens);
+    Ok(())
+}
+
+/// Take in a deposit of `token` and add it to the project's profit per
+/// share, at once or released over `duration` seconds, paying the treasury
+/// for unallocated shares under that policy
+fn deposit(
+    env: &Env,
+    project_id: u64,
+    token: &Address,
+    depositor: &Address,
+    amount: i128,
+    duration: u64,
+) -> Result<(), DistributionError> {
+    // Spreading the deposit over shares scales it by PRECISION
+    if amount <= 0 || amount.checked_mul(PRECISION).is_none() {
+        return Err(DistributionError::InvalidAmount);
+    }
+
+    // Other tokens would pay investors around their waterfall tiers
+    if get_waterfall(env, project_id).is_some()
+        && get_project_token(env, project_id).as_ref() != Some(token)
+    {
+        return Err(DistributionError::WaterfallActive);
+    }
+
+    depositor.require_auth();
+
+    let Allocation {
+        shares,
+        unallocated,
+        treasury,
+    } = allocate(env, project_id, amount)?;
+    // Finished streams only leave once accrued
+    if duration > 0 {
+        accrue(env, project_id, token);
+    }
+    let mut streams = get_vesting_streams(env, project_id, token);
+    if duration > 0 && streams.len() >= MAX_VESTING_STREAMS {
+        return Err(DistributionError::TooManyVestingStreams);
+    }
+
+    // Transfer tokens to contract
+    let token_client = TokenClient::new(env, token);
+    token_client.transfer(depositor, &env.current_contract_address(), &amount);
+
+    // Streams are split as they release, so investors registered later only
+    // share in what is released after they join
+    if duration > 0 {
+        let start = env.ledger().timestamp();
+        streams.push_back(VestingStream {
+            amount,
+            released: 0,
+            start,
+            end: start + duration,
+        });
+        set_vesting_streams(env, project_id, token, &streams);
+    } else {
+        if let Some(treasury) = treasury {
+            token_client.transfer(&env.current_contract_address(), &treasury, &unallocated);
+        }
+        let (delta, dust) = spread(amount - unallocated, shares);
+        let current_acc = get_acc_profit_per_share(env, project_id, token);
+        set_acc_profit_per_share(env, project_id, token, current_acc + delta);
+        set_dust(
+            env,
+            project_id,
+            token,
+            get_dust(env, project_id, token) + dust,
+        );
+    }
+
+    emit_deposit_event(env, project_id, token, amount);
+    Ok(())
+}
+
+/// Profits an investor accrued in a project's token, run through their
+/// tiers of the project's waterfall
+struct TierPayment {
+    creator: Address,
+    /// The investor's position once paid
+    position: WaterfallPosition,
+    /// What each tier paid
+    tiers: WaterfallProgress,
+}
+
+/// Run `amount` of profits accrued to an investor through their tiers of a
+/// project's waterfall, given what the tiers have paid them so far
+fn waterfall_tiers(
+    env: &Env,
+    project_id: u64,
+    waterfall: &Waterfall,
+    investor: &Address,
+    amount: i128,
+) -> TierPayment {
+    let mut position = waterfall_position(env, project_id, waterfall, investor);
+    let capital_returned = amount.min((position.capital - position.capital_returned).max(0));
+    let rest = amount - capital_returned;
+    let hurdle = calculate_percentage(position.capital, waterfall.preferred_return_bps);
+    let preferred_return = rest.min((hurdle - position.preferred_return).max(0));
+    let rest = rest - preferred_return;
+    let carry = calculate_percentage(rest, waterfall.carry_bps);
+    let tiers = WaterfallProgress {
+        capital_returned,
+        preferred_return,
+        profit: rest - carry,
+        carry,
+    };
+
+    position.capital_returned += tiers.capital_returned;
+    position.preferred_return += tiers.preferred_return;
+    position.profit += tiers.profit;
+    position.carry += tiers.carry;
+    TierPayment {
+        creator: waterfall.creator.clone(),
+        position,
+        tiers,
+    }
+}
+
+/// An investor's waterfall position, starting with their contribution as
+/// the capital to return
+fn waterfall_position(
+    env: &Env,
+    project_id: u64,
+    waterfall: &Waterfall,
+    investor: &Address,
+) -> WaterfallPosition {
+    get_waterfall_position(env, project_id, investor).unwrap_or_else(|| WaterfallPosition {
+        capital: ProjectLaunchClient::new(env, &waterfall.project_launch)
+            .get_user_contribution(&project_id, investor),
+        ..Default::default()
+    })
+}
+
+/// Move the part of a seller's capital, and of what the tiers have returned
+/// on it, that goes with `shares` of the `held` shares they sell
+fn move_waterfall_capital(
+    env: &Env,
+    project_id: u64,
+    waterfall: &Waterfall,
+    from: &Address,
+    to: &Address,
+    shares: u32,
+    held: u32,
+) {
+    let mut seller = waterfall_position(env, project_id, waterfall, from);
+    let mut buyer = waterfall_position(env, project_id, waterfall, to);
+    let part = |value: i128| value * shares as i128 / held as i128;
+
+    let capital = part(seller.capital);
+    let capital_returned = part(seller.capital_returned);
+    let preferred_return = part(seller.preferred_return);
+    seller.capital -= capital;
+    seller.capital_returned -= capital_returned;
+    seller.preferred_return -= preferred_return;
+    buyer.capital += capital;
+    buyer.capital_returned += capital_returned;
+    buyer.preferred_return += preferred_return;
+
+    set_waterfall_position(env, project_id, from, &seller);
+    set_waterfall_position(env, project_id, to, &buyer);
+}
+
+/// Record an investor's tier payment in the waterfall's progress and pay the
+/// creator their carry
+fn pay_waterfall(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+    token: &Address,
+    payment: &TierPayment,
+) {
+    set_waterfall_position(env, project_id, investor, &payment.position);
+
+    let tiers = &payment.tiers;
+    let mut progress = get_waterfall_progress(env, project_id);
+    progress.capital_returned += tiers.capital_returned;
+    progress.preferred_return += tiers.preferred_return;
+    progress.profit += tiers.profit;
+    progress.carry += tiers.carry;
+    set_waterfall_progress(env, project_id, &progress);
+
+    if tiers.carry > 0 {
+        TokenClient::new(env, token).transfer(
+            &env.current_contract_address(),
+            &payment.creator,
+            &tiers.carry,
+        );
+    }
+    emit_waterfall_event(env, project_id, investor, tiers);
+}
+
+/// Route a token's profits through the waterfall from now on, the first
+/// time it is the project's token while the waterfall is set
+fn start_waterfall(env: &Env, project_id: u64, token: &Address) {
+    if get_waterfall_start(env, project_id, token).is_none() {
+        accrue(env, project_id, token);
+        let start = get_acc_profit_per_share(env, project_id, token);
+        set_waterfall_start(env, project_id, token, start);
+    }
+}
+
+/// How a deposit is split under the project's unallocated policy
+struct Allocation {
+    /// Registered shares the rest of the deposit is split between
+    shares: u32,
+    /// Part of the deposit for shares no investor holds
+    unallocated: i128,
+    /// Where the unallocated part is paid, if there is any
+    treasury: Option<Address>,
+}
+
+fn allocate(env: &Env, project_id: u64, amount: i128) -> Result<Allocation, DistributionError> {
+    let total_shares =
+        get_total_shares(env, project_id).ok_or(DistributionError::NotInitialized)?;
+    let counted_shares = match get_unallocated_policy(env, project_id) {
+        UnallocatedPolicy::ProRata => total_shares,
+        UnallocatedPolicy::Treasury => TOTAL_SHARES,
+    };
+    if counted_shares == 0 {
+        return Err(DistributionError::InvalidAmount);
+    }
+    let unallocated = amount * (counted_shares - total_shares) as i128 / counted_shares as i128;
+    let treasury = match unallocated {
+        0 => None,
+        _ => Some(get_treasury(env).ok_or(DistributionError::NotInitialized)?),
+    };
+    Ok(Allocation {
+        shares: total_shares,
+        unallocated,
+        treasury,
+    })
+}
+
+/// A holder's part of a round at the shares they held when it opened
+fn round_entitlement(
+    env: &Env,
+    project_id: u64,
+    round_id: u64,
+    round: &DistributionRound,
+    holder: &Address,
+) -> i128 {
+    let shares = shares_at(env, project_id, holder, round_id);
+    round.amount * shares as i128 / round.total_shares as i128
+}
+
+/// Shares a holder held when a round opened
+///
+/// Holders without checkpoints have held the same shares since before
+/// rounds were recorded.
+fn shares_at(env: &Env, project_id: u64, holder: &Address, round_id: u64) -> u32 {
+    let checkpoints = get_share_checkpoints(env, project_id, holder);
+    if checkpoints.is_empty() {
+        return get_shares(env, project_id, holder).unwrap_or(0);
+    }
+    for checkpoint in checkpoints.iter().rev() {
+        if checkpoint.round <= round_id {
+            return checkpoint.shares;
+        }
+    }
+    0
+}
+
+/// Set a holder's shares, checkpointing them for the rounds opened from now
+/// on
+///
+/// Checkpoints no open round needs any more are dropped.
+fn update_shares(env: &Env, project_id: u64, holder: &Address, shares: u32) {
+    set_shares(env, project_id, holder, shares);
+
+    let next_round = get_round_count(env, project_id);
+    let mut checkpoints = get_share_checkpoints(env, project_id, holder);
+    if checkpoints.last().map(|checkpoint| checkpoint.round) == Some(next_round) {
+        checkpoints.pop_back();
+    }
+    checkpoints.push_back(ShareCheckpoint {
+        round: next_round,
+        shares,
+    });
+
+    let oldest_open = get_open_rounds(env, project_id)
+        .iter()
+        .min()
+        .unwrap_or(next_round);
+    while checkpoints.len() > 1 && checkpoints.get_unchecked(1).round <= oldest_open {
+        checkpoints.pop_front();
+    }
+    set_share_checkpoints(env, project_id, holder, &checkpoints);
+}
+
+/// Pay out an investor's claimable profits in `token`, returning the amount
+/// paid
+fn claim(env: &Env, project_id: u64, investor: &Address, shares: u32, token: &Address) -> i128 {
+    let amount = take_claimable(env, project_id, investor, shares, token);
+    if amount <= 0 {
+        return 0;
+    }
+
+    TokenClient::new(env, token).transfer(&env.current_contract_address(), investor, &amount);
+
+    emit_claim_event(env, project_id, investor, token, amount);
+    amount
+}
+
+/// Mark an investor's claimable profits in `token` as claimed, returning the
+/// amount for the caller to pay out
+fn take_claimable(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+    shares: u32,
+    token: &Address,
+) -> i128 {
+    accrue(env, project_id, token);
+    let (mut position, payment) = settled_position(env, project_id, investor, shares, token);
+    if let Some(payment) = payment {
+        // The carry is due even when nothing is left to claim
+        pay_waterfall(env, project_id, investor, token, &payment);
+        set_reward_position(env, project_id, investor, token, &position);
+    }
+    let amount = position.claimable_amount;
+    if amount <= 0 {
+        return 0;
+    }
+
+    position.claimable_amount = 0;
+    position.total_claimed += amount;
+    set_reward_position(env, project_id, investor, token, &position);
+    amount
+}
+
+/// Token a compounding target takes, if it exists
+fn target_token(env: &Env, target: &CompoundTarget) -> Option<Address> {
+    match target {
+        CompoundTarget::Project(launch, project_id) => {
+            match ProjectLaunchClient::new(env, launch).try_get_project(project_id) {
+                Ok(Ok(project)) => Some(project.token),
+                _ => None,
+            }
+        }
+        CompoundTarget::Pool(pools, pool_id) => {
+            match PoolFundingClient::new(env, pools).try_get_pool_token(pool_id) {
+                Ok(Ok(token)) => Some(token),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Pay `amount` of `token` into a compounding target for `investor`
+fn reinvest(env: &Env, target: &CompoundTarget, investor: &Address, token: &Address, amount: i128) {
+    let target_contract = match target {
+        CompoundTarget::Project(launch, _) => launch,
+        CompoundTarget::Pool(pools, _) => pools,
+    };
+
+    // The target pulls the dividends from this contract
+    let contract = env.current_contract_address();
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (contract.clone(), target_contract.clone(), amount).into_val(env),
+            },
+            sub_invocations: Vec::new(env),
+        }),
+    ]);
+    match target {
+        CompoundTarget::Project(launch, project_id) => {
+            ProjectFundingClient::new(env, launch)
+                .contribute_for(project_id, &contract, investor, &amount);
+        }
+        CompoundTarget::Pool(pools, pool_id) => {
+            PoolFundingClient::new(env, pools).deposit_for(pool_id, &contract, investor, &amount);
+        }
+    }
+}
+
+/// An investor's position in `token` with the profits accrued to `shares`
+/// since it was last updated added to its claimable amount
+///
+/// A holder's positions are settled whenever their shares change, so a
+/// missing position belongs to a token added since, whose profits all
+/// accrued to the current shares. Profits in the project's token accrued
+/// since its waterfall was set go through the investor's tiers, with the
+/// creator's carry left out of the claimable amount and returned as a
+/// payment for the caller to make.
+fn settled_position(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+    shares: u32,
+    token: &Address,
+) -> (RewardPosition, Option<TierPayment>) {
+    let acc_profit_per_share = match release(env, project_id, token) {
+        Some(release) => release.acc_profit_per_share,
+        None => get_acc_profit_per_share(env, project_id, token),
+    };
+    let mut position = get_reward_position(env, project_id, investor, token);
+    let last = position.accumulated_at_last_update;
+    let shares = shares as i128;
+    let accrued = shares * (acc_profit_per_share - last) / PRECISION;
+
+    let waterfall = match get_project_token(env, project_id) {
+        Some(project_token) if project_token == *token => get_waterfall(env, project_id),
+        _ => None,
+    };
+    let payment = waterfall.and_then(|waterfall| {
+        let start = get_waterfall_start(env, project_id, token)
+            .unwrap_or_default()
+            .max(last);
+        let routed = shares * (acc_profit_per_share - start) / PRECISION;
+        (routed > 0).then(|| waterfall_tiers(env, project_id, &waterfall, investor, routed))
+    });
+
+    position.claimable_amount +=
+        accrued - payment.as_ref().map_or(0, |payment| payment.tiers.carry);
+    position.accumulated_at_last_update = acc_profit_per_share;
+    (position, payment)
+}
+
+/// Split `amount` between `total_shares`, returning what it adds to the
+/// profit per share and the dust the division rounds off
+fn spread(amount: i128, total_shares: u32) -> (i128, i128) {
+    if total_shares == 0 {
+        return (0, amount);
+    }
+    let delta = amount * PRECISION / total_shares as i128;
+    (delta, amount - delta * total_shares as i128 / PRECISION)
+}
+
+/// A reward token's profit per share with everything its vesting streams
+/// have released by now, and what they have left to release
+struct Release {
+    acc_profit_per_share: i128,
+    streams: Vec<VestingStream>,
+    dust: i128,
+    /// Released for shares no investor holds, owed to the treasury
+    unallocated: i128,
+}
+
+/// Work out what a token's vesting streams have released since they were
+/// last accrued, split under the project's unallocated policy over the
+/// shares registered now, if it has any
+fn release(env: &Env, project_id: u64, token: &Address) -> Option<Release> {
+    let streams = get_vesting_streams(env, project_id, token);
+    if streams.is_empty() {
+        return None;
+    }
+
+    let now = env.ledger().timestamp();
+    let mut released = 0;
+    let mut remaining = Vec::new(env);
+    for mut stream in streams.iter() {
+        let vested = if now >= stream.end {
+            stream.amount
+        } else {
+            stream.amount * (now - stream.start) as i128 / (stream.end - stream.start) as i128
+        };
+        released += vested - stream.released;
+        stream.released = vested;
+        if vested < stream.amount {
+            remaining.push_back(stream);
+        }
+    }
+
+    let total_shares = get_total_shares(env, project_id).unwrap_or(0);
+    let unallocated = match (get_unallocated_policy(env, project_id), get_treasury(env)) {
+        (UnallocatedPolicy::Treasury, Some(_)) => {
+            released * (TOTAL_SHARES - total_shares) as i128 / TOTAL_SHARES as i128
+        }
+        _ => 0,
+    };
+    let (delta, dust) = spread(released - unallocated, total_shares);
+    Some(Release {
+        acc_profit_per_share: get_acc_profit_per_share(env, project_id, token) + delta,
+        streams: remaining,
+        dust,
+        unallocated,
+    })
+}
+
+/// Store what a token's vesting streams have released by now, which must
+/// happen before the project's total shares or unallocated policy change
+fn accrue(env: &Env, project_id: u64, token: &Address) {
+    let Some(release) = release(env, project_id, token) else {
+        return;
+    };
+    set_acc_profit_per_share(env, project_id, token, release.acc_profit_per_share);
+    set_vesting_streams(env, project_id, token, &release.streams);
+    set_dust(
+        env,
+        project_id,
+        token,
+        get_dust(env, project_id, token) + release.dust,
+    );
+    if release.unallocated > 0 {
+        if let Some(treasury) = get_treasury(env) {
+            TokenClient::new(env, token).transfer(
+                &env.current_contract_address(),
+                &treasury,
+                &release.unallocated,
+            );
+        }
+    }
+}
+
+/// Settle a holder's positions in every reward token at the shares they
+/// held so far, before the shares change
+fn settle_holder(env: &Env, project_id: u64, investor: &Address, shares: u32) {
+    for token in get_reward_tokens(env, project_id).iter() {
+        accrue(env, project_id, &token);
+        let (position, payment) = settled_position(env, project_id, investor, shares, &token);
+        if let Some(payment) = payment {
+            pay_waterfall(env, project_id, investor, &token, &payment);
+        }
+        set_reward_position(env, project_id, investor, &token, &position);
+    }
+}
+
+/// An allowance as it stands now, empty once expired
+fn live_allowance(
+    env: &Env,
+    project_id: u64,
+    owner: &Address,
+    spender: &Address,
+) -> ShareAllowance {
+    match get_allowance(env, project_id, owner, spender) {
+        Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+        _ => ShareAllowance {
+            shares: 0,
+            expiration_ledger: 0,
+        },
+    }
+}
+
+/// Replace `spender`'s allowance over `owner`'s shares
+fn approve(
+    env: &Env,
+    project_id: u64,
+    owner: &Address,
+    spender: &Address,
+    shares: u32,
+    expiration_ledger: u32,
+) -> Result<(), DistributionError> {
+    if shares > 0 && expiration_ledger < env.ledger().sequence() {
+        return Err(DistributionError::InvalidAmount);
+    }
+
+    let allowance = ShareAllowance {
+        shares,
+        expiration_ledger,
+    };
+    set_allowance(env, project_id, owner, spender, &allowance);
+    emit_approve_event(env, project_id, owner, spender, &allowance);
+    Ok(())
+}
+
+/// Take `shares` off `spender`'s allowance over `owner`'s shares
+fn spend_allowance(
+    env: &Env,
+    project_id: u64,
+    owner: &Address,
+    spender: &Address,
+    shares: u32,
+) -> Result<(), DistributionError> {
+    let mut allowance = live_allowance(env, project_id, owner, spender);
+    if allowance.shares < shares {
+        return Err(DistributionError::InsufficientAllowance);
+    }
+    allowance.shares -= shares;
+    set_allowance(env, project_id, owner, spender, &allowance);
+    Ok(())
+}
+
+/// Only let the project's registered share token through
+fn require_share_token(env: &Env, project_id: u64) -> Result<(), DistributionError> {
+    get_share_token(env, project_id)
+        .ok_or(DistributionError::NotInitialized)?
+        .require_auth();
+    Ok(())
+}
+
+/// Settle a holder and take `shares` basis points out of the project
+fn burn_shares(
+    env: &Env,
+    project_id: u64,
+    from: &Address,
+    shares: u32,
+) -> Result<(), DistributionError> {
+    if shares == 0 {
+        return Err(DistributionError::InvalidSharePercentage);
+    }
+
+    let held = get_shares(env, project_id, from).ok_or(DistributionError::Unauthorized)?;
+    if held < shares {
+        return Err(DistributionError::InsufficientBalance);
+    }
+
+    settle_holder(env, project_id, from, held);
+    update_shares(env, project_id, from, held - shares);
+    let total_shares = get_total_shares(env, project_id).unwrap_or(0);
+    set_total_shares(env, project_id, total_shares.saturating_sub(shares));
+
+    emit_burn_event(env, project_id, from, shares);
+    Ok(())
+}
+
+/// Settle both holders and move `shares` basis points between them
+fn move_shares(
+    env: &Env,
+    project_id: u64,
+    from: &Address,
+    to: &Address,
+    shares: u32,
+) -> Result<(), DistributionError> {
+    if shares == 0 {
+        return Err(DistributionError::InvalidSharePercentage);
+    }
+    if from == to {
+        return Err(DistributionError::InvalidAmount);
+    }
+
+    let sent = get_shares(env, project_id, from).ok_or(DistributionError::Unauthorized)?;
+    if sent < shares {
+        return Err(DistributionError::InsufficientBalance);
+    }
+    let received = get_shares(env, project_id, to).unwrap_or(0);
+
+    settle_holder(env, project_id, from, sent);
+    settle_holder(env, project_id, to, received);
+    if let Some(waterfall) = get_waterfall(env, project_id) {
+        move_waterfall_capital(env, project_id, &waterfall, from, to, shares, sent);
+    }
+    update_shares(env, project_id, from, sent - shares);
+    update_shares(env, project_id, to, received + shares);
+
+    emit_transfer_event(env, project_id, from, to, shares);
+    Ok(())
+}
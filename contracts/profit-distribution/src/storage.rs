@@ -1,8 +1,8 @@
-use soroban_sdk::{Address, Env, Map};
+use soroban_sdk::{Address, Env, Vec};
 
-use crate::{
-    errors::ContractError,
-    types::{DataKey, InvestorShare},
+use crate::types::{
+    CompoundTarget, DataKey, DistributionRound, RewardPosition, ShareAllowance, ShareCheckpoint,
+    UnallocatedPolicy, VestingStream, Waterfall, WaterfallPosition, WaterfallProgress,
 };
 
 pub fn set_project_token(env: &Env, project_id: u64, token: &Address) {
@@ -17,16 +17,96 @@ pub fn get_project_token(env: &Env, project_id: u64) -> Option<Address> {
         .get(&DataKey::ProjectToken(project_id))
 }
 
-pub fn set_investor_share(env: &Env, project_id: u64, investor: &Address, share: &InvestorShare) {
+pub fn set_reward_tokens(env: &Env, project_id: u64, tokens: &Vec<Address>) {
     env.storage()
         .persistent()
-        .set(&DataKey::InvestorShare(project_id, investor.clone()), share);
+        .set(&DataKey::RewardTokens(project_id), tokens);
 }
 
-pub fn get_investor_share(env: &Env, project_id: u64, investor: &Address) -> Option<InvestorShare> {
+pub fn get_reward_tokens(env: &Env, project_id: u64) -> Vec<Address> {
     env.storage()
         .persistent()
-        .get(&DataKey::InvestorShare(project_id, investor.clone()))
+        .get(&DataKey::RewardTokens(project_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_shares(env: &Env, project_id: u64, investor: &Address, shares: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Shares(project_id, investor.clone()), &shares);
+}
+
+pub fn get_shares(env: &Env, project_id: u64, investor: &Address) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Shares(project_id, investor.clone()))
+}
+
+pub fn set_reward_position(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+    token: &Address,
+    position: &RewardPosition,
+) {
+    env.storage().persistent().set(
+        &DataKey::RewardPosition(project_id, investor.clone(), token.clone()),
+        position,
+    );
+}
+
+pub fn get_reward_position(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+    token: &Address,
+) -> RewardPosition {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RewardPosition(
+            project_id,
+            investor.clone(),
+            token.clone(),
+        ))
+        .unwrap_or_default()
+}
+
+pub fn set_allowance(
+    env: &Env,
+    project_id: u64,
+    owner: &Address,
+    spender: &Address,
+    allowance: &ShareAllowance,
+) {
+    env.storage().persistent().set(
+        &DataKey::Allowance(project_id, owner.clone(), spender.clone()),
+        allowance,
+    );
+}
+
+pub fn get_allowance(
+    env: &Env,
+    project_id: u64,
+    owner: &Address,
+    spender: &Address,
+) -> Option<ShareAllowance> {
+    env.storage().persistent().get(&DataKey::Allowance(
+        project_id,
+        owner.clone(),
+        spender.clone(),
+    ))
+}
+
+pub fn set_share_token(env: &Env, project_id: u64, token: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ShareToken(project_id), token);
+}
+
+pub fn get_share_token(env: &Env, project_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ShareToken(project_id))
 }
 
 pub fn set_total_shares(env: &Env, project_id: u64, total_shares: u32) {
@@ -41,19 +121,235 @@ pub fn get_total_shares(env: &Env, project_id: u64) -> Option<u32> {
         .get(&DataKey::TotalShares(project_id))
 }
 
-pub fn set_acc_profit_per_share(env: &Env, project_id: u64, acc: i128) {
+pub fn set_acc_profit_per_share(env: &Env, project_id: u64, token: &Address, acc: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::AccProfitPerShare(project_id, token.clone()), &acc);
+}
+
+pub fn get_acc_profit_per_share(env: &Env, project_id: u64, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AccProfitPerShare(project_id, token.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_unallocated_policy(env: &Env, project_id: u64, policy: UnallocatedPolicy) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::UnallocatedPolicy(project_id), &policy);
+}
+
+pub fn get_unallocated_policy(env: &Env, project_id: u64) -> UnallocatedPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnallocatedPolicy(project_id))
+        .unwrap_or(UnallocatedPolicy::ProRata)
+}
+
+pub fn set_dust(env: &Env, project_id: u64, token: &Address, dust: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Dust(project_id, token.clone()), &dust);
+}
+
+pub fn get_dust(env: &Env, project_id: u64, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Dust(project_id, token.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_vesting_streams(
+    env: &Env,
+    project_id: u64,
+    token: &Address,
+    streams: &Vec<VestingStream>,
+) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::VestingStreams(project_id, token.clone()), streams);
+}
+
+pub fn get_vesting_streams(env: &Env, project_id: u64, token: &Address) -> Vec<VestingStream> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VestingStreams(project_id, token.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_compounding(env: &Env, project_id: u64, investor: &Address, target: &CompoundTarget) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Compounding(project_id, investor.clone()), target);
+}
+
+pub fn remove_compounding(env: &Env, project_id: u64, investor: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Compounding(project_id, investor.clone()));
+}
+
+pub fn get_compounding(env: &Env, project_id: u64, investor: &Address) -> Option<CompoundTarget> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Compounding(project_id, investor.clone()))
+}
+
+pub fn set_keeper_fee(env: &Env, fee_bps: u32) {
+    env.storage().instance().set(&DataKey::KeeperFee, &fee_bps);
+}
+
+pub fn get_keeper_fee(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::KeeperFee)
+        .unwrap_or(0)
+}
+
+pub fn set_round_count(env: &Env, project_id: u64, count: u64) {
     env.storage()
         .persistent()
-        .set(&DataKey::AccProfitPerShare(project_id), &acc);
+        .set(&DataKey::RoundCount(project_id), &count);
 }
 
-pub fn get_acc_profit_per_share(env: &Env, project_id: u64) -> i128 {
+pub fn get_round_count(env: &Env, project_id: u64) -> u64 {
     env.storage()
         .persistent()
-        .get(&DataKey::AccProfitPerShare(project_id))
+        .get(&DataKey::RoundCount(project_id))
         .unwrap_or(0)
 }
 
+pub fn set_round(env: &Env, project_id: u64, round_id: u64, round: &DistributionRound) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Round(project_id, round_id), round);
+}
+
+pub fn get_round(env: &Env, project_id: u64, round_id: u64) -> Option<DistributionRound> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Round(project_id, round_id))
+}
+
+pub fn set_open_rounds(env: &Env, project_id: u64, rounds: &Vec<u64>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::OpenRounds(project_id), rounds);
+}
+
+pub fn get_open_rounds(env: &Env, project_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OpenRounds(project_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_round_claimed(env: &Env, project_id: u64, round_id: u64, holder: &Address) {
+    env.storage().persistent().set(
+        &DataKey::RoundClaimed(project_id, round_id, holder.clone()),
+        &true,
+    );
+}
+
+pub fn is_round_claimed(env: &Env, project_id: u64, round_id: u64, holder: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::RoundClaimed(project_id, round_id, holder.clone()))
+}
+
+pub fn set_share_checkpoints(
+    env: &Env,
+    project_id: u64,
+    holder: &Address,
+    checkpoints: &Vec<ShareCheckpoint>,
+) {
+    env.storage().persistent().set(
+        &DataKey::ShareCheckpoints(project_id, holder.clone()),
+        checkpoints,
+    );
+}
+
+pub fn get_share_checkpoints(env: &Env, project_id: u64, holder: &Address) -> Vec<ShareCheckpoint> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ShareCheckpoints(project_id, holder.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_waterfall(env: &Env, project_id: u64, waterfall: &Waterfall) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Waterfall(project_id), waterfall);
+}
+
+pub fn get_waterfall(env: &Env, project_id: u64) -> Option<Waterfall> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Waterfall(project_id))
+}
+
+pub fn set_waterfall_progress(env: &Env, project_id: u64, progress: &WaterfallProgress) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::WaterfallProgress(project_id), progress);
+}
+
+pub fn get_waterfall_progress(env: &Env, project_id: u64) -> WaterfallProgress {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WaterfallProgress(project_id))
+        .unwrap_or_default()
+}
+
+pub fn set_waterfall_start(
+    env: &Env,
+    project_id: u64,
+    token: &Address,
+    acc_profit_per_share: i128,
+) {
+    env.storage().persistent().set(
+        &DataKey::WaterfallStart(project_id, token.clone()),
+        &acc_profit_per_share,
+    );
+}
+
+pub fn get_waterfall_start(env: &Env, project_id: u64, token: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WaterfallStart(project_id, token.clone()))
+}
+
+pub fn set_waterfall_position(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+    position: &WaterfallPosition,
+) {
+    env.storage().persistent().set(
+        &DataKey::WaterfallPosition(project_id, investor.clone()),
+        position,
+    );
+}
+
+pub fn get_waterfall_position(
+    env: &Env,
+    project_id: u64,
+    investor: &Address,
+) -> Option<WaterfallPosition> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WaterfallPosition(project_id, investor.clone()))
+}
+
+pub fn set_treasury(env: &Env, treasury: &Address) {
+    env.storage().instance().set(&DataKey::Treasury, treasury);
+}
+
+pub fn get_treasury(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Treasury)
+}
+
 pub fn set_admin(env: &Env, admin: &Address) {
     env.storage().instance().set(&DataKey::Admin, admin);
 }
@@ -1,9 +1,129 @@
 #![cfg(test)]
 
 use super::*;
+use shared::types::Project;
 use soroban_sdk::testutils::{Address as _, Ledger};
 use soroban_sdk::token::StellarAssetClient;
-use soroban_sdk::{Address, Env, Map};
+use soroban_sdk::{contracttype, vec, Address, Bytes, Env, Map};
+
+#[contracttype]
+enum MockKey {
+    TotalRaised,
+    Status,
+    Token,
+    Contribution(Address),
+}
+
+/// Stand-in for the ProjectLaunch contract that records contributions directly
+#[contract]
+pub struct MockProjectLaunch;
+
+#[contractimpl]
+impl MockProjectLaunch {
+    pub fn contribute(env: Env, contributor: Address, amount: i128) {
+        let raised: i128 = env
+            .storage()
+            .instance()
+            .get(&MockKey::TotalRaised)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&MockKey::TotalRaised, &(raised + amount));
+        env.storage()
+            .instance()
+            .set(&MockKey::Contribution(contributor), &amount);
+    }
+
+    pub fn contribute_for(
+        env: Env,
+        _project_id: u64,
+        payer: Address,
+        contributor: Address,
+        amount: i128,
+    ) {
+        let token: Address = env.storage().instance().get(&MockKey::Token).unwrap();
+        TokenClient::new(&env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+        let contributed = Self::get_user_contribution(env.clone(), 0, contributor.clone());
+        env.storage()
+            .instance()
+            .set(&MockKey::Contribution(contributor), &(contributed + amount));
+    }
+
+    pub fn set_token(env: Env, token: Address) {
+        env.storage().instance().set(&MockKey::Token, &token);
+    }
+
+    pub fn set_status(env: Env, status: ProjectStatus) {
+        env.storage().instance().set(&MockKey::Status, &status);
+    }
+
+    pub fn get_project(env: Env, _project_id: u64) -> Project {
+        Project {
+            creator: env.current_contract_address(),
+            funding_goal: 0,
+            deadline: 0,
+            token: env
+                .storage()
+                .instance()
+                .get(&MockKey::Token)
+                .unwrap_or(env.current_contract_address()),
+            status: env
+                .storage()
+                .instance()
+                .get(&MockKey::Status)
+                .unwrap_or(ProjectStatus::Completed),
+            metadata_hash: Bytes::new(&env),
+            total_raised: env
+                .storage()
+                .instance()
+                .get(&MockKey::TotalRaised)
+                .unwrap_or(0),
+            created_at: 0,
+        }
+    }
+
+    pub fn get_user_contribution(env: Env, _project_id: u64, contributor: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&MockKey::Contribution(contributor))
+            .unwrap_or(0)
+    }
+}
+
+/// Stand-in for a SubscriptionPool whose pools all hold one token
+#[contract]
+pub struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn set_pool_token(env: Env, token: Address) {
+        env.storage().instance().set(&MockKey::Token, &token);
+    }
+
+    pub fn get_pool_token(env: Env, _pool_id: u64) -> Address {
+        env.storage().instance().get(&MockKey::Token).unwrap()
+    }
+
+    pub fn deposit_for(env: Env, pool_id: u64, payer: Address, _subscriber: Address, amount: i128) {
+        let token = Self::get_pool_token(env.clone(), pool_id);
+        TokenClient::new(&env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+    }
+}
+
+/// A completed project in a mock ProjectLaunch, backed with `contributions`
+fn setup_launch(
+    env: &Env,
+    contributions: &[i128],
+) -> (MockProjectLaunchClient<'static>, Vec<Address>) {
+    let launch = MockProjectLaunchClient::new(env, &env.register_contract(None, MockProjectLaunch));
+    let mut backers = Vec::new(env);
+    for amount in contributions {
+        let backer = Address::generate(env);
+        launch.contribute(&backer, amount);
+        backers.push_back(backer);
+    }
+    (launch, backers)
+}
 
 fn setup_test() -> (Env, ProfitDistributionClient<'static>, Address, Address) {
     let env = Env::default();
@@ -13,14 +133,16 @@ fn setup_test() -> (Env, ProfitDistributionClient<'static>, Address, Address) {
     let client = ProfitDistributionClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
-    let token = env.register_stellar_asset_contract(admin.clone());
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
 
     (env, client, admin, token)
 }
 
 #[test]
 fn test_initialize() {
-    let (env, client, admin, _) = setup_test();
+    let (_env, client, admin, _) = setup_test();
     client.initialize(&admin);
     assert_eq!(client.get_admin(), Some(admin));
 }
@@ -76,3 +198,997 @@ fn test_full_distribution_flow() {
         600
     ); // 400 + 40% of 500 = 400+200=600
 }
+
+#[test]
+fn test_register_investors_from_launch() {
+    let (env, client, admin, token) = setup_test();
+    let token_admin = StellarAssetClient::new(&env, &token);
+    client.initialize(&admin);
+    client.set_token(&1, &token);
+    let (launch, backers) = setup_launch(&env, &[5_000, 3_000, 2_000]);
+
+    let shares = client.register_investors_from_launch(&1, &launch.address, &backers);
+    for (backer, expected) in backers.iter().zip([5_000, 3_000, 2_000]) {
+        assert_eq!(shares.get(backer.clone()), Some(expected));
+        assert_eq!(
+            client.get_investor_share(&1, &backer).share_percentage,
+            expected
+        );
+    }
+
+    let profit_provider = Address::generate(&env);
+    token_admin.mint(&profit_provider, &1000);
+    client.deposit_profits(&1, &profit_provider, &1000);
+    assert_eq!(client.claim_dividends(&1, &backers.get(1).unwrap()), 300);
+}
+
+#[test]
+fn test_register_investors_from_launch_rounding() {
+    let (env, client, admin, _) = setup_test();
+    client.initialize(&admin);
+
+    // Left over basis points go to the largest remainders, earliest first
+    let (launch, backers) = setup_launch(&env, &[1, 1, 1]);
+    let shares = client.register_investors_from_launch(&1, &launch.address, &backers);
+    for (backer, expected) in backers.iter().zip([3_334, 3_333, 3_333]) {
+        assert_eq!(shares.get(backer), Some(expected));
+    }
+
+    // Backers too small for a basis point get no share
+    let (launch, backers) = setup_launch(&env, &[100_000_000, 1, 1]);
+    let shares = client.register_investors_from_launch(&2, &launch.address, &backers);
+    assert_eq!(shares.len(), 1);
+    assert_eq!(shares.get(backers.get(0).unwrap()), Some(10_000));
+    assert_eq!(
+        client.try_get_investor_share(&2, &backers.get(1).unwrap()),
+        Err(Ok(DistributionError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_register_investors_from_launch_rejects_mismatch() {
+    let (env, client, admin, _) = setup_test();
+    client.initialize(&admin);
+    let (launch, backers) = setup_launch(&env, &[600, 400]);
+
+    // Every backer, once, and nobody else
+    let missing = vec![&env, backers.get(0).unwrap()];
+    let duplicated = vec![
+        &env,
+        backers.get(0).unwrap(),
+        backers.get(1).unwrap(),
+        backers.get(1).unwrap(),
+    ];
+    let mut stranger = backers.clone();
+    stranger.push_back(Address::generate(&env));
+    for list in [missing, duplicated, stranger] {
+        assert_eq!(
+            client.try_register_investors_from_launch(&1, &launch.address, &list),
+            Err(Ok(DistributionError::ContributionMismatch))
+        );
+    }
+
+    launch.set_status(&ProjectStatus::Active);
+    assert_eq!(
+        client.try_register_investors_from_launch(&1, &launch.address, &backers),
+        Err(Ok(DistributionError::ProjectNotCompleted))
+    );
+
+    // No project to read from
+    assert_eq!(
+        client.try_register_investors_from_launch(&1, &Address::generate(&env), &backers),
+        Err(Ok(DistributionError::ProjectNotCompleted))
+    );
+}
+
+fn setup_investors(
+    env: &Env,
+    client: &ProfitDistributionClient,
+    token: &Address,
+) -> (Address, Address) {
+    client.set_token(&1, token);
+    let investor1 = Address::generate(env);
+    let investor2 = Address::generate(env);
+    let mut investors = Map::new(env);
+    investors.set(investor1.clone(), 6000);
+    investors.set(investor2.clone(), 4000);
+    client.register_investors(&1, &investors);
+    (investor1, investor2)
+}
+
+fn deposit(env: &Env, client: &ProfitDistributionClient, token: &Address, amount: i128) {
+    let provider = Address::generate(env);
+    StellarAssetClient::new(env, token).mint(&provider, &amount);
+    client.deposit_profits(&1, &provider, &amount);
+}
+
+#[test]
+fn test_transfer_shares_settles_dividends() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let buyer = Address::generate(&env);
+
+    deposit(&env, &client, &token, 1000);
+    client.transfer_shares(&1, &investor1, &buyer, &2000);
+    deposit(&env, &client, &token, 1000);
+
+    // Dividends from before the transfer stay with the seller
+    let seller = client.get_investor_share(&1, &investor1);
+    assert_eq!(seller.share_percentage, 4000);
+    assert_eq!(seller.claimable_amount, 600 + 400);
+    let bought = client.get_investor_share(&1, &buyer);
+    assert_eq!(bought.share_percentage, 2000);
+    assert_eq!(bought.claimable_amount, 200);
+    assert_eq!(
+        client.get_investor_share(&1, &investor2).claimable_amount,
+        800
+    );
+
+    // Selling everything keeps what was earned claimable
+    client.transfer_shares(&1, &investor1, &buyer, &4000);
+    client.claim_dividends(&1, &investor1);
+    assert_eq!(
+        soroban_sdk::token::Client::new(&env, &token).balance(&investor1),
+        1000
+    );
+    assert_eq!(client.get_investor_share(&1, &buyer).share_percentage, 6000);
+}
+
+#[test]
+fn test_transfer_shares_rejects_invalid() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(
+        client.try_transfer_shares(&1, &investor1, &investor2, &6001),
+        Err(Ok(DistributionError::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_transfer_shares(&1, &investor1, &investor2, &0),
+        Err(Ok(DistributionError::InvalidSharePercentage))
+    );
+    assert_eq!(
+        client.try_transfer_shares(&1, &investor1, &investor1, &100),
+        Err(Ok(DistributionError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer_shares(&1, &stranger, &investor1, &100),
+        Err(Ok(DistributionError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_transfer_shares_from_spends_allowance() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let spender = Address::generate(&env);
+    let expiration = env.ledger().sequence() + 100;
+
+    assert_eq!(
+        client.try_transfer_shares_from(&1, &spender, &investor1, &investor2, &1),
+        Err(Ok(DistributionError::InsufficientAllowance))
+    );
+
+    client.approve_shares(&1, &investor1, &spender, &1500, &expiration);
+    assert_eq!(client.get_share_allowance(&1, &investor1, &spender), 1500);
+
+    client.transfer_shares_from(&1, &spender, &investor1, &investor2, &1000);
+    assert_eq!(client.get_share_allowance(&1, &investor1, &spender), 500);
+    assert_eq!(
+        client.get_investor_share(&1, &investor2).share_percentage,
+        5000
+    );
+    assert_eq!(
+        client.try_transfer_shares_from(&1, &spender, &investor1, &investor2, &501),
+        Err(Ok(DistributionError::InsufficientAllowance))
+    );
+
+    // Expired allowances can't be spent
+    env.ledger().set_sequence_number(expiration + 1);
+    assert_eq!(client.get_share_allowance(&1, &investor1, &spender), 0);
+    assert_eq!(
+        client.try_transfer_shares_from(&1, &spender, &investor1, &investor2, &1),
+        Err(Ok(DistributionError::InsufficientAllowance))
+    );
+    assert_eq!(
+        client.try_approve_shares(&1, &investor1, &spender, &1, &expiration),
+        Err(Ok(DistributionError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_register_investors_again_keeps_dividends() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let investor3 = Address::generate(&env);
+
+    deposit(&env, &client, &token, 1000);
+    client.claim_dividends(&1, &investor1);
+
+    // Shrink investor1 to make room for investor3
+    let mut update = Map::new(&env);
+    update.set(investor1.clone(), 3000);
+    update.set(investor3.clone(), 3000);
+    client.register_investors(&1, &update);
+    deposit(&env, &client, &token, 1000);
+
+    let share1 = client.get_investor_share(&1, &investor1);
+    assert_eq!(share1.share_percentage, 3000);
+    assert_eq!(share1.total_claimed, 600);
+    assert_eq!(share1.claimable_amount, 300);
+    assert_eq!(
+        client.get_investor_share(&1, &investor2).claimable_amount,
+        400 + 400
+    );
+    assert_eq!(
+        client.get_investor_share(&1, &investor3).claimable_amount,
+        300
+    );
+}
+
+#[test]
+fn test_register_investors_rejects_over_100_without_writes() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+
+    let mut update = Map::new(&env);
+    update.set(investor1.clone(), 5000);
+    update.set(Address::generate(&env), 1001);
+    assert_eq!(
+        client.try_register_investors(&1, &update),
+        Err(Ok(DistributionError::TotalSharesNot100))
+    );
+    update.set(investor2.clone(), 0);
+    assert_eq!(
+        client.try_register_investors(&1, &update),
+        Err(Ok(DistributionError::InvalidSharePercentage))
+    );
+
+    assert_eq!(
+        client.get_investor_share(&1, &investor1).share_percentage,
+        6000
+    );
+    deposit(&env, &client, &token, 1000);
+    assert_eq!(
+        client.get_investor_share(&1, &investor2).claimable_amount,
+        400
+    );
+}
+
+#[test]
+fn test_remove_investors() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+
+    deposit(&env, &client, &token, 1000);
+    client.remove_investors(&1, &vec![&env, investor2.clone()]);
+    deposit(&env, &client, &token, 1200);
+
+    // investor2 keeps what was earned, and only investor1 shares later profits
+    let removed = client.get_investor_share(&1, &investor2);
+    assert_eq!(removed.share_percentage, 0);
+    assert_eq!(removed.claimable_amount, 400);
+    assert_eq!(
+        client.get_investor_share(&1, &investor1).claimable_amount,
+        600 + 1200
+    );
+
+    assert_eq!(
+        client.try_remove_investors(&1, &vec![&env, Address::generate(&env)]),
+        Err(Ok(DistributionError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_unallocated_policy() {
+    let (env, client, admin, token) = setup_test();
+    let balances = soroban_sdk::token::Client::new(&env, &token);
+    client.initialize(&admin);
+    client.set_token(&1, &token);
+    let investor = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let mut investors = Map::new(&env);
+    investors.set(investor.clone(), 6000);
+    client.register_investors(&1, &investors);
+
+    // Investors split everything by default
+    deposit(&env, &client, &token, 1200);
+    assert_eq!(
+        client.get_investor_share(&1, &investor).claimable_amount,
+        1200
+    );
+
+    assert_eq!(
+        client.try_set_unallocated_policy(&1, &UnallocatedPolicy::Treasury),
+        Err(Ok(DistributionError::NotInitialized))
+    );
+    client.set_treasury(&treasury);
+    client.set_unallocated_policy(&1, &UnallocatedPolicy::Treasury);
+
+    deposit(&env, &client, &token, 1000);
+    assert_eq!(balances.balance(&treasury), 400);
+    assert_eq!(
+        client.get_investor_share(&1, &investor).claimable_amount,
+        1200 + 600
+    );
+}
+
+#[test]
+fn test_sweep_dust() {
+    let (env, client, admin, token) = setup_test();
+    let balances = soroban_sdk::token::Client::new(&env, &token);
+    client.initialize(&admin);
+    client.set_token(&1, &token);
+    let mut investors = Map::new(&env);
+    for _ in 0..3 {
+        investors.set(Address::generate(&env), 3333);
+    }
+    client.register_investors(&1, &investors);
+
+    // 1000 over 9999 shares leaves 999 claimable
+    deposit(&env, &client, &token, 1000);
+    for investor in investors.keys() {
+        assert_eq!(
+            client.get_investor_share(&1, &investor).claimable_amount,
+            333
+        );
+    }
+    assert_eq!(client.get_dust(&1, &token), 1);
+
+    assert_eq!(
+        client.try_sweep_dust(&1, &token),
+        Err(Ok(DistributionError::NotInitialized))
+    );
+    let treasury = Address::generate(&env);
+    client.set_treasury(&treasury);
+    assert_eq!(client.sweep_dust(&1, &token), 1);
+    assert_eq!(balances.balance(&treasury), 1);
+    assert_eq!(client.get_dust(&1, &token), 0);
+    assert_eq!(
+        client.try_sweep_dust(&1, &token),
+        Err(Ok(DistributionError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_multi_token_rewards() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let provider = Address::generate(&env);
+    StellarAssetClient::new(&env, &usdc).mint(&provider, &500);
+
+    assert_eq!(
+        client.try_deposit_token_profits(&1, &usdc, &provider, &500),
+        Err(Ok(DistributionError::UnknownRewardToken))
+    );
+    client.add_reward_token(&1, &usdc);
+    assert_eq!(
+        client.get_reward_tokens(&1),
+        vec![&env, token.clone(), usdc.clone()]
+    );
+
+    deposit(&env, &client, &token, 1000);
+    client.deposit_token_profits(&1, &usdc, &provider, &500);
+
+    // Accumulators are independent, and survive share transfers
+    client.transfer_shares(&1, &investor1, &investor2, &3000);
+    let pending = client.get_pending_rewards(&1, &investor1);
+    assert_eq!(pending.get(token.clone()), Some(600));
+    assert_eq!(pending.get(usdc.clone()), Some(300));
+    assert_eq!(
+        client
+            .get_reward_position(&1, &investor2, &usdc)
+            .claimable_amount,
+        200
+    );
+
+    let claimed = client.claim_all(&1, &investor1);
+    assert_eq!(claimed.get(token.clone()), Some(600));
+    assert_eq!(claimed.get(usdc.clone()), Some(300));
+    assert_eq!(
+        soroban_sdk::token::Client::new(&env, &usdc).balance(&investor1),
+        300
+    );
+    assert_eq!(
+        client
+            .get_reward_position(&1, &investor1, &usdc)
+            .total_claimed,
+        300
+    );
+    assert_eq!(
+        client.try_claim_all(&1, &investor1),
+        Err(Ok(DistributionError::NothingToClaim))
+    );
+
+    // Only what is owed is claimed
+    client.claim_dividends(&1, &investor2);
+    assert_eq!(client.claim_all(&1, &investor2).len(), 1);
+}
+
+#[test]
+fn test_reward_token_added_later_skips_earlier_holders() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, _) = setup_investors(&env, &client, &token);
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let provider = Address::generate(&env);
+    StellarAssetClient::new(&env, &usdc).mint(&provider, &1000);
+
+    client.add_reward_token(&1, &usdc);
+    client.deposit_token_profits(&1, &usdc, &provider, &1000);
+
+    // A holder joining afterwards earns nothing from earlier deposits
+    let late = Address::generate(&env);
+    client.transfer_shares(&1, &investor1, &late, &1000);
+    assert_eq!(
+        client
+            .get_reward_position(&1, &late, &usdc)
+            .claimable_amount,
+        0
+    );
+    assert_eq!(
+        client
+            .get_reward_position(&1, &investor1, &usdc)
+            .claimable_amount,
+        600
+    );
+
+    for _ in 0..8 {
+        let other = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        client.add_reward_token(&1, &other);
+    }
+    let extra = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    assert_eq!(
+        client.try_add_reward_token(&1, &extra),
+        Err(Ok(DistributionError::TooManyRewardTokens))
+    );
+}
+
+#[test]
+fn test_vested_profits_release_linearly() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let provider = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&provider, &9000);
+    let day = 86_400;
+
+    client.deposit_vested_profits(&1, &token, &provider, &9000, &(90 * day));
+    assert_eq!(
+        client.get_investor_share(&1, &investor1).claimable_amount,
+        0
+    );
+    assert_eq!(
+        client.try_claim_dividends(&1, &investor1),
+        Err(Ok(DistributionError::NothingToClaim))
+    );
+
+    // A third released after 30 days
+    env.ledger().with_mut(|ledger| ledger.timestamp += 30 * day);
+    assert_eq!(client.claim_dividends(&1, &investor1), 1800);
+    assert_eq!(
+        client.get_investor_share(&1, &investor2).claimable_amount,
+        1200
+    );
+
+    // Shares sold halfway leave the rest of the release to the buyer
+    let buyer = Address::generate(&env);
+    client.transfer_shares(&1, &investor1, &buyer, &6000);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 90 * day);
+    assert_eq!(
+        client.get_investor_share(&1, &investor1).claimable_amount,
+        0
+    );
+    assert_eq!(client.claim_dividends(&1, &buyer), 3600);
+    assert_eq!(client.claim_dividends(&1, &investor2), 3600);
+    assert_eq!(client.get_vesting_streams(&1, &token).len(), 0);
+}
+
+#[test]
+fn test_vested_profits_follow_share_changes() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let provider = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&provider, &2200);
+
+    client.deposit_vested_profits(&1, &token, &provider, &1200, &100);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 50);
+
+    // What was released before the removal is split between both
+    client.remove_investors(&1, &vec![&env, investor2.clone()]);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 50);
+    assert_eq!(
+        client.get_investor_share(&1, &investor2).claimable_amount,
+        240
+    );
+    assert_eq!(
+        client.get_investor_share(&1, &investor1).claimable_amount,
+        360 + 600
+    );
+
+    assert_eq!(
+        client.try_deposit_vested_profits(&1, &token, &provider, &1000, &0),
+        Err(Ok(DistributionError::InvalidAmount))
+    );
+    for _ in 0..10 {
+        client.deposit_vested_profits(&1, &token, &provider, &100, &100);
+    }
+    assert_eq!(
+        client.try_deposit_vested_profits(&1, &token, &provider, &100, &100),
+        Err(Ok(DistributionError::TooManyVestingStreams))
+    );
+}
+
+#[test]
+fn test_vested_profits_under_treasury_policy() {
+    let (env, client, admin, token) = setup_test();
+    let balances = soroban_sdk::token::Client::new(&env, &token);
+    client.initialize(&admin);
+    client.set_token(&1, &token);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&treasury);
+    client.set_unallocated_policy(&1, &UnallocatedPolicy::Treasury);
+
+    let early = Address::generate(&env);
+    let mut investors = Map::new(&env);
+    investors.set(early.clone(), 5000);
+    client.register_investors(&1, &investors);
+    let provider = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&provider, &1000);
+    client.deposit_vested_profits(&1, &token, &provider, &1000, &100);
+    assert_eq!(balances.balance(&treasury), 0);
+
+    // An investor registered halfway only shares in the second half, which
+    // the treasury was not paid for
+    env.ledger().with_mut(|ledger| ledger.timestamp += 50);
+    let late = Address::generate(&env);
+    let mut investors = Map::new(&env);
+    investors.set(late.clone(), 2000);
+    client.register_investors(&1, &investors);
+    assert_eq!(balances.balance(&treasury), 250);
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 50);
+    assert_eq!(client.claim_dividends(&1, &early), 500);
+    assert_eq!(client.claim_dividends(&1, &late), 100);
+    assert_eq!(balances.balance(&treasury), 400);
+}
+
+#[test]
+fn test_compound_reinvests_dividends() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, _) = setup_investors(&env, &client, &token);
+    let (launch, _) = setup_launch(&env, &[]);
+    launch.set_token(&token);
+    let keeper = Address::generate(&env);
+
+    client.set_keeper_fee(&100);
+    client.set_compounding(
+        &1,
+        &investor1,
+        &Some(CompoundTarget::Project(launch.address.clone(), 7)),
+    );
+    deposit(&env, &client, &token, 12_000);
+
+    // 1% of the 7200 claimable goes to the keeper
+    assert_eq!(client.compound(&1, &investor1, &keeper), 7128);
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&keeper), 72);
+    assert_eq!(token_client.balance(&launch.address), 7128);
+    assert_eq!(launch.get_user_contribution(&7, &investor1), 7128);
+    assert_eq!(
+        client.get_investor_share(&1, &investor1).total_claimed,
+        7200
+    );
+
+    assert_eq!(
+        client.try_compound(&1, &investor1, &keeper),
+        Err(Ok(DistributionError::NothingToClaim))
+    );
+
+    // Opting out leaves dividends to be claimed
+    client.set_compounding(&1, &investor1, &None);
+    deposit(&env, &client, &token, 12_000);
+    assert_eq!(
+        client.try_compound(&1, &investor1, &keeper),
+        Err(Ok(DistributionError::CompoundingNotSet))
+    );
+    assert_eq!(client.claim_dividends(&1, &investor1), 7200);
+}
+
+#[test]
+fn test_compound_checks_target_and_fee() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (_, investor2) = setup_investors(&env, &client, &token);
+    let keeper = Address::generate(&env);
+    let pools = MockPoolClient::new(&env, &env.register_contract(None, MockPool));
+
+    assert_eq!(
+        client.try_set_keeper_fee(&501),
+        Err(Ok(DistributionError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_compounding(
+            &1,
+            &Address::generate(&env),
+            &Some(CompoundTarget::Pool(pools.address.clone(), 3)),
+        ),
+        Err(Ok(DistributionError::Unauthorized))
+    );
+
+    let other_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    pools.set_pool_token(&other_token);
+    let target = CompoundTarget::Pool(pools.address.clone(), 3);
+    client.set_compounding(&1, &investor2, &Some(target.clone()));
+    assert_eq!(client.get_compounding(&1, &investor2), Some(target));
+    deposit(&env, &client, &token, 12_000);
+    assert_eq!(
+        client.try_compound(&1, &investor2, &keeper),
+        Err(Ok(DistributionError::CompoundTargetMismatch))
+    );
+
+    // Without a keeper fee the whole 4800 is reinvested
+    pools.set_pool_token(&token);
+    assert_eq!(client.compound(&1, &investor2, &keeper), 4800);
+    assert_eq!(TokenClient::new(&env, &token).balance(&pools.address), 4800);
+}
+
+#[test]
+fn test_rounds_pay_holders_of_record() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let buyer = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&depositor, &2400);
+
+    let first = client.deposit_round(&1, &token, &depositor, &1200, &100);
+    client.transfer_shares(&1, &investor1, &buyer, &2000);
+    let second = client.deposit_round(&1, &token, &depositor, &1200, &100);
+
+    // Each round is split at the shares held when it opened
+    assert_eq!(client.get_round_shares(&1, &first, &investor1), 6000);
+    assert_eq!(client.get_round_shares(&1, &first, &buyer), 0);
+    let mut expected = Map::new(&env);
+    expected.set(first, 720);
+    expected.set(second, 480);
+    assert_eq!(client.get_claimable_rounds(&1, &investor1), expected);
+    let mut expected = Map::new(&env);
+    expected.set(second, 240);
+    assert_eq!(client.get_claimable_rounds(&1, &buyer), expected);
+
+    assert_eq!(client.claim_round(&1, &investor1, &first), 720);
+    assert_eq!(
+        client.try_claim_round(&1, &investor1, &first),
+        Err(Ok(DistributionError::AlreadyClaimed))
+    );
+    assert_eq!(
+        client.try_claim_round(&1, &buyer, &first),
+        Err(Ok(DistributionError::NothingToClaim))
+    );
+    assert_eq!(client.claim_round(&1, &buyer, &second), 240);
+    assert_eq!(client.claim_round(&1, &investor2, &second), 480);
+    assert_eq!(client.get_round(&1, &second).claimed, 720);
+
+    // Rounds are kept apart from the running dividends
+    assert_eq!(
+        client.try_claim_dividends(&1, &investor2),
+        Err(Ok(DistributionError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_expired_round_credits_unclaimed() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let depositor = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&depositor, &3200);
+
+    let round_id = client.deposit_round(&1, &token, &depositor, &1200, &100);
+    assert_eq!(client.claim_round(&1, &investor2, &round_id), 480);
+    assert_eq!(
+        client.try_close_round(&1, &round_id),
+        Err(Ok(DistributionError::RoundNotExpired))
+    );
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 100);
+    assert_eq!(
+        client.try_claim_round(&1, &investor1, &round_id),
+        Err(Ok(DistributionError::RoundExpired))
+    );
+    assert_eq!(client.get_claimable_rounds(&1, &investor1).len(), 0);
+    assert_eq!(client.close_round(&1, &round_id), 720);
+    assert_eq!(TokenClient::new(&env, &token).balance(&depositor), 2000);
+    assert_eq!(client.get_open_rounds(&1).len(), 0);
+
+    // What was left unclaimed goes to the current shareholders
+    assert_eq!(client.claim_dividends(&1, &investor1), 432);
+    assert_eq!(client.claim_dividends(&1, &investor2), 288);
+    assert_eq!(
+        client.try_close_round(&1, &round_id),
+        Err(Ok(DistributionError::RoundExpired))
+    );
+    assert_eq!(
+        client.try_claim_round(&1, &investor1, &7),
+        Err(Ok(DistributionError::RoundNotFound))
+    );
+    assert_eq!(
+        client.try_deposit_round(&1, &token, &depositor, &100, &u64::MAX),
+        Err(Ok(DistributionError::InvalidAmount))
+    );
+
+    for _ in 0..20 {
+        client.deposit_round(&1, &token, &depositor, &100, &100);
+    }
+    assert_eq!(
+        client.try_deposit_round(&1, &token, &depositor, &100, &100),
+        Err(Ok(DistributionError::TooManyOpenRounds))
+    );
+}
+
+#[test]
+fn test_round_under_treasury_policy() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    client.set_token(&1, &token);
+    let investor1 = Address::generate(&env);
+    let investor2 = Address::generate(&env);
+    let mut investors = Map::new(&env);
+    investors.set(investor1.clone(), 3000);
+    investors.set(investor2.clone(), 2000);
+    client.register_investors(&1, &investors);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&treasury);
+    client.set_unallocated_policy(&1, &UnallocatedPolicy::Treasury);
+    let depositor = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    // The treasury is paid for the unheld half, holders split the rest
+    let round_id = client.deposit_round(&1, &token, &depositor, &1000, &100);
+    assert_eq!(TokenClient::new(&env, &token).balance(&treasury), 500);
+    let mut expected = Map::new(&env);
+    expected.set(round_id, 300);
+    assert_eq!(client.get_claimable_rounds(&1, &investor1), expected);
+    assert_eq!(client.claim_round(&1, &investor1, &round_id), 300);
+    assert_eq!(client.claim_round(&1, &investor2, &round_id), 200);
+    assert_eq!(client.get_round(&1, &round_id).claimed, 500);
+}
+
+#[test]
+fn test_waterfall_returns_capital_before_carry() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, investor2) = setup_investors(&env, &client, &token);
+    let creator = Address::generate(&env);
+
+    // Capital follows each investor's contribution, not their shares
+    let (launch, _) = setup_launch(&env, &[]);
+    launch.contribute(&investor1, &3_000);
+    launch.contribute(&investor2, &5_000);
+
+    // Profits deposited before the waterfall bypass it
+    deposit(&env, &client, &token, 1_000);
+    client.set_waterfall(
+        &1,
+        &Waterfall {
+            project_launch: launch.address.clone(),
+            preferred_return_bps: 1_000,
+            carry_bps: 2_000,
+            creator: creator.clone(),
+        },
+    );
+
+    // Everything goes to an investor until they have their capital back
+    deposit(&env, &client, &token, 5_000);
+    assert_eq!(client.claim_dividends(&1, &investor1), 600 + 3_000);
+
+    // Then a 10% preferred return, and the creator only carries what is left
+    deposit(&env, &client, &token, 5_000);
+    assert_eq!(client.claim_dividends(&1, &investor1), 300 + 2_160);
+    assert_eq!(
+        client.get_waterfall_position(&1, &investor1),
+        WaterfallPosition {
+            capital: 3_000,
+            capital_returned: 3_000,
+            preferred_return: 300,
+            profit: 2_160,
+            carry: 540,
+        }
+    );
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&creator), 540);
+
+    // The larger contribution is still being returned
+    assert_eq!(client.claim_dividends(&1, &investor2), 400 + 4_000);
+    assert_eq!(
+        client.get_waterfall_progress(&1),
+        WaterfallProgress {
+            capital_returned: 7_000,
+            preferred_return: 300,
+            profit: 2_160,
+            carry: 540,
+        }
+    );
+
+    // A buyer takes over capital that has already been returned
+    let buyer = Address::generate(&env);
+    client.transfer_shares(&1, &investor1, &buyer, &3_000);
+    deposit(&env, &client, &token, 1_000);
+    let position = client.get_waterfall_position(&1, &buyer);
+    assert_eq!(position.capital, 1_500);
+    assert_eq!(position.capital_returned, 1_500);
+    assert_eq!(position.profit, 240);
+    assert_eq!(position.carry, 60);
+    assert_eq!(client.get_investor_share(&1, &buyer).claimable_amount, 240);
+    assert_eq!(
+        client
+            .get_waterfall_position(&1, &investor2)
+            .capital_returned,
+        4_400
+    );
+    assert_eq!(client.claim_dividends(&1, &buyer), 240);
+    assert_eq!(token_client.balance(&creator), 600);
+}
+
+#[test]
+fn test_waterfall_capital_moves_with_shares() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, _) = setup_investors(&env, &client, &token);
+    let creator = Address::generate(&env);
+    let (launch, _) = setup_launch(&env, &[]);
+    launch.contribute(&investor1, &1_000);
+    client.set_waterfall(
+        &1,
+        &Waterfall {
+            project_launch: launch.address.clone(),
+            preferred_return_bps: 0,
+            carry_bps: 5_000,
+            creator: creator.clone(),
+        },
+    );
+
+    // Half the shares take half the capital, and half of what was returned
+    deposit(&env, &client, &token, 1_000);
+    let buyer = Address::generate(&env);
+    client.transfer_shares(&1, &investor1, &buyer, &3_000);
+    let position = client.get_waterfall_position(&1, &buyer);
+    assert_eq!(position.capital, 500);
+    assert_eq!(position.capital_returned, 300);
+
+    // Both sides finish returning their capital before any carry
+    deposit(&env, &client, &token, 1_000);
+    let expected = WaterfallPosition {
+        capital: 500,
+        capital_returned: 500,
+        preferred_return: 0,
+        profit: 50,
+        carry: 50,
+    };
+    assert_eq!(client.get_waterfall_position(&1, &investor1), expected);
+    assert_eq!(client.get_waterfall_position(&1, &buyer), expected);
+    assert_eq!(client.claim_dividends(&1, &investor1), 600 + 250);
+    assert_eq!(client.claim_dividends(&1, &buyer), 250);
+    assert_eq!(TokenClient::new(&env, &token).balance(&creator), 100);
+}
+
+#[test]
+fn test_waterfall_follows_new_project_token() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, _) = setup_investors(&env, &client, &token);
+    let creator = Address::generate(&env);
+    let (launch, _) = setup_launch(&env, &[]);
+    launch.contribute(&investor1, &100);
+
+    deposit(&env, &client, &token, 1_000);
+    client.set_waterfall(
+        &1,
+        &Waterfall {
+            project_launch: launch.address.clone(),
+            preferred_return_bps: 0,
+            carry_bps: 5_000,
+            creator: creator.clone(),
+        },
+    );
+
+    // The new token starts below the old token's profit per share
+    let new_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.set_token(&1, &new_token);
+    deposit(&env, &client, &new_token, 1_000);
+    assert_eq!(client.claim_dividends(&1, &investor1), 100 + 250);
+    assert_eq!(TokenClient::new(&env, &new_token).balance(&creator), 250);
+
+    // The old token's profits bypassed the waterfall and it is closed to new ones
+    assert_eq!(
+        client.claim_all(&1, &investor1).get(token.clone()),
+        Some(600)
+    );
+    assert_eq!(
+        client.try_deposit_token_profits(&1, &token, &admin, &1_000),
+        Err(Ok(DistributionError::WaterfallActive))
+    );
+}
+
+#[test]
+fn test_waterfall_config_and_bypass() {
+    let (env, client, admin, token) = setup_test();
+    client.initialize(&admin);
+    let (investor1, _) = setup_investors(&env, &client, &token);
+    let (launch, _) = setup_launch(&env, &[]);
+    let waterfall = Waterfall {
+        project_launch: launch.address.clone(),
+        preferred_return_bps: 0,
+        carry_bps: 10_001,
+        creator: Address::generate(&env),
+    };
+    assert_eq!(
+        client.try_set_waterfall(&1, &waterfall),
+        Err(Ok(DistributionError::InvalidSharePercentage))
+    );
+    client.set_waterfall(
+        &1,
+        &Waterfall {
+            carry_bps: 2_000,
+            ..waterfall
+        },
+    );
+
+    // Other reward tokens and rounds would pay around the waterfall
+    let other = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.add_reward_token(&1, &other);
+    let provider = Address::generate(&env);
+    StellarAssetClient::new(&env, &other).mint(&provider, &1_200);
+    StellarAssetClient::new(&env, &token).mint(&provider, &1_200);
+    assert_eq!(
+        client.try_deposit_token_profits(&1, &other, &provider, &1_200),
+        Err(Ok(DistributionError::WaterfallActive))
+    );
+    assert_eq!(
+        client.try_deposit_vested_profits(&1, &other, &provider, &1_200, &100),
+        Err(Ok(DistributionError::WaterfallActive))
+    );
+    assert_eq!(
+        client.try_deposit_round(&1, &token, &provider, &1_200, &100),
+        Err(Ok(DistributionError::WaterfallActive))
+    );
+
+    assert_eq!(
+        client.get_waterfall_progress(&1),
+        WaterfallProgress::default()
+    );
+    assert_eq!(
+        client.get_waterfall_position(&1, &investor1),
+        WaterfallPosition::default()
+    );
+    assert_eq!(
+        client.try_get_waterfall_position(&1, &Address::generate(&env)),
+        Err(Ok(DistributionError::Unauthorized))
+    );
+}
@@ -1,5 +1,6 @@
 use soroban_sdk::{contracttype, Address};
 
+/// An investor's shares and their profits in the project's token
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InvestorShare {
@@ -10,12 +11,135 @@ pub struct InvestorShare {
     pub total_claimed: i128,
 }
 
+/// An investor's profits in one reward token
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RewardPosition {
+    pub accumulated_at_last_update: i128,
+    pub claimable_amount: i128,
+    pub total_claimed: i128,
+}
+
+/// Profits deposited to be released linearly over a period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingStream {
+    pub amount: i128,
+    pub released: i128,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Profits set aside for the holders of record when they were deposited
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionRound {
+    pub token: Address,
+    pub depositor: Address,
+    pub amount: i128,
+    pub total_shares: u32, // shares the amount is split between
+    pub claimed: i128,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub closed: bool,
+}
+
+/// A holder's shares from a round on, until their next checkpoint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShareCheckpoint {
+    pub round: u64,
+    pub shares: u32,
+}
+
+/// How a project's profits flow to investors and its creator
+///
+/// Each investor's profits first return their capital, then pay them a
+/// preferred return on it, and only past that hurdle give the creator their
+/// carry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Waterfall {
+    pub project_launch: Address, // records each investor's contribution, their capital
+    pub preferred_return_bps: u32, // hurdle, in basis points of the capital
+    pub carry_bps: u32,          // creator's cut of profits past the hurdle
+    pub creator: Address,
+}
+
+/// Profits paid through each tier of a project's waterfall
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WaterfallProgress {
+    pub capital_returned: i128,
+    pub preferred_return: i128,
+    pub profit: i128, // to investors past the hurdle
+    pub carry: i128,
+}
+
+/// An investor's progress through a project's waterfall
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WaterfallPosition {
+    pub capital: i128, // the investor's contribution
+    pub capital_returned: i128,
+    pub preferred_return: i128,
+    pub profit: i128,
+    pub carry: i128, // paid to the creator out of the investor's profits
+}
+
+/// Shares a spender may move on an owner's behalf
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShareAllowance {
+    pub shares: u32, // basis points
+    pub expiration_ledger: u32,
+}
+
+/// Where profits go for the shares no investor holds
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnallocatedPolicy {
+    /// Split everything between the registered investors
+    ProRata,
+    /// Pay the unregistered part of 100% to the treasury
+    Treasury,
+}
+
+/// Where an investor's dividends are reinvested when compounded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CompoundTarget {
+    /// Contribute to a project in a ProjectLaunch contract
+    Project(Address, u64),
+    /// Pay into a pool of a SubscriptionPool contract
+    Pool(Address, u64),
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
-    ProjectToken(u64),           // project_id -> token address
-    InvestorShare(u64, Address), // project_id, investor -> InvestorShare
-    TotalShares(u64),            // project_id -> total registered shares
-    AccProfitPerShare(u64),      // project_id -> accumulated profit per share
+    ProjectToken(u64),                     // project_id -> token address
+    RewardTokens(u64),                     // project_id -> Vec<Address> of reward tokens
+    Shares(u64, Address),                  // project_id, investor -> shares in basis points
+    TotalShares(u64),                      // project_id -> total registered shares
+    AccProfitPerShare(u64, Address),       // project_id, token -> accumulated profit per share
+    RewardPosition(u64, Address, Address), // project_id, investor, token -> RewardPosition
+    Allowance(u64, Address, Address),      // project_id, owner, spender -> ShareAllowance
+    ShareToken(u64),                       // project_id -> share token address
+    UnallocatedPolicy(u64),                // project_id -> UnallocatedPolicy
+    Dust(u64, Address),                    // project_id, token -> rounding left unclaimable
+    VestingStreams(u64, Address), // project_id, token -> Vec<VestingStream> still releasing
+    Compounding(u64, Address),    // project_id, investor -> CompoundTarget
+    RoundCount(u64),              // project_id -> rounds opened so far
+    Round(u64, u64),              // project_id, round_id -> DistributionRound
+    OpenRounds(u64),              // project_id -> Vec<u64> of rounds not closed yet
+    RoundClaimed(u64, u64, Address), // project_id, round_id, holder -> bool
+    ShareCheckpoints(u64, Address), // project_id, holder -> Vec<ShareCheckpoint>
+    Waterfall(u64),               // project_id -> Waterfall
+    WaterfallProgress(u64),       // project_id -> WaterfallProgress
+    WaterfallStart(u64, Address), // project_id, token -> profit per share when it joined the waterfall
+    WaterfallPosition(u64, Address), // project_id, investor -> WaterfallPosition
+    KeeperFee,
+    Treasury,
     Admin,
 }
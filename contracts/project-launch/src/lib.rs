@@ -7,35 +7,11 @@ use soroban_sdk::{
 use shared::{
     constants::{MAX_PROJECT_DURATION, MIN_CONTRIBUTION, MIN_FUNDING_GOAL, MIN_PROJECT_DURATION},
     errors::Error,
-    events::{CONTRIBUTION_MADE, PROJECT_CREATED, PROJECT_FAILED, REFUND_ISSUED},
+    events::{publish, ContributionMade, ProjectCreated, ProjectFailed, RefundIssued},
+    types::{Project, ProjectStatus},
     utils::verify_future_timestamp,
 };
 
-/// Project status enumeration
-#[contracttype]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-#[repr(u32)]
-pub enum ProjectStatus {
-    Active = 0,
-    Completed = 1,
-    Failed = 2,
-    Cancelled = 3,
-}
-
-/// Project structure
-#[contracttype]
-#[derive(Clone)]
-pub struct Project {
-    pub creator: Address,
-    pub funding_goal: i128,
-    pub deadline: u64,
-    pub token: Address,
-    pub status: ProjectStatus,
-    pub metadata_hash: Bytes,
-    pub total_raised: i128,
-    pub created_at: u64,
-}
-
 /// Contract state
 #[contracttype]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -85,7 +61,7 @@ impl ProjectLaunch {
         let current_time = env.ledger().timestamp();
         let duration = deadline.saturating_sub(current_time);
 
-        if duration < MIN_PROJECT_DURATION || duration > MAX_PROJECT_DURATION {
+        if !(MIN_PROJECT_DURATION..=MAX_PROJECT_DURATION).contains(&duration) {
             return Err(Error::InvalidDeadline);
         }
 
@@ -123,9 +99,15 @@ impl ProjectLaunch {
             .set(&(DataKey::Project, project_id), &project);
 
         // Emit event
-        env.events().publish(
-            (PROJECT_CREATED,),
-            (project_id, creator, funding_goal, deadline, token),
+        publish(
+            &env,
+            ProjectCreated {
+                project_id,
+                creator,
+                funding_goal,
+                deadline,
+                token,
+            },
         );
 
         Ok(project_id)
@@ -144,53 +126,27 @@ impl ProjectLaunch {
         }
         contributor.require_auth();
 
-        // Get project
-        let mut project: Project = env
-            .storage()
-            .instance()
-            .get(&(DataKey::Project, project_id))
-            .ok_or(Error::ProjectNotFound)?;
-
-        // Validate project status and deadline
-        if project.status != ProjectStatus::Active {
-            return Err(Error::ProjectNotActive);
-        }
+        record_contribution(&env, project_id, &contributor, &contributor, amount)
+    }
 
-        let current_time = env.ledger().timestamp();
-        if current_time >= project.deadline {
-            return Err(Error::DeadlinePassed);
+    /// Contribute to a project on `contributor`'s behalf, paid by `payer`
+    ///
+    /// The contribution, and any refund of it, is `contributor`'s. This lets
+    /// other contracts fund projects for their users, such as profit
+    /// distribution reinvesting dividends.
+    pub fn contribute_for(
+        env: Env,
+        project_id: u64,
+        payer: Address,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if amount < MIN_CONTRIBUTION {
+            return Err(Error::ContributionTooLow);
         }
+        payer.require_auth();
 
-        // Update project totals
-        project.total_raised += amount;
-        env.storage()
-            .instance()
-            .set(&(DataKey::Project, project_id), &project);
-
-        // Perform token transfer
-        let token_client = TokenClient::new(&env, &project.token);
-        token_client.transfer(&contributor, &env.current_contract_address(), &amount);
-
-        // 1. Store aggregated individual contribution (Scalable O(1))
-        let contribution_key = (DataKey::ContributionAmount, project_id, contributor.clone());
-        let current_contribution: i128 = env
-            .storage()
-            .persistent()
-            .get(&contribution_key)
-            .unwrap_or(0);
-
-        let new_contribution = current_contribution.checked_add(amount).unwrap();
-        env.storage()
-            .persistent()
-            .set(&contribution_key, &new_contribution);
-
-        // Emit event
-        env.events().publish(
-            (CONTRIBUTION_MADE,),
-            (project_id, contributor, amount, project.total_raised),
-        );
-
-        Ok(())
+        record_contribution(&env, project_id, &payer, &contributor, amount)
     }
 
     /// Get project details
@@ -264,7 +220,7 @@ impl ProjectLaunch {
             // Project failed due to insufficient funding
             project.status = ProjectStatus::Failed;
             // Emit event to indicate project failure
-            env.events().publish((PROJECT_FAILED,), project_id);
+            publish(&env, ProjectFailed { project_id });
         }
 
         // Store updated project
@@ -331,8 +287,14 @@ impl ProjectLaunch {
             .set(&refund_key, &true);
 
         // Emit refund event
-        env.events()
-            .publish((REFUND_ISSUED,), (project_id, contributor, contribution_amount));
+        publish(
+            &env,
+            RefundIssued {
+                project_id,
+                contributor,
+                amount: contribution_amount,
+            },
+        );
 
         Ok(contribution_amount)
     }
@@ -351,6 +313,68 @@ impl ProjectLaunch {
     }
 }
 
+/// Take `amount` from `payer` and record it as `contributor`'s contribution
+fn record_contribution(
+    env: &Env,
+    project_id: u64,
+    payer: &Address,
+    contributor: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    // Get project
+    let mut project: Project = env
+        .storage()
+        .instance()
+        .get(&(DataKey::Project, project_id))
+        .ok_or(Error::ProjectNotFound)?;
+
+    // Validate project status and deadline
+    if project.status != ProjectStatus::Active {
+        return Err(Error::ProjectNotActive);
+    }
+
+    let current_time = env.ledger().timestamp();
+    if current_time >= project.deadline {
+        return Err(Error::DeadlinePassed);
+    }
+
+    // Update project totals
+    project.total_raised += amount;
+    env.storage()
+        .instance()
+        .set(&(DataKey::Project, project_id), &project);
+
+    // Perform token transfer
+    let token_client = TokenClient::new(env, &project.token);
+    token_client.transfer(payer, &env.current_contract_address(), &amount);
+
+    // 1. Store aggregated individual contribution (Scalable O(1))
+    let contribution_key = (DataKey::ContributionAmount, project_id, contributor.clone());
+    let current_contribution: i128 = env
+        .storage()
+        .persistent()
+        .get(&contribution_key)
+        .unwrap_or(0);
+
+    let new_contribution = current_contribution.checked_add(amount).unwrap();
+    env.storage()
+        .persistent()
+        .set(&contribution_key, &new_contribution);
+
+    // Emit event
+    publish(
+        env,
+        ContributionMade {
+            project_id,
+            contributor: contributor.clone(),
+            amount,
+            total_raised: project.total_raised,
+        },
+    );
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -511,6 +535,53 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_contribute_for() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, ProjectLaunch);
+        let client = ProjectLaunchClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let payer = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        client.initialize(&admin);
+
+        let token_admin = Address::generate(&env);
+        let (token, token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+        env.ledger().set_timestamp(1000000);
+        let deadline = 1000000 + MIN_PROJECT_DURATION + 86400;
+        let project_id = client.create_project(
+            &creator,
+            &MIN_FUNDING_GOAL,
+            &deadline,
+            &token,
+            &Bytes::from_slice(&env, b"QmHash123"),
+        );
+        token_admin_client.mint(&payer, &100_0000000);
+
+        // The payer's tokens are credited to the contributor
+        client.contribute_for(&project_id, &payer, &contributor, &MIN_CONTRIBUTION);
+
+        assert_eq!(token_client.balance(&payer), 90_0000000);
+        assert_eq!(token_client.balance(&client.address), 10_0000000);
+        assert_eq!(
+            client.get_user_contribution(&project_id, &contributor),
+            MIN_CONTRIBUTION
+        );
+        assert_eq!(client.get_user_contribution(&project_id, &payer), 0);
+
+        let result = client.try_contribute_for(
+            &project_id,
+            &payer,
+            &contributor,
+            &(MIN_CONTRIBUTION - 1),
+        );
+        assert_eq!(result, Err(Ok(Error::ContributionTooLow)));
+    }
+
     #[test]
     #[should_panic] // Since require_auth() will fail without mocking or proper signature
     fn test_create_project_unauthorized() {
@@ -554,7 +625,7 @@ mod tests {
 
         // Register token
         let token_admin = Address::generate(&env);
-        let (token, token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+        let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
         let metadata_hash = Bytes::from_slice(&env, b"QmHash123");
 
         // Create project
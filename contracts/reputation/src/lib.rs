@@ -2,7 +2,7 @@
 
 use shared::constants::{REPUTATION_MAX, REPUTATION_MIN, REPUTATION_START};
 use shared::errors::Error;
-use shared::events::{BADGE_EARNED, REPUTATION_UPDATED, USER_REGISTERED};
+use shared::events::{publish, BadgeEarned, ReputationUpdated, UserRegistered};
 use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
 
 /// Storage keys for the reputation contract
@@ -118,7 +118,7 @@ impl ReputationContract {
             .set(&DataKey::Profile(user.clone()), &profile);
 
         // Emit registration event
-        env.events().publish((USER_REGISTERED,), user);
+        publish(&env, UserRegistered { user });
 
         Ok(profile)
     }
@@ -145,7 +145,7 @@ impl ReputationContract {
 
         // Calculate new score with clamping
         let new_score = profile.score.saturating_add(delta);
-        let clamped_score = new_score.max(REPUTATION_MIN).min(REPUTATION_MAX);
+        let clamped_score = new_score.clamp(REPUTATION_MIN, REPUTATION_MAX);
 
         // Update profile
         profile.score = clamped_score;
@@ -154,8 +154,13 @@ impl ReputationContract {
             .set(&DataKey::Profile(user.clone()), &profile);
 
         // Emit score update event
-        env.events()
-            .publish((REPUTATION_UPDATED,), (user, clamped_score));
+        publish(
+            &env,
+            ReputationUpdated {
+                user,
+                score: clamped_score,
+            },
+        );
 
         Ok(clamped_score)
     }
@@ -194,7 +199,13 @@ impl ReputationContract {
             .set(&DataKey::Profile(user.clone()), &profile);
 
         // Emit badge award event
-        env.events().publish((BADGE_EARNED,), (user, badge as u32));
+        publish(
+            &env,
+            BadgeEarned {
+                user,
+                badge: badge as u32,
+            },
+        );
 
         Ok(())
     }
@@ -0,0 +1,167 @@
+#![no_std]
+
+//! SEP-41 token standing for one project's profit shares
+//!
+//! The token keeps no balances of its own. Balances are the shares the
+//! ProfitDistribution contract holds for the project, in basis points, and
+//! every transfer, approval and burn is forwarded to it, so dividends are
+//! settled whenever shares change hands and `claim_dividends` pays whoever
+//! holds the tokens.
+
+use shared::{errors::DistributionError, interfaces::ShareLedgerClient};
+use soroban_sdk::{
+    contract, contractimpl, contractmeta, contracttype, panic_with_error, token::TokenInterface,
+    Address, Env, String,
+};
+use soroban_token_sdk::{metadata::TokenMetadata, TokenUtils};
+
+#[cfg(test)]
+mod tests;
+
+/// Shares are whole basis points
+const DECIMALS: u32 = 0;
+
+contractmeta!(key = "name", val = "Share Token Contract");
+
+/// Storage keys for the share token
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    /// ProfitDistribution contract keeping the shares
+    Ledger,
+    /// Project whose shares the token stands for
+    ProjectId,
+}
+
+#[contract]
+pub struct ShareToken;
+
+#[contractimpl]
+impl ShareToken {
+    /// Bind the token to a project's shares in `ledger`
+    ///
+    /// The ledger's admin still has to register the token with
+    /// `set_share_token` before it can move shares.
+    pub fn initialize(env: Env, ledger: Address, project_id: u64, name: String, symbol: String) {
+        if env.storage().instance().has(&DataKey::Ledger) {
+            panic_with_error!(&env, DistributionError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Ledger, &ledger);
+        env.storage()
+            .instance()
+            .set(&DataKey::ProjectId, &project_id);
+        TokenUtils::new(&env)
+            .metadata()
+            .set_metadata(&TokenMetadata {
+                decimal: DECIMALS,
+                name,
+                symbol,
+            });
+    }
+
+    /// ProfitDistribution contract keeping the shares
+    pub fn ledger(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ledger)
+            .unwrap_or_else(|| panic_with_error!(&env, DistributionError::NotInitialized))
+    }
+
+    /// Project whose shares the token stands for
+    pub fn project_id(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ProjectId)
+            .unwrap_or_else(|| panic_with_error!(&env, DistributionError::NotInitialized))
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for ShareToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        let project_id = Self::project_id(env.clone());
+        share_ledger(&env)
+            .get_share_allowance(&project_id, &from, &spender)
+            .into()
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        let project_id = Self::project_id(env.clone());
+        share_ledger(&env).token_approve(
+            &project_id,
+            &from,
+            &spender,
+            &to_shares(&env, amount),
+            &expiration_ledger,
+        );
+        TokenUtils::new(&env)
+            .events()
+            .approve(from, spender, amount, expiration_ledger);
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        let project_id = Self::project_id(env.clone());
+        share_ledger(&env).share_balance(&project_id, &id).into()
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let project_id = Self::project_id(env.clone());
+        // Transfers of nothing succeed without touching the ledger
+        let shares = to_shares(&env, amount);
+        if shares > 0 {
+            share_ledger(&env).token_transfer(&project_id, &from, &to, &shares);
+        }
+        TokenUtils::new(&env).events().transfer(from, to, amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        let project_id = Self::project_id(env.clone());
+        let shares = to_shares(&env, amount);
+        if shares > 0 {
+            share_ledger(&env).token_transfer_from(&project_id, &spender, &from, &to, &shares);
+        }
+        TokenUtils::new(&env).events().transfer(from, to, amount);
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        let project_id = Self::project_id(env.clone());
+        share_ledger(&env).token_burn(&project_id, &from, &to_shares(&env, amount));
+        TokenUtils::new(&env).events().burn(from, amount);
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        let project_id = Self::project_id(env.clone());
+        share_ledger(&env).token_burn_from(&project_id, &spender, &from, &to_shares(&env, amount));
+        TokenUtils::new(&env).events().burn(from, amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        TokenUtils::new(&env).metadata().get_metadata().decimal
+    }
+
+    fn name(env: Env) -> String {
+        TokenUtils::new(&env).metadata().get_metadata().name
+    }
+
+    fn symbol(env: Env) -> String {
+        TokenUtils::new(&env).metadata().get_metadata().symbol
+    }
+}
+
+fn share_ledger(env: &Env) -> ShareLedgerClient<'_> {
+    ShareLedgerClient::new(env, &ShareToken::ledger(env.clone()))
+}
+
+/// Token amounts are basis points of the project's shares
+fn to_shares(env: &Env, amount: i128) -> u32 {
+    if amount < 0 {
+        panic_with_error!(env, DistributionError::InvalidAmount);
+    }
+    u32::try_from(amount)
+        .unwrap_or_else(|_| panic_with_error!(env, DistributionError::InsufficientBalance))
+}
@@ -0,0 +1,204 @@
+#![cfg(test)]
+
+use super::*;
+use profit_distribution::{ProfitDistribution, ProfitDistributionClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Map,
+};
+
+struct Setup<'a> {
+    env: Env,
+    distribution: ProfitDistributionClient<'a>,
+    shares: TokenClient<'a>,
+    profit_token: Address,
+    investor1: Address,
+    investor2: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let distribution =
+        ProfitDistributionClient::new(&env, &env.register_contract(None, ProfitDistribution));
+    distribution.initialize(&admin);
+    let profit_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    distribution.set_token(&1, &profit_token);
+
+    let investor1 = Address::generate(&env);
+    let investor2 = Address::generate(&env);
+    let mut investors = Map::new(&env);
+    investors.set(investor1.clone(), 6000);
+    investors.set(investor2.clone(), 4000);
+    distribution.register_investors(&1, &investors);
+
+    let token_id = env.register_contract(None, ShareToken);
+    ShareTokenClient::new(&env, &token_id).initialize(
+        &distribution.address,
+        &1,
+        &String::from_str(&env, "Project 1 Shares"),
+        &String::from_str(&env, "NF1"),
+    );
+    distribution.set_share_token(&1, &token_id);
+
+    Setup {
+        shares: TokenClient::new(&env, &token_id),
+        env,
+        distribution,
+        profit_token,
+        investor1,
+        investor2,
+    }
+}
+
+fn deposit(setup: &Setup, amount: i128) {
+    let provider = Address::generate(&setup.env);
+    StellarAssetClient::new(&setup.env, &setup.profit_token).mint(&provider, &amount);
+    setup.distribution.deposit_profits(&1, &provider, &amount);
+}
+
+#[test]
+fn test_balances_are_shares() {
+    let setup = setup();
+    assert_eq!(setup.shares.balance(&setup.investor1), 6000);
+    assert_eq!(setup.shares.balance(&setup.investor2), 4000);
+    assert_eq!(setup.shares.balance(&Address::generate(&setup.env)), 0);
+    assert_eq!(setup.shares.decimals(), 0);
+    assert_eq!(setup.shares.symbol(), String::from_str(&setup.env, "NF1"));
+}
+
+#[test]
+fn test_transfer_settles_dividends() {
+    let setup = setup();
+    let buyer = Address::generate(&setup.env);
+
+    deposit(&setup, 1000);
+    setup.shares.transfer(&setup.investor1, &buyer, &6000);
+    deposit(&setup, 1000);
+
+    assert_eq!(setup.shares.balance(&setup.investor1), 0);
+    assert_eq!(setup.shares.balance(&buyer), 6000);
+    assert_eq!(
+        setup.distribution.claim_dividends(&1, &setup.investor1),
+        600
+    );
+    assert_eq!(setup.distribution.claim_dividends(&1, &buyer), 600);
+    assert_eq!(
+        setup.distribution.claim_dividends(&1, &setup.investor2),
+        800
+    );
+}
+
+#[test]
+fn test_transfer_from_spends_allowance() {
+    let setup = setup();
+    let spender = Address::generate(&setup.env);
+    let expiration = setup.env.ledger().sequence() + 10;
+
+    setup
+        .shares
+        .approve(&setup.investor1, &spender, &1000, &expiration);
+    assert_eq!(setup.shares.allowance(&setup.investor1, &spender), 1000);
+    // Allowances are shared with the ledger's own
+    assert_eq!(
+        setup
+            .distribution
+            .get_share_allowance(&1, &setup.investor1, &spender),
+        1000
+    );
+
+    setup
+        .shares
+        .transfer_from(&spender, &setup.investor1, &setup.investor2, &400);
+    assert_eq!(setup.shares.allowance(&setup.investor1, &spender), 600);
+    assert_eq!(setup.shares.balance(&setup.investor2), 4400);
+    assert!(setup
+        .shares
+        .try_transfer_from(&spender, &setup.investor1, &setup.investor2, &601)
+        .is_err());
+}
+
+#[test]
+fn test_burn_leaves_profits_to_remaining_holders() {
+    let setup = setup();
+
+    deposit(&setup, 1000);
+    setup.shares.burn(&setup.investor2, &4000);
+    deposit(&setup, 1200);
+
+    assert_eq!(setup.shares.balance(&setup.investor2), 0);
+    assert_eq!(
+        setup.distribution.claim_dividends(&1, &setup.investor2),
+        400
+    );
+    assert_eq!(
+        setup.distribution.claim_dividends(&1, &setup.investor1),
+        600 + 1200
+    );
+}
+
+#[test]
+fn test_rejects_invalid_amounts() {
+    let setup = setup();
+    let other = Address::generate(&setup.env);
+    assert!(setup
+        .shares
+        .try_transfer(&setup.investor1, &other, &-1)
+        .is_err());
+    assert!(setup
+        .shares
+        .try_transfer(&setup.investor1, &other, &6001)
+        .is_err());
+    assert!(setup
+        .shares
+        .try_transfer(&setup.investor1, &other, &(u32::MAX as i128 + 1))
+        .is_err());
+}
+
+#[test]
+fn test_zero_transfers_succeed() {
+    let setup = setup();
+    let stranger = Address::generate(&setup.env);
+    let spender = Address::generate(&setup.env);
+
+    setup.shares.transfer(&stranger, &setup.investor1, &0);
+    setup
+        .shares
+        .transfer_from(&spender, &setup.investor1, &stranger, &0);
+
+    assert_eq!(setup.shares.balance(&setup.investor1), 6000);
+    assert_eq!(setup.shares.balance(&stranger), 0);
+    assert_eq!(setup.shares.allowance(&setup.investor1, &spender), 0);
+}
+
+#[test]
+fn test_hooks_only_accept_registered_token() {
+    let setup = setup();
+    let env = &setup.env;
+
+    // A token bound to another project can't be registered
+    let other_id = env.register_contract(None, ShareToken);
+    ShareTokenClient::new(env, &other_id).initialize(
+        &setup.distribution.address,
+        &2,
+        &String::from_str(env, "Project 2 Shares"),
+        &String::from_str(env, "NF2"),
+    );
+    assert_eq!(
+        setup.distribution.try_set_share_token(&1, &other_id),
+        Err(Ok(DistributionError::ShareTokenMismatch))
+    );
+
+    // Holders can't call the hooks themselves
+    env.set_auths(&[]);
+    assert!(setup
+        .distribution
+        .try_token_transfer(&1, &setup.investor1, &setup.investor2, &100)
+        .is_err());
+    assert_eq!(setup.shares.balance(&setup.investor1), 6000);
+}
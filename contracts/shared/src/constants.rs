@@ -1,5 +1,7 @@
-/// Platform constants
-use soroban_sdk::{symbol_short, Symbol};
+//! Platform constants
+//!
+//! Token amounts are written as `whole_units` followed by the 7 stroop decimals.
+#![allow(clippy::inconsistent_digit_grouping)]
 
 /// Default platform fee (2.5%)
 pub const DEFAULT_PLATFORM_FEE: u32 = 250;
@@ -38,11 +40,3 @@ pub const GOVERNANCE_QUORUM: u32 = 2000;
 
 /// Voting period duration (7 days in seconds)
 pub const VOTING_PERIOD: u64 = 604800;
-
-pub const ESCROW_INITIALIZED: Symbol = symbol_short!("esc_init");
-pub const FUNDS_LOCKED: Symbol = symbol_short!("f_lock");
-pub const MILESTONE_CREATED: Symbol = symbol_short!("mile_cr");
-pub const MILESTONE_SUBMITTED: Symbol = symbol_short!("mile_sub");
-pub const MILESTONE_APPROVED: Symbol = symbol_short!("mile_apr");
-pub const MILESTONE_REJECTED: Symbol = symbol_short!("mile_rej");
-pub const FUNDS_RELEASED: Symbol = symbol_short!("fund_rel");
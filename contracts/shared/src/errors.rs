@@ -1,5 +1,30 @@
+//! NovaFund error registry
+//!
+//! Every contract returns one of the enums below. Codes are unique across all
+//! of them, so a contract error code decodes to exactly one `NovaFundError`
+//! regardless of which contract raised it. New codes go in the range of their
+//! domain:
+//!
+//! | Range     | Domain                                             |
+//! |-----------|----------------------------------------------------|
+//! | 1-99      | General (`Error`)                                  |
+//! | 100-199   | Project launch (`Error`)                           |
+//! | 200-299   | Escrow (`Error`)                                   |
+//! | 300-309   | Distribution (`Error`)                             |
+//! | 310-399   | Profit distribution contract (`DistributionError`) |
+//! | 400-409   | Subscription (`Error`)                             |
+//! | 410-499   | Subscription pool contract (`SubscriptionError`)   |
+//! | 500-599   | Reputation (`Error`)                               |
+//! | 600-699   | Governance (`Error`)                               |
+//! | 700-799   | Cross-chain bridge (`Error`)                       |
+//! | 800-899   | Multi-party payment contract (`PaymentError`)      |
+//! | 1000-1099 | Project launch validation (`Error`)                |
+//!
+//! A contract error enum is limited to 50 cases, which is why the registry is
+//! split across several enums.
 use soroban_sdk::contracterror;
 
+/// Errors shared by the platform contracts
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -24,14 +49,20 @@ pub enum Error {
     InvalidMilestoneStatus = 202,
     NotAValidator = 203,
     AlreadyVoted = 204,
+    NotABacker = 205,
+    VotingPeriodEnded = 206,
+    VotingPeriodActive = 207,
+    InvalidVotingMode = 208,
+    InsufficientBond = 209,
+    UnbondingPeriodActive = 210,
+    HoldbackActive = 211,
 
-    // Distribution errors (300-399)
+    // Distribution errors (300-309)
     InsufficientFunds = 300,
     InvalidDistribution = 301,
     NoClaimableAmount = 302,
-    DistributionFailed = 303,
 
-    // Subscription errors (400-499)
+    // Subscription errors (400-409)
     SubscriptionNotActive = 400,
     InvalidSubscriptionPeriod = 401,
     SubscriptionExists = 402,
@@ -65,3 +96,157 @@ pub enum Error {
     ProjectNotFound = 1002,
     ContributionTooLow = 1003,
 }
+
+/// Errors of the profit distribution contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DistributionError {
+    AlreadyInitialized = 310,
+    NotInitialized = 311,
+    InvalidSharePercentage = 312,
+    TotalSharesNot100 = 313,
+    InsufficientBalance = 314,
+    NothingToClaim = 315,
+    AlreadyClaimed = 316,
+    Unauthorized = 317,
+    InvalidAmount = 318,
+    ProjectNotCompleted = 319,
+    ContributionMismatch = 320,
+    InsufficientAllowance = 321,
+    ShareTokenMismatch = 322,
+    UnknownRewardToken = 323,
+    TooManyRewardTokens = 324,
+    TooManyVestingStreams = 325,
+    CompoundingNotSet = 326,
+    CompoundTargetMismatch = 327,
+    RoundNotFound = 328,
+    RoundExpired = 329,
+    RoundNotExpired = 330,
+    TooManyOpenRounds = 331,
+    WaterfallActive = 332,
+}
+
+/// Errors of the subscription pool contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SubscriptionError {
+    NotInitialized = 410,
+    AlreadyInitialized = 411,
+    InvalidAmount = 412,
+    InsufficientBalance = 413,
+    PoolNotFound = 414,
+    SubscriptionNotFound = 415,
+    SubscriptionNotActive = 416,
+    SubscriptionAlreadyCancelled = 417,
+    MaxFailuresReached = 418,
+    InvalidStatus = 419,
+    Unauthorized = 420,
+    PaymentNotDue = 421,
+}
+
+/// Errors of the multi-party payment contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PaymentError {
+    InvalidInput = 800,
+    InvalidShareTotal = 801,
+    PartyIndexOutOfBounds = 802,
+    PartiesNotSetup = 803,
+    PartyMismatch = 804,
+    NoClaimableAmount = 805,
+}
+
+/// Any error raised by a NovaFund contract
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NovaFundError {
+    Platform(Error),
+    Distribution(DistributionError),
+    Subscription(SubscriptionError),
+    Payment(PaymentError),
+}
+
+impl NovaFundError {
+    /// Decode a contract error code raised by any NovaFund contract
+    pub fn from_code(code: u32) -> Option<Self> {
+        let error = soroban_sdk::Error::from_contract_error(code);
+        if let Ok(error) = Error::try_from(error) {
+            Some(Self::Platform(error))
+        } else if let Ok(error) = DistributionError::try_from(error) {
+            Some(Self::Distribution(error))
+        } else if let Ok(error) = SubscriptionError::try_from(error) {
+            Some(Self::Subscription(error))
+        } else if let Ok(error) = PaymentError::try_from(error) {
+            Some(Self::Payment(error))
+        } else {
+            None
+        }
+    }
+
+    /// Contract error code of the error
+    pub fn code(&self) -> u32 {
+        match *self {
+            Self::Platform(error) => error as u32,
+            Self::Distribution(error) => error as u32,
+            Self::Subscription(error) => error as u32,
+            Self::Payment(error) => error as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of registry enums that claim a contract error code
+    fn claims(code: u32) -> usize {
+        let error = soroban_sdk::Error::from_contract_error(code);
+        [
+            Error::try_from(error).is_ok(),
+            DistributionError::try_from(error).is_ok(),
+            SubscriptionError::try_from(error).is_ok(),
+            PaymentError::try_from(error).is_ok(),
+        ]
+        .iter()
+        .filter(|claimed| **claimed)
+        .count()
+    }
+
+    #[test]
+    fn test_error_codes_do_not_collide() {
+        let mut registered = 0;
+        for code in 0..2000 {
+            let count = claims(code);
+            assert!(count <= 1, "error code {} is claimed {} times", code, count);
+            registered += count;
+        }
+        // 50 platform, 23 distribution, 12 subscription and 6 payment errors
+        assert_eq!(registered, 91);
+    }
+
+    #[test]
+    fn test_decode_error_code() {
+        assert_eq!(
+            NovaFundError::from_code(202),
+            Some(NovaFundError::Platform(Error::InvalidMilestoneStatus))
+        );
+        assert_eq!(
+            NovaFundError::from_code(315),
+            Some(NovaFundError::Distribution(
+                DistributionError::NothingToClaim
+            ))
+        );
+        assert_eq!(
+            NovaFundError::from_code(414),
+            Some(NovaFundError::Subscription(SubscriptionError::PoolNotFound))
+        );
+        assert_eq!(
+            NovaFundError::from_code(805),
+            Some(NovaFundError::Payment(PaymentError::NoClaimableAmount))
+        );
+        assert_eq!(NovaFundError::from_code(999), None);
+        assert_eq!(NovaFundError::from_code(421).map(|e| e.code()), Some(421));
+    }
+}
@@ -1,9 +1,39 @@
-use soroban_sdk::{symbol_short, Symbol};
+//! NovaFund event schema
+//!
+//! Every event is a `#[contracttype]` struct published through [`publish`],
+//! with three topics: the event name, [`EVENT_SCHEMA_VERSION`] and the id of
+//! the entity the event is about (project, pool, proposal, bridge tx, ...), so
+//! subscribers can filter on a single project without decoding payloads.
+//! Names are unique across all contracts (see [`ALL_EVENTS`]), so an indexer
+//! can decode any NovaFund event from its first topic alone.
+use soroban_sdk::{
+    contracttype, symbol_short, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
+};
+
+use crate::types::ChainId;
+
+/// Version of the event schema, bumped whenever an event's payload changes
+pub const EVENT_SCHEMA_VERSION: u32 = 3;
+
+/// A typed NovaFund event
+pub trait NovaFundEvent: IntoVal<Env, Val> {
+    /// Event name, published as the first topic
+    const NAME: Symbol;
+
+    /// Entity the event is about, published as the third topic
+    type Id: IntoVal<Env, Val>;
+
+    fn id(&self) -> Self::Id;
+}
+
+/// Publish a NovaFund event under the current schema version
+pub fn publish<E: NovaFundEvent>(env: &Env, event: E) {
+    env.events()
+        .publish((E::NAME, EVENT_SCHEMA_VERSION, event.id()), event);
+}
 
 // Project events
 pub const PROJECT_CREATED: Symbol = symbol_short!("proj_new");
-pub const PROJECT_FUNDED: Symbol = symbol_short!("proj_fund");
-pub const PROJECT_COMPLETED: Symbol = symbol_short!("proj_done");
 pub const PROJECT_FAILED: Symbol = symbol_short!("proj_fail");
 
 // Contribution events
@@ -14,16 +44,41 @@ pub const REFUND_ISSUED: Symbol = symbol_short!("refund");
 pub const ESCROW_INITIALIZED: Symbol = symbol_short!("esc_init");
 pub const FUNDS_LOCKED: Symbol = symbol_short!("lock");
 pub const FUNDS_RELEASED: Symbol = symbol_short!("release");
+pub const TOKEN_FUNDS_LOCKED: Symbol = symbol_short!("t_lock");
+pub const TOKEN_FUNDS_RELEASED: Symbol = symbol_short!("t_release");
 pub const MILESTONE_CREATED: Symbol = symbol_short!("m_create");
 pub const MILESTONE_SUBMITTED: Symbol = symbol_short!("m_submit");
 pub const MILESTONE_APPROVED: Symbol = symbol_short!("m_apprv");
 pub const MILESTONE_REJECTED: Symbol = symbol_short!("m_reject");
-pub const MILESTONE_COMPLETED: Symbol = symbol_short!("milestone");
 pub const VALIDATORS_UPDATED: Symbol = symbol_short!("v_update");
+pub const VALIDATOR_CHANGE_PROPOSED: Symbol = symbol_short!("v_propose");
+pub const VALIDATOR_CHANGE_APPROVED: Symbol = symbol_short!("v_approve");
+pub const BACKER_VOTE_CAST: Symbol = symbol_short!("bk_vote");
+pub const VALIDATOR_BONDED: Symbol = symbol_short!("v_bond");
+pub const VALIDATOR_UNBONDED: Symbol = symbol_short!("v_unbond");
+pub const VALIDATOR_WITHDRAWN: Symbol = symbol_short!("v_withdrw");
+pub const VALIDATOR_SLASHED: Symbol = symbol_short!("v_slash");
+pub const VALIDATOR_FEES_CLAIMED: Symbol = symbol_short!("v_fees");
+pub const VALIDATOR_FEES_FORFEITED: Symbol = symbol_short!("v_forfeit");
+pub const DISPUTE_OPENED: Symbol = symbol_short!("disp_open");
+pub const DISPUTE_VOTE_CAST: Symbol = symbol_short!("disp_vote");
+pub const DISPUTE_RESOLVED: Symbol = symbol_short!("disp_res");
+pub const YIELD_INVESTED: Symbol = symbol_short!("y_invest");
+pub const YIELD_DIVESTED: Symbol = symbol_short!("y_divest");
+pub const YIELD_HARVESTED: Symbol = symbol_short!("y_harvest");
 
 // Distribution events
 pub const PROFIT_DISTRIBUTED: Symbol = symbol_short!("profit");
 pub const DIVIDEND_CLAIMED: Symbol = symbol_short!("claim");
+pub const SHARES_TRANSFERRED: Symbol = symbol_short!("sh_xfer");
+pub const SHARES_APPROVED: Symbol = symbol_short!("sh_apprv");
+pub const SHARES_BURNED: Symbol = symbol_short!("sh_burn");
+pub const DUST_SWEPT: Symbol = symbol_short!("dust_swpt");
+pub const DIVIDENDS_COMPOUNDED: Symbol = symbol_short!("div_cmpd");
+pub const ROUND_OPENED: Symbol = symbol_short!("round_opn");
+pub const ROUND_CLAIMED: Symbol = symbol_short!("round_clm");
+pub const ROUND_CLOSED: Symbol = symbol_short!("round_cls");
+pub const WATERFALL_PAID: Symbol = symbol_short!("wfall_pay");
 
 // Governance events
 pub const PROPOSAL_CREATED: Symbol = symbol_short!("proposal");
@@ -48,13 +103,14 @@ pub const SUBSCRIPTION_PAUSED: Symbol = symbol_short!("sub_pause");
 pub const SUBSCRIPTION_RESUMED: Symbol = symbol_short!("sub_resum");
 pub const PAYMENT_FAILED: Symbol = symbol_short!("pay_fail");
 pub const SUBSCRIPTION_PAYMENT: Symbol = symbol_short!("deposit");
+pub const POOL_CREATED: Symbol = symbol_short!("pool_cre");
+pub const POOL_WITHDRAWN: Symbol = symbol_short!("pool_wdr");
 
 // Cross-chain bridge events
 pub const BRIDGE_INITIALIZED: Symbol = symbol_short!("br_init");
 pub const SUPPORTED_CHAIN_ADDED: Symbol = symbol_short!("chain_add");
 pub const SUPPORTED_CHAIN_REMOVED: Symbol = symbol_short!("chain_rem");
 pub const ASSET_WRAPPED: Symbol = symbol_short!("wrap");
-pub const ASSET_UNWRAPPED: Symbol = symbol_short!("unwrap");
 pub const BRIDGE_DEPOSIT: Symbol = symbol_short!("br_dep");
 pub const BRIDGE_WITHDRAW: Symbol = symbol_short!("br_wdraw");
 pub const BRIDGE_PAUSED: Symbol = symbol_short!("br_pause");
@@ -62,4 +118,750 @@ pub const BRIDGE_UNPAUSED: Symbol = symbol_short!("br_res");
 pub const RELAYER_ADDED: Symbol = symbol_short!("rel_add");
 pub const RELAYER_REMOVED: Symbol = symbol_short!("rel_rem");
 pub const BRIDGE_TX_CONFIRMED: Symbol = symbol_short!("tx_conf");
-pub const BRIDGE_TX_FAILED: Symbol = symbol_short!("tx_fail");
+
+/// Every NovaFund event name, in schema order
+pub const ALL_EVENTS: [Symbol; 69] = [
+    PROJECT_CREATED,
+    PROJECT_FAILED,
+    CONTRIBUTION_MADE,
+    REFUND_ISSUED,
+    ESCROW_INITIALIZED,
+    FUNDS_LOCKED,
+    FUNDS_RELEASED,
+    TOKEN_FUNDS_LOCKED,
+    TOKEN_FUNDS_RELEASED,
+    MILESTONE_CREATED,
+    MILESTONE_SUBMITTED,
+    MILESTONE_APPROVED,
+    MILESTONE_REJECTED,
+    VALIDATORS_UPDATED,
+    VALIDATOR_CHANGE_PROPOSED,
+    VALIDATOR_CHANGE_APPROVED,
+    BACKER_VOTE_CAST,
+    VALIDATOR_BONDED,
+    VALIDATOR_UNBONDED,
+    VALIDATOR_WITHDRAWN,
+    VALIDATOR_SLASHED,
+    VALIDATOR_FEES_CLAIMED,
+    VALIDATOR_FEES_FORFEITED,
+    DISPUTE_OPENED,
+    DISPUTE_VOTE_CAST,
+    DISPUTE_RESOLVED,
+    YIELD_INVESTED,
+    YIELD_DIVESTED,
+    YIELD_HARVESTED,
+    PROFIT_DISTRIBUTED,
+    DIVIDEND_CLAIMED,
+    SHARES_TRANSFERRED,
+    SHARES_APPROVED,
+    SHARES_BURNED,
+    DUST_SWEPT,
+    DIVIDENDS_COMPOUNDED,
+    ROUND_OPENED,
+    ROUND_CLAIMED,
+    ROUND_CLOSED,
+    WATERFALL_PAID,
+    PROPOSAL_CREATED,
+    VOTE_CAST,
+    PROPOSAL_EXECUTED,
+    USER_REGISTERED,
+    REPUTATION_UPDATED,
+    BADGE_EARNED,
+    PAYMENT_SETUP,
+    PAYMENT_RECEIVED,
+    PAYMENT_WITHDRAWN,
+    SUBSCRIPTION_CREATED,
+    SUBSCRIPTION_CANCELLED,
+    SUBSCRIPTION_MODIFIED,
+    SUBSCRIPTION_PAUSED,
+    SUBSCRIPTION_RESUMED,
+    PAYMENT_FAILED,
+    SUBSCRIPTION_PAYMENT,
+    POOL_CREATED,
+    POOL_WITHDRAWN,
+    BRIDGE_INITIALIZED,
+    SUPPORTED_CHAIN_ADDED,
+    SUPPORTED_CHAIN_REMOVED,
+    ASSET_WRAPPED,
+    BRIDGE_DEPOSIT,
+    BRIDGE_WITHDRAW,
+    BRIDGE_PAUSED,
+    BRIDGE_UNPAUSED,
+    RELAYER_ADDED,
+    RELAYER_REMOVED,
+    BRIDGE_TX_CONFIRMED,
+];
+
+macro_rules! event {
+    ($event:ident, $name:ident, $id:ident: $ty:ty) => {
+        impl NovaFundEvent for $event {
+            const NAME: Symbol = $name;
+            type Id = $ty;
+
+            fn id(&self) -> $ty {
+                self.$id.clone()
+            }
+        }
+    };
+    ($event:ident, $name:ident) => {
+        impl NovaFundEvent for $event {
+            const NAME: Symbol = $name;
+            type Id = ();
+
+            fn id(&self) {}
+        }
+    };
+}
+
+// Project events
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectCreated {
+    pub project_id: u64,
+    pub creator: Address,
+    pub funding_goal: i128,
+    pub deadline: u64,
+    pub token: Address,
+}
+event!(ProjectCreated, PROJECT_CREATED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectFailed {
+    pub project_id: u64,
+}
+event!(ProjectFailed, PROJECT_FAILED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributionMade {
+    pub project_id: u64,
+    pub contributor: Address,
+    pub amount: i128,
+    pub total_raised: i128,
+}
+event!(ContributionMade, CONTRIBUTION_MADE, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundIssued {
+    pub project_id: u64,
+    pub contributor: Address,
+    pub amount: i128,
+}
+event!(RefundIssued, REFUND_ISSUED, project_id: u64);
+
+// Escrow events
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowInitialized {
+    pub project_id: u64,
+    pub creator: Address,
+    pub token: Address,
+}
+event!(EscrowInitialized, ESCROW_INITIALIZED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundsLocked {
+    pub project_id: u64,
+    pub amount: i128,
+}
+event!(FundsLocked, FUNDS_LOCKED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundsReleased {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub amount: i128,
+}
+event!(FundsReleased, FUNDS_RELEASED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenFundsLocked {
+    pub project_id: u64,
+    pub token: Address,
+    pub amount: i128,
+}
+event!(TokenFundsLocked, TOKEN_FUNDS_LOCKED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenFundsReleased {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub token: Address,
+    pub amount: i128,
+}
+event!(TokenFundsReleased, TOKEN_FUNDS_RELEASED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneCreated {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub amount: i128,
+    pub description_hash: BytesN<32>,
+}
+event!(MilestoneCreated, MILESTONE_CREATED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneSubmitted {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub proof_hash: BytesN<32>,
+}
+event!(MilestoneSubmitted, MILESTONE_SUBMITTED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneApproved {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub approval_count: u32,
+}
+event!(MilestoneApproved, MILESTONE_APPROVED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneRejected {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub rejection_count: u32,
+}
+event!(MilestoneRejected, MILESTONE_REJECTED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidatorsUpdated {
+    pub project_id: u64,
+    pub validators: Vec<Address>,
+}
+event!(ValidatorsUpdated, VALIDATORS_UPDATED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidatorChangeProposed {
+    pub project_id: u64,
+    pub validators: Vec<Address>,
+}
+event!(ValidatorChangeProposed, VALIDATOR_CHANGE_PROPOSED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidatorChangeApproved {
+    pub project_id: u64,
+    pub approver: Address,
+}
+event!(ValidatorChangeApproved, VALIDATOR_CHANGE_APPROVED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BackerVoteCast {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub voter: Address,
+    pub approve: bool,
+    pub weight: i128,
+}
+event!(BackerVoteCast, BACKER_VOTE_CAST, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidatorBonded {
+    pub validator: Address,
+    pub amount: i128,
+    pub bonded: i128,
+}
+event!(ValidatorBonded, VALIDATOR_BONDED, validator: Address);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidatorUnbonded {
+    pub validator: Address,
+    pub amount: i128,
+    pub unbonding_ends_at: u64,
+}
+event!(ValidatorUnbonded, VALIDATOR_UNBONDED, validator: Address);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidatorWithdrawn {
+    pub validator: Address,
+    pub amount: i128,
+}
+event!(ValidatorWithdrawn, VALIDATOR_WITHDRAWN, validator: Address);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidatorSlashed {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub validator: Address,
+    pub amount: i128,
+}
+event!(ValidatorSlashed, VALIDATOR_SLASHED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidatorFeesClaimed {
+    pub validator: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+event!(ValidatorFeesClaimed, VALIDATOR_FEES_CLAIMED, validator: Address);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidatorFeesForfeited {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub validator: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+event!(ValidatorFeesForfeited, VALIDATOR_FEES_FORFEITED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeOpened {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub challenger: Address,
+}
+event!(DisputeOpened, DISPUTE_OPENED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeVoteCast {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub arbitrator: Address,
+    pub uphold: bool,
+}
+event!(DisputeVoteCast, DISPUTE_VOTE_CAST, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResolved {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub upheld: bool,
+}
+event!(DisputeResolved, DISPUTE_RESOLVED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldInvested {
+    pub project_id: u64,
+    pub vault: Address,
+    pub amount: i128,
+}
+event!(YieldInvested, YIELD_INVESTED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldDivested {
+    pub project_id: u64,
+    pub vault: Address,
+    pub amount: i128,
+}
+event!(YieldDivested, YIELD_DIVESTED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldHarvested {
+    pub project_id: u64,
+    pub backer_amount: i128,
+    pub creator_amount: i128,
+}
+event!(YieldHarvested, YIELD_HARVESTED, project_id: u64);
+
+// Distribution events
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProfitDistributed {
+    pub project_id: u64,
+    pub token: Address,
+    pub amount: i128,
+}
+event!(ProfitDistributed, PROFIT_DISTRIBUTED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DividendClaimed {
+    pub project_id: u64,
+    pub investor: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+event!(DividendClaimed, DIVIDEND_CLAIMED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharesTransferred {
+    pub project_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub shares: u32,
+}
+event!(SharesTransferred, SHARES_TRANSFERRED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharesApproved {
+    pub project_id: u64,
+    pub owner: Address,
+    pub spender: Address,
+    pub shares: u32,
+    pub expiration_ledger: u32,
+}
+event!(SharesApproved, SHARES_APPROVED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharesBurned {
+    pub project_id: u64,
+    pub holder: Address,
+    pub shares: u32,
+}
+event!(SharesBurned, SHARES_BURNED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DustSwept {
+    pub project_id: u64,
+    pub token: Address,
+    pub treasury: Address,
+    pub amount: i128,
+}
+event!(DustSwept, DUST_SWEPT, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DividendsCompounded {
+    pub project_id: u64,
+    pub investor: Address,
+    pub token: Address,
+    pub target: Address,
+    pub target_id: u64,
+    pub amount: i128,
+    pub keeper_fee: i128,
+}
+event!(DividendsCompounded, DIVIDENDS_COMPOUNDED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundOpened {
+    pub project_id: u64,
+    pub round_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub expires_at: u64,
+}
+event!(RoundOpened, ROUND_OPENED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundClaimed {
+    pub project_id: u64,
+    pub round_id: u64,
+    pub holder: Address,
+    pub amount: i128,
+}
+event!(RoundClaimed, ROUND_CLAIMED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundClosed {
+    pub project_id: u64,
+    pub round_id: u64,
+    pub unclaimed: i128,
+}
+event!(RoundClosed, ROUND_CLOSED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaterfallPaid {
+    pub project_id: u64,
+    pub investor: Address,
+    pub capital_returned: i128,
+    pub preferred_return: i128,
+    pub profit: i128,
+    pub carry: i128,
+}
+event!(WaterfallPaid, WATERFALL_PAID, project_id: u64);
+
+// Governance events
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub creator: Address,
+    pub payload_ref: Bytes,
+}
+event!(ProposalCreated, PROPOSAL_CREATED, proposal_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Address,
+    pub support: bool,
+}
+event!(VoteCast, VOTE_CAST, proposal_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub executed: bool,
+}
+event!(ProposalExecuted, PROPOSAL_EXECUTED, proposal_id: u64);
+
+// Reputation events
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserRegistered {
+    pub user: Address,
+}
+event!(UserRegistered, USER_REGISTERED, user: Address);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationUpdated {
+    pub user: Address,
+    pub score: i128,
+}
+event!(ReputationUpdated, REPUTATION_UPDATED, user: Address);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeEarned {
+    pub user: Address,
+    pub badge: u32,
+}
+event!(BadgeEarned, BADGE_EARNED, user: Address);
+
+// Multi-party payment events
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentSetup {
+    pub project_id: u64,
+    pub party_count: u32,
+}
+event!(PaymentSetup, PAYMENT_SETUP, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentReceived {
+    pub project_id: u64,
+    pub amount: i128,
+}
+event!(PaymentReceived, PAYMENT_RECEIVED, project_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentWithdrawn {
+    pub project_id: u64,
+    pub party_index: u32,
+    pub party: Address,
+    pub amount: i128,
+}
+event!(PaymentWithdrawn, PAYMENT_WITHDRAWN, project_id: u64);
+
+// Subscription events
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolCreated {
+    pub pool_id: u64,
+    pub name: String,
+}
+event!(PoolCreated, POOL_CREATED, pool_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionCreated {
+    pub pool_id: u64,
+    pub subscriber: Address,
+}
+event!(SubscriptionCreated, SUBSCRIPTION_CREATED, pool_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionCancelled {
+    pub pool_id: u64,
+    pub subscriber: Address,
+}
+event!(SubscriptionCancelled, SUBSCRIPTION_CANCELLED, pool_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionModified {
+    pub pool_id: u64,
+    pub subscriber: Address,
+    pub amount: i128,
+    pub period: u32,
+}
+event!(SubscriptionModified, SUBSCRIPTION_MODIFIED, pool_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionPaused {
+    pub pool_id: u64,
+    pub subscriber: Address,
+}
+event!(SubscriptionPaused, SUBSCRIPTION_PAUSED, pool_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionResumed {
+    pub pool_id: u64,
+    pub subscriber: Address,
+}
+event!(SubscriptionResumed, SUBSCRIPTION_RESUMED, pool_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentFailed {
+    pub pool_id: u64,
+    pub subscriber: Address,
+    pub failure_count: u32,
+}
+event!(PaymentFailed, PAYMENT_FAILED, pool_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionPayment {
+    pub pool_id: u64,
+    pub subscriber: Address,
+    pub amount: i128,
+}
+event!(SubscriptionPayment, SUBSCRIPTION_PAYMENT, pool_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolWithdrawn {
+    pub pool_id: u64,
+    pub subscriber: Address,
+    pub amount: i128,
+}
+event!(PoolWithdrawn, POOL_WITHDRAWN, pool_id: u64);
+
+// Cross-chain bridge events
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeInitialized {
+    pub admin: Address,
+}
+event!(BridgeInitialized, BRIDGE_INITIALIZED);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SupportedChainAdded {
+    pub chain_id: ChainId,
+    pub bridge_contract: BytesN<32>,
+}
+event!(SupportedChainAdded, SUPPORTED_CHAIN_ADDED, chain_id: ChainId);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SupportedChainRemoved {
+    pub chain_id: ChainId,
+}
+event!(SupportedChainRemoved, SUPPORTED_CHAIN_REMOVED, chain_id: ChainId);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetWrapped {
+    pub asset_code: String,
+    pub issuer: Address,
+    pub original_chain: ChainId,
+}
+event!(AssetWrapped, ASSET_WRAPPED, original_chain: ChainId);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeDeposit {
+    pub tx_id: u64,
+    pub recipient: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub source_tx_hash: BytesN<32>,
+}
+event!(BridgeDeposit, BRIDGE_DEPOSIT, tx_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeWithdraw {
+    pub tx_id: u64,
+    pub destination_chain: ChainId,
+    pub recipient: BytesN<32>,
+    pub asset: Address,
+    pub amount: i128,
+}
+event!(BridgeWithdraw, BRIDGE_WITHDRAW, tx_id: u64);
+
+/// `external_tx_hash` is the transaction on the other chain that settled the
+/// bridge tx: the source tx for deposits, the destination tx for withdrawals
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeTxConfirmed {
+    pub tx_id: u64,
+    pub external_tx_hash: BytesN<32>,
+}
+event!(BridgeTxConfirmed, BRIDGE_TX_CONFIRMED, tx_id: u64);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgePaused {
+    pub admin: Address,
+}
+event!(BridgePaused, BRIDGE_PAUSED);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeUnpaused {
+    pub admin: Address,
+}
+event!(BridgeUnpaused, BRIDGE_UNPAUSED);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerAdded {
+    pub relayer: Address,
+    pub stake: i128,
+}
+event!(RelayerAdded, RELAYER_ADDED, relayer: Address);
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerRemoved {
+    pub relayer: Address,
+}
+event!(RelayerRemoved, RELAYER_REMOVED, relayer: Address);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_names_are_unique() {
+        for (i, name) in ALL_EVENTS.iter().enumerate() {
+            for other in ALL_EVENTS.iter().skip(i + 1) {
+                assert_ne!(name, other);
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Client interfaces for cross-contract calls between NovaFund contracts
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::{
+    errors::{DistributionError, Error, SubscriptionError},
+    types::Project,
+};
+
+/// Read-only view of the ProjectLaunch contract used by other contracts
+#[contractclient(name = "ProjectLaunchClient")]
+pub trait ProjectLaunchInterface {
+    /// Get project details
+    fn get_project(env: Env, project_id: u64) -> Result<Project, Error>;
+
+    /// Get individual contribution amount for a user
+    fn get_user_contribution(env: Env, project_id: u64, contributor: Address) -> i128;
+}
+
+/// ProjectLaunch entry point for contributing on someone else's behalf
+#[contractclient(name = "ProjectFundingClient")]
+pub trait ProjectFundingInterface {
+    /// Take `amount` from `payer` as `contributor`'s contribution
+    fn contribute_for(
+        env: Env,
+        project_id: u64,
+        payer: Address,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<(), Error>;
+}
+
+/// SubscriptionPool entry points for paying into a pool on someone else's
+/// behalf
+#[contractclient(name = "PoolFundingClient")]
+pub trait PoolFundingInterface {
+    /// Token the pool holds
+    fn get_pool_token(env: Env, pool_id: u64) -> Result<Address, SubscriptionError>;
+
+    /// Take `amount` from `payer` into the pool for `subscriber`
+    fn deposit_for(
+        env: Env,
+        pool_id: u64,
+        payer: Address,
+        subscriber: Address,
+        amount: i128,
+    ) -> Result<(), SubscriptionError>;
+}
+
+/// Yield-bearing vault the escrow can park idle funds in
+///
+/// Vaults hold a single asset and issue shares against deposits; the value of
+/// a share grows as the vault earns yield.
+#[contractclient(name = "VaultClient")]
+pub trait VaultInterface {
+    /// Token the vault accepts
+    fn asset(env: Env) -> Address;
+
+    /// Move `amount` of the asset from `from` into the vault, returning the shares minted
+    fn deposit(env: Env, from: Address, amount: i128) -> i128;
+
+    /// Pay `amount` of the asset to `owner`, returning the shares burned
+    fn withdraw(env: Env, owner: Address, amount: i128) -> i128;
+
+    /// Current asset value of `shares`
+    fn preview_redeem(env: Env, shares: i128) -> i128;
+}
+
+/// Share ledger kept by the ProfitDistribution contract, as used by a
+/// project's share token
+///
+/// Shares are in basis points. The `token_*` hooks only accept calls from the
+/// share token registered for the project, which authorizes the holders.
+#[contractclient(name = "ShareLedgerClient")]
+pub trait ShareLedgerInterface {
+    /// Shares `holder` owns in a project
+    fn share_balance(env: Env, project_id: u64, holder: Address) -> u32;
+
+    /// Shares `spender` may still move on `owner`'s behalf
+    fn get_share_allowance(env: Env, project_id: u64, owner: Address, spender: Address) -> u32;
+
+    /// Settle both holders' dividends and move shares between them
+    fn token_transfer(
+        env: Env,
+        project_id: u64,
+        from: Address,
+        to: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError>;
+
+    /// Move shares on `from`'s behalf, spending `spender`'s allowance
+    fn token_transfer_from(
+        env: Env,
+        project_id: u64,
+        spender: Address,
+        from: Address,
+        to: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError>;
+
+    /// Replace `spender`'s allowance over `owner`'s shares
+    fn token_approve(
+        env: Env,
+        project_id: u64,
+        owner: Address,
+        spender: Address,
+        shares: u32,
+        expiration_ledger: u32,
+    ) -> Result<(), DistributionError>;
+
+    /// Settle `from`'s dividends and give up shares
+    fn token_burn(
+        env: Env,
+        project_id: u64,
+        from: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError>;
+
+    /// Give up shares on `from`'s behalf, spending `spender`'s allowance
+    fn token_burn_from(
+        env: Env,
+        project_id: u64,
+        spender: Address,
+        from: Address,
+        shares: u32,
+    ) -> Result<(), DistributionError>;
+}
+
+/// Token standing for one project's profit shares
+#[contractclient(name = "ShareTokenClient")]
+pub trait ShareTokenInterface {
+    /// Share ledger the token reads balances from
+    fn ledger(env: Env) -> Address;
+
+    /// Project whose shares the token stands for
+    fn project_id(env: Env) -> u64;
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, String, Vec};
 
 /// Common timestamp type
 pub type Timestamp = u64;
@@ -42,8 +42,51 @@ pub struct UserProfile {
     pub verified: bool,
 }
 
+/// Project status enumeration
 #[contracttype]
-#[derive(Clone)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProjectStatus {
+    Active = 0,
+    Completed = 1,
+    Failed = 2,
+    Cancelled = 3,
+}
+
+/// Project structure
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Project {
+    pub creator: Address,
+    pub funding_goal: i128,
+    pub deadline: u64,
+    pub token: Address,
+    pub status: ProjectStatus,
+    pub metadata_hash: Bytes,
+    pub total_raised: i128,
+    pub created_at: u64,
+}
+
+/// Who decides the outcome of an escrow's milestones
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VotingMode {
+    Validators = 0, // Fixed validator set, one address one vote
+    Backers = 1,    // Project contributors, weighted by amount contributed
+}
+
+/// Backer voting parameters for an escrow
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BackerVotingConfig {
+    pub quorum: BasisPoints,    // Share of total raised that must take part
+    pub threshold: BasisPoints, // Share of cast weight that must approve
+    pub voting_period: u64,     // Seconds a submitted milestone stays open for votes
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
 pub struct EscrowInfo {
     pub project_id: u64,
     pub creator: Address,
@@ -51,6 +94,35 @@ pub struct EscrowInfo {
     pub total_deposited: Amount,
     pub released_amount: Amount,
     pub validators: Vec<Address>,
+    pub voting_mode: VotingMode,
+    pub tokens: Vec<Address>, // Other tokens deposited besides `token`
+}
+
+/// An amount of a given token
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TokenAmount {
+    pub token: Address,
+    pub amount: Amount,
+}
+
+/// Deposits and releases of one escrow token
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TokenBalance {
+    pub token: Address,
+    pub total_deposited: Amount,
+    pub released_amount: Amount,
+}
+
+/// Portion of a milestone denominated in one of the escrow's other tokens
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BasketAmount {
+    pub token: Address,
+    pub amount: Amount,
+    pub approved_amount: Amount,
+    pub released_amount: Amount,
 }
 
 #[contracttype]
@@ -60,6 +132,7 @@ pub enum MilestoneStatus {
     Submitted = 1, // Submitted with proof, awaiting validator votes
     Approved = 2,  // Approved by majority, funds released
     Rejected = 3,  // Rejected by majority
+    Disputed = 4,  // Decision contested, awaiting dispute resolution
 }
 
 /// Milestone structure
@@ -69,14 +142,187 @@ pub struct Milestone {
     pub id: u64,
     pub project_id: u64,
     pub description_hash: Hash,
-    pub amount: Amount,
+    pub amount: Amount, // Amount in the escrow's primary token (may be 0 for a basket)
+    pub basket: Vec<BasketAmount>, // Amounts in the escrow's other tokens
     pub status: MilestoneStatus,
     pub proof_hash: Hash,
     pub approval_count: u32,
     pub rejection_count: u32,
+    pub approval_weight: Amount, // Contribution-weighted approvals (backer voting)
+    pub rejection_weight: Amount, // Contribution-weighted rejections (backer voting)
+    pub voting_ends_at: Timestamp, // End of the backer voting window (0 for validator voting)
+    pub approved_amount: Amount, // Amount approved for release (may be less than `amount`)
+    pub released_amount: Amount, // Amount paid out to the creator so far
+    pub decided_at: Timestamp,   // When the milestone was last approved or rejected
+    pub dependencies: Vec<u64>,  // Milestones that must be approved before submission
     pub created_at: Timestamp,
 }
 
+/// A page of milestones returned by `list_milestones`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MilestonePage {
+    pub milestones: Vec<Milestone>,
+    pub next_cursor: Option<u64>, // Milestone id to continue from, if any remain
+}
+
+/// Escrow totals for dashboards
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EscrowSummary {
+    pub project_id: u64,
+    pub total_deposited: Amount,
+    pub released_amount: Amount,
+    pub allocated_amount: Amount, // Amount held by milestones (see `get_total_milestone_amount`)
+    pub milestone_count: u64,
+    pub pending_count: u32,
+    pub pending_amount: Amount,
+    pub submitted_count: u32,
+    pub submitted_amount: Amount,
+    pub approved_count: u32,
+    pub approved_amount: Amount,
+    pub rejected_count: u32,
+    pub disputed_count: u32,
+}
+
+/// A milestone's place in a project's roadmap
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoadmapItem {
+    pub milestone_id: u64,
+    pub status: MilestoneStatus,
+    pub amount: Amount,
+    pub released_amount: Amount,
+    pub dependencies: Vec<u64>,
+    pub unlocked: bool, // All dependencies approved
+}
+
+/// Milestone roadmap and progress of a project
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Roadmap {
+    pub project_id: u64,
+    pub items: Vec<RoadmapItem>,
+    pub approved_count: u32,
+    pub total_amount: Amount,    // Sum of all milestone amounts
+    pub released_amount: Amount, // Sum paid out across milestones
+}
+
+/// Part of an approved milestone released after a delay
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Tranche {
+    pub share: BasisPoints, // Share of the approved amount
+    pub delay: u64,         // Seconds after approval before the tranche can be released
+}
+
+/// Creator-proposed replacement of an escrow's validator set
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ValidatorChangeProposal {
+    pub project_id: u64,
+    pub validators: Vec<Address>, // Proposed validator set
+    pub approvals: Vec<Address>,  // Current validators that approved the change
+    pub proposed_at: Timestamp,
+}
+
+/// Validator staking parameters for the escrow contract
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ValidatorRegistryConfig {
+    pub stake_token: Address,    // Token validators bond
+    pub min_bond: Amount,        // Bond required to be eligible as an escrow validator
+    pub unbonding_period: u64,   // Seconds unbonded stake stays slashable before withdrawal
+    pub fee_share: BasisPoints,  // Share of each released milestone paid to approving validators
+    pub slash_rate: BasisPoints, // Share of a validator's stake slashed for an upheld dispute
+    pub treasury: Address,       // Recipient of slashed stake
+}
+
+/// Stake bonded by a validator
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ValidatorBond {
+    pub validator: Address,
+    pub bonded: Amount,
+    pub unbonding: Amount,            // Stake waiting out the unbonding period
+    pub unbonding_ends_at: Timestamp, // When `unbonding` can be withdrawn
+    pub slashable_until: Timestamp,   // End of the last dispute window on a milestone it approved
+    pub open_disputes: u32,           // Open disputes against milestones it approved
+}
+
+/// A validator's vote on a milestone
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ValidatorVote {
+    pub validator: Address,
+    pub approve: bool,
+    pub share: BasisPoints, // Share of the milestone approved (0 for a rejection)
+}
+
+/// Dispute parameters for the escrow contract
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeConfig {
+    pub bond_token: Address,       // Token challengers post their bond in
+    pub bond_amount: Amount,       // Bond required to open a dispute
+    pub window: u64,               // Seconds after a decision during which it can be disputed
+    pub arbitrators: Vec<Address>, // Panel ruling on disputes by majority
+    pub treasury: Address,         // Recipient of forfeited bonds when no arbitrator voted
+}
+
+/// An arbitrator's vote on a dispute
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ArbitratorVote {
+    pub arbitrator: Address,
+    pub uphold: bool,
+}
+
+/// Milestone dispute status
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DisputeStatus {
+    Open = 0,
+    Upheld = 1,    // Decision overturned
+    Dismissed = 2, // Decision stands
+}
+
+/// Dispute raised against a milestone decision
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Dispute {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub challenger: Address,
+    pub evidence_hash: Hash,
+    pub disputed_status: MilestoneStatus, // Decision being contested (Approved or Rejected)
+    pub bond: Amount,
+    pub uphold_votes: u32,
+    pub dismiss_votes: u32,
+    pub status: DisputeStatus,
+    pub opened_at: Timestamp,
+}
+
+/// Yield settings of an escrow whose idle balance is parked in a vault
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct YieldConfig {
+    pub vault: Address,            // Whitelisted vault holding the idle balance
+    pub backer_share: BasisPoints, // Share of accrued yield paid to backers; the rest goes to the creator
+    pub backer_recipient: Address, // Receives the backers' share (e.g. a distribution contract)
+}
+
+/// Escrow funds currently deposited in a vault
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct YieldPosition {
+    pub vault: Address,
+    pub shares: Amount,    // Vault shares held for the escrow
+    pub principal: Amount, // Escrow funds deposited and not yet withdrawn
+    pub harvested: Amount, // Yield paid out so far
+}
+
 /// Proposal status
 #[contracttype]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -1,7 +1,13 @@
 #![no_std]
-use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, String,
-    Vec,
+use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env, String, Vec};
+
+use shared::{
+    errors::SubscriptionError,
+    events::{
+        publish, PaymentFailed, PoolCreated, PoolWithdrawn, SubscriptionCancelled,
+        SubscriptionCreated, SubscriptionModified, SubscriptionPaused, SubscriptionPayment,
+        SubscriptionResumed,
+    },
 };
 
 const MIN_SUBSCRIPTION: i128 = 100;
@@ -34,6 +40,7 @@ pub enum DataKey {
     Subscription(u64, Address), // (pool_id, subscriber) -> Subscription
     SubscribersList(u64),       // pool_id -> Vec<Address>
     FailedPaymentCount(u64, Address), // (pool_id, subscriber) -> u32
+    Deposited(u64, Address),    // (pool_id, subscriber) -> i128 paid in on the subscriber's behalf
 }
 
 #[contracttype]
@@ -59,32 +66,14 @@ pub struct Subscription {
     pub failure_count: u32,
 }
 
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-#[repr(u32)]
-pub enum Error {
-    NotInitialized = 1,
-    AlreadyInitialized = 2,
-    InvalidAmount = 4,
-    InsufficientBalance = 5,
-    PoolNotFound = 7,
-    SubscriptionNotFound = 8,
-    SubscriptionNotActive = 9,
-    SubscriptionAlreadyCancelled = 10,
-    MaxFailuresReached = 11,
-    InvalidStatus = 12,
-    Unauthorized = 13,
-    PaymentNotDue = 14,
-}
-
 #[contract]
 pub struct SubscriptionPool;
 
 #[contractimpl]
 impl SubscriptionPool {
-    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), SubscriptionError> {
         if env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::AlreadyInitialized);
+            return Err(SubscriptionError::AlreadyInitialized);
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::PoolCounter, &0u64);
@@ -109,8 +98,13 @@ impl SubscriptionPool {
 
         env.storage().persistent().set(&DataKey::Pool(count), &pool);
         env.storage().instance().set(&DataKey::PoolCounter, &count);
-        env.events()
-            .publish((symbol_short!("pool_cre"), count), pool.name);
+        publish(
+            &env,
+            PoolCreated {
+                pool_id: count,
+                name: pool.name,
+            },
+        );
         count
     }
 
@@ -120,10 +114,10 @@ impl SubscriptionPool {
         subscriber: Address,
         amount: i128,
         period: SubscriptionPeriod,
-    ) -> Result<(), Error> {
+    ) -> Result<(), SubscriptionError> {
         subscriber.require_auth();
         if amount < MIN_SUBSCRIPTION {
-            return Err(Error::InvalidAmount);
+            return Err(SubscriptionError::InvalidAmount);
         }
 
         let pool_key = DataKey::Pool(pool_id);
@@ -131,11 +125,11 @@ impl SubscriptionPool {
             .storage()
             .persistent()
             .get(&pool_key)
-            .ok_or(Error::PoolNotFound)?;
+            .ok_or(SubscriptionError::PoolNotFound)?;
 
         let sub_key = DataKey::Subscription(pool_id, subscriber.clone());
         if env.storage().persistent().has(&sub_key) {
-            return Err(Error::AlreadyInitialized);
+            return Err(SubscriptionError::AlreadyInitialized);
         }
 
         let current_time = env.ledger().timestamp();
@@ -164,18 +158,23 @@ impl SubscriptionPool {
         env.storage().persistent().set(&list_key, &subscribers);
         env.storage().persistent().set(&pool_key, &pool);
 
-        env.events()
-            .publish((symbol_short!("subscr"), pool_id), subscriber);
+        publish(
+            &env,
+            SubscriptionCreated {
+                pool_id,
+                subscriber,
+            },
+        );
         Ok(())
     }
 
-    pub fn process_deposits(env: Env, pool_id: u64) -> Result<(), Error> {
+    pub fn process_deposits(env: Env, pool_id: u64) -> Result<(), SubscriptionError> {
         let pool_key = DataKey::Pool(pool_id);
         let mut pool: Pool = env
             .storage()
             .persistent()
             .get(&pool_key)
-            .ok_or(Error::PoolNotFound)?;
+            .ok_or(SubscriptionError::PoolNotFound)?;
         let token_client = token::Client::new(&env, &pool.token);
 
         let list_key = DataKey::SubscribersList(pool_id);
@@ -212,9 +211,12 @@ impl SubscriptionPool {
                     // Auto-cancel subscription after max failures
                     sub.status = SubscriptionStatus::Cancelled;
                     env.storage().persistent().set(&sub_key, &sub);
-                    env.events().publish(
-                        (symbol_short!("sub_cancl"), pool_id, subscriber_addr.clone()),
-                        symbol_short!("max_fail"),
+                    publish(
+                        &env,
+                        SubscriptionCancelled {
+                            pool_id,
+                            subscriber: subscriber_addr.clone(),
+                        },
                     );
                     continue;
                 }
@@ -233,20 +235,29 @@ impl SubscriptionPool {
                         sub.next_payment = current_time + (sub.period as u32 as u64);
                         sub.failure_count = 0; // Reset failure count on success
                         pool.total_balance += sub.amount;
+                        record_deposit(&env, pool_id, &subscriber_addr, sub.amount);
 
                         env.storage().persistent().set(&sub_key, &sub);
-                        env.events().publish(
-                            (symbol_short!("deposit"), pool_id),
-                            (subscriber_addr, sub.amount),
+                        publish(
+                            &env,
+                            SubscriptionPayment {
+                                pool_id,
+                                subscriber: subscriber_addr,
+                                amount: sub.amount,
+                            },
                         );
                     }
                     Err(_) => {
                         // Failed payment - increment failure count
                         sub.failure_count += 1;
                         env.storage().persistent().set(&sub_key, &sub);
-                        env.events().publish(
-                            (symbol_short!("pay_fail"), pool_id, subscriber_addr.clone()),
-                            sub.failure_count,
+                        publish(
+                            &env,
+                            PaymentFailed {
+                                pool_id,
+                                subscriber: subscriber_addr.clone(),
+                                failure_count: sub.failure_count,
+                            },
                         );
                     }
                 }
@@ -261,7 +272,7 @@ impl SubscriptionPool {
         env: Env,
         pool_id: u64,
         subscriber: Address,
-    ) -> Result<(), Error> {
+    ) -> Result<(), SubscriptionError> {
         subscriber.require_auth();
 
         let sub_key = DataKey::Subscription(pool_id, subscriber.clone());
@@ -269,18 +280,21 @@ impl SubscriptionPool {
             .storage()
             .persistent()
             .get(&sub_key)
-            .ok_or(Error::SubscriptionNotFound)?;
+            .ok_or(SubscriptionError::SubscriptionNotFound)?;
 
         if sub.status == SubscriptionStatus::Cancelled {
-            return Err(Error::SubscriptionAlreadyCancelled);
+            return Err(SubscriptionError::SubscriptionAlreadyCancelled);
         }
 
         sub.status = SubscriptionStatus::Cancelled;
         env.storage().persistent().set(&sub_key, &sub);
 
-        env.events().publish(
-            (symbol_short!("sub_cancl"), pool_id),
-            subscriber,
+        publish(
+            &env,
+            SubscriptionCancelled {
+                pool_id,
+                subscriber,
+            },
         );
         Ok(())
     }
@@ -291,11 +305,11 @@ impl SubscriptionPool {
         subscriber: Address,
         new_amount: i128,
         new_period: SubscriptionPeriod,
-    ) -> Result<(), Error> {
+    ) -> Result<(), SubscriptionError> {
         subscriber.require_auth();
 
         if new_amount < MIN_SUBSCRIPTION {
-            return Err(Error::InvalidAmount);
+            return Err(SubscriptionError::InvalidAmount);
         }
 
         let sub_key = DataKey::Subscription(pool_id, subscriber.clone());
@@ -303,10 +317,10 @@ impl SubscriptionPool {
             .storage()
             .persistent()
             .get(&sub_key)
-            .ok_or(Error::SubscriptionNotFound)?;
+            .ok_or(SubscriptionError::SubscriptionNotFound)?;
 
         if sub.status == SubscriptionStatus::Cancelled {
-            return Err(Error::SubscriptionAlreadyCancelled);
+            return Err(SubscriptionError::SubscriptionAlreadyCancelled);
         }
 
         sub.amount = new_amount;
@@ -316,9 +330,14 @@ impl SubscriptionPool {
 
         env.storage().persistent().set(&sub_key, &sub);
 
-        env.events().publish(
-            (symbol_short!("sub_mod"), pool_id),
-            (subscriber, new_amount, new_period as u32),
+        publish(
+            &env,
+            SubscriptionModified {
+                pool_id,
+                subscriber,
+                amount: new_amount,
+                period: new_period as u32,
+            },
         );
         Ok(())
     }
@@ -327,7 +346,7 @@ impl SubscriptionPool {
         env: Env,
         pool_id: u64,
         subscriber: Address,
-    ) -> Result<(), Error> {
+    ) -> Result<(), SubscriptionError> {
         subscriber.require_auth();
 
         let sub_key = DataKey::Subscription(pool_id, subscriber.clone());
@@ -335,18 +354,21 @@ impl SubscriptionPool {
             .storage()
             .persistent()
             .get(&sub_key)
-            .ok_or(Error::SubscriptionNotFound)?;
+            .ok_or(SubscriptionError::SubscriptionNotFound)?;
 
         if sub.status != SubscriptionStatus::Active {
-            return Err(Error::InvalidStatus);
+            return Err(SubscriptionError::InvalidStatus);
         }
 
         sub.status = SubscriptionStatus::Paused;
         env.storage().persistent().set(&sub_key, &sub);
 
-        env.events().publish(
-            (symbol_short!("sub_pause"), pool_id),
-            subscriber,
+        publish(
+            &env,
+            SubscriptionPaused {
+                pool_id,
+                subscriber,
+            },
         );
         Ok(())
     }
@@ -355,7 +377,7 @@ impl SubscriptionPool {
         env: Env,
         pool_id: u64,
         subscriber: Address,
-    ) -> Result<(), Error> {
+    ) -> Result<(), SubscriptionError> {
         subscriber.require_auth();
 
         let sub_key = DataKey::Subscription(pool_id, subscriber.clone());
@@ -363,10 +385,10 @@ impl SubscriptionPool {
             .storage()
             .persistent()
             .get(&sub_key)
-            .ok_or(Error::SubscriptionNotFound)?;
+            .ok_or(SubscriptionError::SubscriptionNotFound)?;
 
         if sub.status != SubscriptionStatus::Paused {
-            return Err(Error::InvalidStatus);
+            return Err(SubscriptionError::InvalidStatus);
         }
 
         sub.status = SubscriptionStatus::Active;
@@ -374,9 +396,53 @@ impl SubscriptionPool {
         sub.next_payment = env.ledger().timestamp() + (sub.period as u32 as u64);
         env.storage().persistent().set(&sub_key, &sub);
 
-        env.events().publish(
-            (symbol_short!("sub_resum"), pool_id),
-            subscriber,
+        publish(
+            &env,
+            SubscriptionResumed {
+                pool_id,
+                subscriber,
+            },
+        );
+        Ok(())
+    }
+
+    /// Pay `amount` into a pool on `subscriber`'s behalf, outside any
+    /// subscription schedule. The amount is recorded against the subscriber
+    /// (see `get_deposited`).
+    pub fn deposit_for(
+        env: Env,
+        pool_id: u64,
+        payer: Address,
+        subscriber: Address,
+        amount: i128,
+    ) -> Result<(), SubscriptionError> {
+        payer.require_auth();
+
+        if amount <= 0 {
+            return Err(SubscriptionError::InvalidAmount);
+        }
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(SubscriptionError::PoolNotFound)?;
+
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&payer, &env.current_contract_address(), &amount);
+
+        pool.total_balance += amount;
+        env.storage().persistent().set(&pool_key, &pool);
+        record_deposit(&env, pool_id, &subscriber, amount);
+
+        publish(
+            &env,
+            SubscriptionPayment {
+                pool_id,
+                subscriber,
+                amount,
+            },
         );
         Ok(())
     }
@@ -386,7 +452,7 @@ impl SubscriptionPool {
         pool_id: u64,
         subscriber: Address,
         amount: i128,
-    ) -> Result<(), Error> {
+    ) -> Result<(), SubscriptionError> {
         subscriber.require_auth();
 
         let pool_key = DataKey::Pool(pool_id);
@@ -394,10 +460,10 @@ impl SubscriptionPool {
             .storage()
             .persistent()
             .get(&pool_key)
-            .ok_or(Error::PoolNotFound)?;
+            .ok_or(SubscriptionError::PoolNotFound)?;
 
         if amount <= 0 || amount > pool.total_balance {
-            return Err(Error::InsufficientBalance);
+            return Err(SubscriptionError::InsufficientBalance);
         }
 
         let token_client = token::Client::new(&env, &pool.token);
@@ -406,28 +472,54 @@ impl SubscriptionPool {
         pool.total_balance -= amount;
         env.storage().persistent().set(&pool_key, &pool);
 
-        env.events()
-            .publish((symbol_short!("withdraw"), pool_id), subscriber);
+        publish(
+            &env,
+            PoolWithdrawn {
+                pool_id,
+                subscriber,
+                amount,
+            },
+        );
         Ok(())
     }
 
-    pub fn get_pool(env: Env, pool_id: u64) -> Result<Pool, Error> {
+    pub fn get_pool(env: Env, pool_id: u64) -> Result<Pool, SubscriptionError> {
         env.storage()
             .persistent()
             .get(&DataKey::Pool(pool_id))
-            .ok_or(Error::PoolNotFound)
+            .ok_or(SubscriptionError::PoolNotFound)
+    }
+
+    pub fn get_pool_token(env: Env, pool_id: u64) -> Result<Address, SubscriptionError> {
+        Ok(Self::get_pool(env, pool_id)?.token)
+    }
+
+    /// Total paid into a pool on `subscriber`'s behalf, by subscription
+    /// payments and `deposit_for`
+    pub fn get_deposited(env: Env, pool_id: u64, subscriber: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Deposited(pool_id, subscriber))
+            .unwrap_or(0)
     }
 
     pub fn get_subscription(
         env: Env,
         pool_id: u64,
         subscriber: Address,
-    ) -> Result<Subscription, Error> {
+    ) -> Result<Subscription, SubscriptionError> {
         env.storage()
             .persistent()
             .get(&DataKey::Subscription(pool_id, subscriber))
-            .ok_or(Error::SubscriptionNotFound)
+            .ok_or(SubscriptionError::SubscriptionNotFound)
     }
 }
 
+/// Add `amount` to the total paid into a pool on `subscriber`'s behalf
+fn record_deposit(env: &Env, pool_id: u64, subscriber: &Address, amount: i128) {
+    let key = DataKey::Deposited(pool_id, subscriber.clone());
+    let deposited: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(deposited + amount));
+}
+
 mod test;
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod test {
     use crate::{SubscriptionPeriod, SubscriptionPool, SubscriptionPoolClient, SubscriptionStatus};
     use soroban_sdk::testutils::{Address as _, Ledger};
@@ -116,6 +117,7 @@ mod test {
         ctx.contract
             .subscribe(&pool_id, &ctx.user_1, &2000, &SubscriptionPeriod::Weekly);
         ctx.contract.process_deposits(&pool_id);
+        assert_eq!(ctx.contract.get_deposited(&pool_id, &ctx.user_1), 2000);
 
         // Withdraw 1000
         ctx.contract.withdraw(&pool_id, &ctx.user_1, &1000);
@@ -124,6 +126,28 @@ mod test {
         assert_eq!(ctx.token.balance(&ctx.contract.address), 1000);
     }
 
+    #[test]
+    fn test_deposit_for() {
+        let ctx = TestContext::setup();
+        let pool_id = ctx
+            .contract
+            .create_pool(&String::from_str(&ctx.env, "Reinvest"), &ctx.token.address);
+        assert_eq!(ctx.contract.get_pool_token(&pool_id), ctx.token.address);
+
+        ctx.contract
+            .deposit_for(&pool_id, &ctx.user_1, &ctx.user_2, &700);
+
+        assert_eq!(ctx.token.balance(&ctx.user_1), 9300);
+        assert_eq!(ctx.token.balance(&ctx.user_2), 10_000);
+        assert_eq!(ctx.contract.get_pool(&pool_id).total_balance, 700);
+        assert_eq!(ctx.contract.get_deposited(&pool_id, &ctx.user_2), 700);
+        assert_eq!(ctx.contract.get_deposited(&pool_id, &ctx.user_1), 0);
+        assert!(ctx
+            .contract
+            .try_deposit_for(&pool_id, &ctx.user_1, &ctx.user_2, &0)
+            .is_err());
+    }
+
     // ==================== CANCELLATION TESTS ====================
 
     #[test]